@@ -0,0 +1,222 @@
+use crate::gui::*;
+use crate::pak::*;
+use crate::pfb::*;
+use crate::scn::*;
+use crate::suffix::SUFFIX_MAP;
+use crate::user::*;
+use crate::uvs::*;
+use anyhow::*;
+use serde::*;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::{Cursor, Read, Seek};
+use std::str::FromStr;
+
+fn normalize_path(path: &str) -> String {
+    path.trim_start_matches('@').replace('\\', "/")
+}
+
+// Slot strings also hold plain text, so only those with a known resource extension are kept
+fn is_resource_path(path: &str) -> bool {
+    path.rfind('.')
+        .map_or(false, |dot| SUFFIX_MAP.contains_key(&path[dot + 1..]))
+}
+
+fn gui_references(gui: &Gui) -> Vec<String> {
+    let play_objects = std::iter::once(&gui.root).chain(
+        gui.controls
+            .iter()
+            .flat_map(|control| &control.play_objects),
+    );
+    play_objects
+        .flat_map(|object| object.properties.iter().chain(&object.variables))
+        .filter_map(|field| match &field.value {
+            FieldValue::Resource(path) | FieldValue::Texture(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Collects the paths a file refers to, or `None` for file types without references.
+pub fn file_references(file: &[u8]) -> Result<Option<Vec<String>>> {
+    if file.len() < 4 {
+        return Ok(None);
+    }
+    let paths = match &file[0..4] {
+        b"SCN\0" => {
+            let scn = Scn::new(Cursor::new(file))?;
+            scn.resource_a_names
+                .into_iter()
+                .chain(scn.resource_b_names)
+                .chain(scn.children.into_iter().map(|child| child.name))
+                .chain(
+                    scn.rsz
+                        .slot_strings
+                        .into_iter()
+                        .map(|s| s.string)
+                        .filter(|s| is_resource_path(s)),
+                )
+                .collect()
+        }
+        b"PFB\0" => {
+            let pfb = Pfb::new(Cursor::new(file))?;
+            pfb.resource_names
+                .into_iter()
+                .chain(pfb.children.into_iter().map(|child| child.name))
+                .chain(
+                    pfb.rsz
+                        .slot_strings
+                        .into_iter()
+                        .map(|s| s.string)
+                        .filter(|s| is_resource_path(s)),
+                )
+                .collect()
+        }
+        b"USR\0" => {
+            let user = User::new(Cursor::new(file))?;
+            user.resource_names
+                .into_iter()
+                .chain(user.children.into_iter().map(|child| child.name))
+                .chain(
+                    user.rsz
+                        .slot_strings
+                        .into_iter()
+                        .map(|s| s.string)
+                        .filter(|s| is_resource_path(s)),
+                )
+                .collect()
+        }
+        b".SVU" => {
+            let uvs = Uvs::new(Cursor::new(file))?;
+            uvs.textures.into_iter().map(|t| t.path).collect()
+        }
+        _ if file.len() >= 8 && &file[4..8] == b"GUIR" => {
+            gui_references(&Gui::new(Cursor::new(file))?)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(paths))
+}
+
+/// Resource dependency graph, keyed by the referencing path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DepGraph {
+    pub edges: BTreeMap<String, BTreeSet<String>>,
+    /// Referenced paths that are not found in the pak
+    pub missing: BTreeSet<String>,
+    /// Paths that are found but failed to parse
+    pub broken: BTreeMap<String, String>,
+}
+
+impl DepGraph {
+    /// Builds the graph from the given root paths, following every reference found.
+    pub fn build<F: Read + Seek>(
+        pak: &mut PakReader<F>,
+        roots: impl IntoIterator<Item = String>,
+    ) -> Result<DepGraph> {
+        let mut graph = DepGraph::default();
+        let mut visited = BTreeSet::new();
+        let mut queue: VecDeque<String> = roots.into_iter().map(|p| normalize_path(&p)).collect();
+
+        while let Some(path) = queue.pop_front() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            let index = match pak.find_file_i18n(&path) {
+                Ok(indexs) => match indexs.first() {
+                    Some(index) => index.index,
+                    None => {
+                        graph.missing.insert(path);
+                        continue;
+                    }
+                },
+                Err(_) => {
+                    graph.missing.insert(path);
+                    continue;
+                }
+            };
+            let file = pak.read_file(index)?;
+            let references = match file_references(&file) {
+                Ok(Some(references)) => references,
+                Ok(None) => vec![],
+                Err(e) => {
+                    graph.broken.insert(path, format!("{:#}", e));
+                    continue;
+                }
+            };
+            let references: BTreeSet<String> =
+                references.iter().map(|p| normalize_path(p)).collect();
+            queue.extend(references.iter().cloned());
+            graph.edges.insert(path, references);
+        }
+
+        Ok(graph)
+    }
+
+    /// Lists files that directly reference `path`
+    pub fn referenced_by(&self, path: &str) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| to.contains(path))
+            .map(|(from, _)| from.as_str())
+            .collect()
+    }
+
+    /// Lists files that `path` pulls in, optionally including indirect ones
+    pub fn dependencies(&self, path: &str, recursive: bool) -> BTreeSet<&str> {
+        let mut result = BTreeSet::new();
+        let mut stack = vec![path];
+        while let Some(current) = stack.pop() {
+            for dep in self.edges.get(current).into_iter().flatten() {
+                if result.insert(dep.as_str()) && recursive {
+                    stack.push(dep);
+                }
+            }
+        }
+        result
+    }
+
+    /// Lists files that depend on `path`, optionally including indirect ones
+    pub fn dependents(&self, path: &str, recursive: bool) -> BTreeSet<&str> {
+        let mut result = BTreeSet::new();
+        let mut stack = vec![path];
+        while let Some(current) = stack.pop() {
+            for from in self.referenced_by(current) {
+                if result.insert(from) && recursive {
+                    stack.push(from);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph {\n".to_owned();
+        for (from, to) in &self.edges {
+            for to in to {
+                dot += &format!("    {:?} -> {:?};\n", from, to);
+            }
+        }
+        for missing in &self.missing {
+            dot += &format!("    {:?} [style=dashed];\n", missing);
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DepGraphFormat {
+    Dot,
+    Json,
+}
+
+impl FromStr for DepGraphFormat {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<DepGraphFormat> {
+        Ok(match s {
+            "dot" => DepGraphFormat::Dot,
+            "json" => DepGraphFormat::Json,
+            _ => bail!("Unknown graph format {}", s),
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use super::pedia::*;
+use crate::rsz::*;
+use serde::*;
+
+#[derive(Debug, Serialize)]
+pub struct EnrageSummary {
+    /// Accumulated anger needed to enrage
+    pub thresholds: Vec<i32>,
+    /// Raw anger timer and stay extension. Their unit is not known
+    pub duration: i32,
+    pub duration_extension: f32,
+    pub attack_multiplier: f32,
+    pub defense_multiplier: f32,
+    pub speed_multiplier: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HpThresholds {
+    pub enemy_level: EnemyLevel,
+    pub limp_hp_percent: f32,
+    pub capture_hp_percent: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SleepRecovery {
+    pub duration_sec: f32,
+    pub recover_hp_percent: f32,
+    pub in_combat: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExhaustSummary {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub threshold_increment: f32,
+    pub max_threshold: f32,
+    pub decay: f32,
+    pub decay_interval_sec: f32,
+    pub duration_sec: f32,
+    pub stamina_drain: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonsterBehavior {
+    pub id: u32,
+    pub sub_id: u32,
+    pub enrage: EnrageSummary,
+    pub hp_thresholds: Vec<HpThresholds>,
+    pub sleep_recovery: SleepRecovery,
+    pub exhaust: ExhaustSummary,
+}
+
+pub fn monster_behavior(monster: &Monster) -> MonsterBehavior {
+    let anger = &monster.anger_data;
+    let tune = &monster.data_tune;
+    let stamina = &monster.condition_damage_data.stamina_data;
+    MonsterBehavior {
+        id: monster.id,
+        sub_id: monster.sub_id,
+        enrage: EnrageSummary {
+            thresholds: anger.data_info.iter().map(|data| data.val).collect(),
+            duration: anger.timer,
+            duration_extension: anger.anger_stay_add_sec,
+            attack_multiplier: anger.atk_rate,
+            defense_multiplier: anger.def_rate,
+            speed_multiplier: anger.mot_rate,
+        },
+        hp_thresholds: vec![
+            HpThresholds {
+                enemy_level: EnemyLevel::Village,
+                limp_hp_percent: tune.dying_village_hp_vital_rate,
+                capture_hp_percent: tune.capture_village_hp_vital_rate,
+            },
+            HpThresholds {
+                enemy_level: EnemyLevel::Low,
+                limp_hp_percent: tune.dying_low_level_hp_vital_rate,
+                capture_hp_percent: tune.capture_low_level_hp_vital_rate,
+            },
+            HpThresholds {
+                enemy_level: EnemyLevel::High,
+                limp_hp_percent: tune.dying_high_level_hp_vital_rate,
+                capture_hp_percent: tune.capture_high_level_hp_vital_rate,
+            },
+        ],
+        sleep_recovery: SleepRecovery {
+            duration_sec: tune.self_sleep_time,
+            recover_hp_percent: tune.self_sleep_recover_hp_vital_rate,
+            in_combat: tune.in_combat_self_sleep_flag,
+        },
+        exhaust: ExhaustSummary {
+            enabled: matches!(
+                monster.condition_damage_data.use_stamina,
+                ConditionDamageDataUsed::Use
+            ),
+            threshold: stamina.base.default_stock.default_limit,
+            threshold_increment: stamina.base.default_stock.add_limit,
+            max_threshold: stamina.base.default_stock.max_limit,
+            decay: stamina.base.default_stock.sub_value,
+            decay_interval_sec: stamina.base.default_stock.sub_interval,
+            duration_sec: stamina.base.active_time,
+            stamina_drain: stamina.sub_stamina,
+        },
+    }
+}
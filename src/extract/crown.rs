@@ -0,0 +1,159 @@
+use super::pedia::*;
+use crate::rsz::*;
+use anyhow::*;
+use serde::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Crown chances in percent. `silver` excludes sizes that are also gold.
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct CrownOdds {
+    pub mini: f32,
+    pub silver: f32,
+    pub gold: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrownKind {
+    Mini,
+    Silver,
+    Gold,
+}
+
+impl FromStr for CrownKind {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<CrownKind> {
+        Ok(match s {
+            "mini" => CrownKind::Mini,
+            "silver" => CrownKind::Silver,
+            "gold" => CrownKind::Gold,
+            _ => bail!("Unknown crown kind {}", s),
+        })
+    }
+}
+
+impl CrownOdds {
+    pub fn get(&self, kind: CrownKind) -> f32 {
+        match kind {
+            CrownKind::Mini => self.mini,
+            CrownKind::Silver => self.silver,
+            CrownKind::Gold => self.gold,
+        }
+    }
+}
+
+pub fn crown_odds(
+    size: &SizeInfo,
+    size_dist: &[ScaleAndRateData],
+    base_scale: u8,
+) -> Option<CrownOdds> {
+    if size.no_size_scale {
+        return None;
+    }
+    let total: u32 = size_dist.iter().map(|sample| sample.rate).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut mini = 0;
+    let mut silver = 0;
+    let mut gold = 0;
+    for sample in size_dist {
+        let scale = sample.scale * f32::from(base_scale) / 100.0;
+        if scale <= size.small_boarder {
+            mini += sample.rate;
+        }
+        if scale >= size.king_boarder {
+            gold += sample.rate;
+        } else if scale >= size.big_boarder {
+            silver += sample.rate;
+        }
+    }
+
+    let percent = |rate: u32| rate as f32 * 100.0 / total as f32;
+    Some(CrownOdds {
+        mini: percent(mini),
+        silver: percent(silver),
+        gold: percent(gold),
+    })
+}
+
+pub fn quest_crown_odds(
+    pedia_ex: &PediaEx<'_>,
+    enemy_param: &SharedEnemyParam,
+    em_type: EmTypes,
+    index: usize,
+) -> Option<CrownOdds> {
+    let scale_tbl = enemy_param.scale_tbl.get(index)?;
+    let base_scale = *enemy_param.scale.get(index)?;
+    let size = pedia_ex.sizes.get(&em_type)?;
+    let size_dist = pedia_ex.size_dists.get(scale_tbl)?;
+    crown_odds(size, size_dist, base_scale)
+}
+
+pub struct CrownRank<'a> {
+    pub quest: &'a Quest,
+    pub index: usize,
+    pub odds: CrownOdds,
+}
+
+/// Lists every quest appearance of each large monster, keyed by monster ID.
+pub fn crown_rankings<'a>(pedia_ex: &'a PediaEx<'_>) -> BTreeMap<u32, Vec<CrownRank<'a>>> {
+    let mut rankings: BTreeMap<u32, Vec<CrownRank>> = BTreeMap::new();
+    for quest in &pedia_ex.quests {
+        let enemy_param = if let Some(enemy_param) = &quest.enemy_param {
+            &enemy_param.param
+        } else {
+            continue;
+        };
+        for (index, &em_type) in quest.param.boss_em_type.iter().enumerate() {
+            let id = match em_type {
+                EmTypes::Em(0) | EmTypes::Ems(_) => continue,
+                EmTypes::Em(id) => id,
+            };
+            if let Some(odds) = quest_crown_odds(pedia_ex, enemy_param, em_type, index) {
+                rankings
+                    .entry(id)
+                    .or_default()
+                    .push(CrownRank { quest, index, odds })
+            }
+        }
+    }
+    rankings
+}
+
+/// Whether `crown_rankings` has an entry, and thus a crown page, for the monster
+pub fn has_crown_ranking(pedia_ex: &PediaEx<'_>, em_type: EmTypes) -> bool {
+    pedia_ex.quests.iter().any(|quest| {
+        let enemy_param = if let Some(enemy_param) = &quest.enemy_param {
+            &enemy_param.param
+        } else {
+            return false;
+        };
+        quest
+            .param
+            .boss_em_type
+            .iter()
+            .enumerate()
+            .any(|(index, &boss)| {
+                boss == em_type && quest_crown_odds(pedia_ex, enemy_param, em_type, index).is_some()
+            })
+    })
+}
+
+/// Orders the quests by the chance of the given crown, dropping quests where it is impossible.
+pub fn sort_crown_ranking<'a, 'b>(
+    ranking: &'b [CrownRank<'a>],
+    kind: CrownKind,
+) -> Vec<&'b CrownRank<'a>> {
+    let mut sorted: Vec<_> = ranking.iter().filter(|r| r.odds.get(kind) > 0.0).collect();
+    sorted.sort_by(|a, b| {
+        b.odds
+            .get(kind)
+            .partial_cmp(&a.odds.get(kind))
+            .unwrap_or(Ordering::Equal)
+            .then(a.quest.param.quest_no.cmp(&b.quest.param.quest_no))
+    });
+    sorted
+}
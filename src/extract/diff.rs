@@ -0,0 +1,388 @@
+use super::gen_website::LANGUAGE_MAP;
+use super::pedia::*;
+use crate::msg::*;
+use crate::rsz::*;
+use anyhow::*;
+use serde::*;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const ENGLISH: usize = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonsterDiff {
+    pub id: u32,
+    pub sub_id: u32,
+    pub is_large: bool,
+    pub name: Option<String>,
+    pub hp: Option<(i32, i32)>,
+    pub hitzone_changes: Vec<FieldChange>,
+    pub condition_changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonsterSummary {
+    pub id: u32,
+    pub sub_id: u32,
+    pub is_large: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestSummary {
+    pub quest_no: i32,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArmorSummary {
+    pub id: String,
+    pub series: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArmorDiff {
+    pub armor: ArmorSummary,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemPriceDiff {
+    pub id: ItemId,
+    pub name: String,
+    pub sell_price: (u32, u32),
+    pub buy_price: (u32, u32),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MsgDiff {
+    pub file: String,
+    pub entry: String,
+    pub language: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PediaDiff {
+    pub monsters_added: Vec<MonsterSummary>,
+    pub monsters_removed: Vec<MonsterSummary>,
+    pub monsters: Vec<MonsterDiff>,
+    pub quests_added: Vec<QuestSummary>,
+    pub quests_removed: Vec<QuestSummary>,
+    pub armors_added: Vec<ArmorSummary>,
+    pub armors_removed: Vec<ArmorSummary>,
+    pub armors: Vec<ArmorDiff>,
+    pub item_prices: Vec<ItemPriceDiff>,
+    pub msgs: Vec<MsgDiff>,
+}
+
+fn diff_json(field: String, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                diff_json(
+                    format!("{}.{}", field, key),
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                diff_json(format!("{}[{}]", field, i), old, new, changes);
+            }
+        }
+        (old, new) => {
+            if old != new {
+                changes.push(FieldChange {
+                    field,
+                    old: old.clone(),
+                    new: new.clone(),
+                })
+            }
+        }
+    }
+}
+
+fn diff_fields<T: Serialize + ?Sized>(name: &str, old: &T, new: &T) -> Result<Vec<FieldChange>> {
+    let mut changes = vec![];
+    diff_json(
+        name.to_owned(),
+        &serde_json::to_value(old)?,
+        &serde_json::to_value(new)?,
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+fn english(msg: &MsgEntry) -> String {
+    msg.content.get(ENGLISH).cloned().unwrap_or_default()
+}
+
+fn monster_map(pedia: &Pedia) -> BTreeMap<(bool, u32, u32), &Monster> {
+    pedia
+        .monsters
+        .iter()
+        .map(|m| ((true, m.id, m.sub_id), m))
+        .chain(
+            pedia
+                .small_monsters
+                .iter()
+                .map(|m| ((false, m.id, m.sub_id), m)),
+        )
+        .collect()
+}
+
+fn monster_name(pedia: &Pedia, monster: &Monster) -> Option<String> {
+    let enemy_type = monster.boss_init_set_data.as_ref()?.enemy_type;
+    let entry = pedia
+        .monster_names
+        .get_entry(&format!("EnemyIndex{:03}", enemy_type))?;
+    Some(english(entry))
+}
+
+fn diff_monsters(old: &Pedia, new: &Pedia, diff: &mut PediaDiff) -> Result<()> {
+    let old_monsters = monster_map(old);
+    let new_monsters = monster_map(new);
+
+    for (&(is_large, id, sub_id), new_monster) in &new_monsters {
+        let old_monster = if let Some(old_monster) = old_monsters.get(&(is_large, id, sub_id)) {
+            old_monster
+        } else {
+            diff.monsters_added.push(MonsterSummary {
+                id,
+                sub_id,
+                is_large,
+            });
+            continue;
+        };
+
+        let old_hp = old_monster.data_tune.base_hp_vital;
+        let new_hp = new_monster.data_tune.base_hp_vital;
+        let hp = if old_hp != new_hp {
+            Some((old_hp, new_hp))
+        } else {
+            None
+        };
+        let hitzone_changes = diff_fields("meat", &old_monster.meat_data, &new_monster.meat_data)?;
+        let condition_changes = diff_fields(
+            "condition",
+            &old_monster.condition_damage_data,
+            &new_monster.condition_damage_data,
+        )?;
+
+        if hp.is_none() && hitzone_changes.is_empty() && condition_changes.is_empty() {
+            continue;
+        }
+
+        diff.monsters.push(MonsterDiff {
+            id,
+            sub_id,
+            is_large,
+            name: monster_name(new, new_monster),
+            hp,
+            hitzone_changes,
+            condition_changes,
+        })
+    }
+
+    for &(is_large, id, sub_id) in old_monsters.keys() {
+        if !new_monsters.contains_key(&(is_large, id, sub_id)) {
+            diff.monsters_removed.push(MonsterSummary {
+                id,
+                sub_id,
+                is_large,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn diff_quests(old: &PediaEx, new: &PediaEx, diff: &mut PediaDiff) {
+    let summary = |quest: &Quest| QuestSummary {
+        quest_no: quest.param.quest_no,
+        name: quest.name.as_ref().map(english),
+    };
+    let old_quests: BTreeMap<i32, &Quest> =
+        old.quests.iter().map(|q| (q.param.quest_no, q)).collect();
+    let new_quests: BTreeMap<i32, &Quest> =
+        new.quests.iter().map(|q| (q.param.quest_no, q)).collect();
+
+    for (quest_no, &quest) in &new_quests {
+        if !old_quests.contains_key(quest_no) {
+            diff.quests_added.push(summary(quest));
+        }
+    }
+    for (quest_no, &quest) in &old_quests {
+        if !new_quests.contains_key(quest_no) {
+            diff.quests_removed.push(summary(quest));
+        }
+    }
+}
+
+fn armor_map<'a>(pedia_ex: &'a PediaEx) -> BTreeMap<String, &'a Armor<'a>> {
+    pedia_ex
+        .armors
+        .iter()
+        .flat_map(|series| series.pieces.iter().flatten())
+        .map(|armor| (format!("{:?}", armor.data.pl_armor_id), armor))
+        .collect()
+}
+
+fn armor_summary(id: &str, armor: &Armor) -> ArmorSummary {
+    ArmorSummary {
+        id: id.to_owned(),
+        series: armor.data.series.0,
+        name: english(&armor.name),
+    }
+}
+
+fn diff_armors(old: &PediaEx, new: &PediaEx, diff: &mut PediaDiff) -> Result<()> {
+    let old_armors = armor_map(old);
+    let new_armors = armor_map(new);
+
+    for (id, new_armor) in &new_armors {
+        let old_armor = if let Some(old_armor) = old_armors.get(id) {
+            old_armor
+        } else {
+            diff.armors_added.push(armor_summary(id, new_armor));
+            continue;
+        };
+
+        let changes = diff_fields("armor", old_armor.data, new_armor.data)?;
+        if !changes.is_empty() {
+            diff.armors.push(ArmorDiff {
+                armor: armor_summary(id, new_armor),
+                changes,
+            })
+        }
+    }
+
+    for (id, old_armor) in &old_armors {
+        if !new_armors.contains_key(id) {
+            diff.armors_removed.push(armor_summary(id, old_armor));
+        }
+    }
+
+    Ok(())
+}
+
+fn diff_items(old: &PediaEx, new: &PediaEx, diff: &mut PediaDiff) {
+    for (id, new_item) in &new.items {
+        let old_item = if let Some(old_item) = old.items.get(id) {
+            old_item
+        } else {
+            continue;
+        };
+        let sell_price = (old_item.param.sell_price, new_item.param.sell_price);
+        let buy_price = (old_item.param.buy_price, new_item.param.buy_price);
+        if sell_price.0 != sell_price.1 || buy_price.0 != buy_price.1 {
+            diff.item_prices.push(ItemPriceDiff {
+                id: *id,
+                name: english(&new_item.name),
+                sell_price,
+                buy_price,
+            })
+        }
+    }
+}
+
+fn msg_files(pedia: &Pedia) -> Vec<(&'static str, &Msg)> {
+    vec![
+        ("monster_names", &pedia.monster_names),
+        ("monster_aliases", &pedia.monster_aliases),
+        ("hunter_note", &pedia.hunter_note_msg),
+        ("quest_hall", &pedia.quest_hall_msg),
+        ("quest_village", &pedia.quest_village_msg),
+        ("quest_tutorial", &pedia.quest_tutorial_msg),
+        ("quest_arena", &pedia.quest_arena_msg),
+        ("armor_head_name", &pedia.armor_head_name_msg),
+        ("armor_chest_name", &pedia.armor_chest_name_msg),
+        ("armor_arm_name", &pedia.armor_arm_name_msg),
+        ("armor_waist_name", &pedia.armor_waist_name_msg),
+        ("armor_leg_name", &pedia.armor_leg_name_msg),
+        ("armor_series_name", &pedia.armor_series_name_msg),
+        ("player_skill_detail", &pedia.player_skill_detail_msg),
+        ("player_skill_explain", &pedia.player_skill_explain_msg),
+        ("player_skill_name", &pedia.player_skill_name_msg),
+        ("items_name", &pedia.items_name_msg),
+        ("material_category", &pedia.material_category_msg),
+    ]
+}
+
+fn diff_msgs(old: &Pedia, new: &Pedia, diff: &mut PediaDiff) {
+    for ((file, old_msg), (_, new_msg)) in msg_files(old).into_iter().zip(msg_files(new)) {
+        let old_entries: HashMap<&str, &MsgEntry> = old_msg
+            .entries
+            .iter()
+            .map(|e| (e.name.as_str(), e))
+            .collect();
+        let new_entries: HashMap<&str, &MsgEntry> = new_msg
+            .entries
+            .iter()
+            .map(|e| (e.name.as_str(), e))
+            .collect();
+        let names: BTreeSet<&str> = old_entries
+            .keys()
+            .chain(new_entries.keys())
+            .cloned()
+            .collect();
+
+        for name in names {
+            let old_entry = old_entries.get(name);
+            let new_entry = new_entries.get(name);
+            for (language, language_name) in LANGUAGE_MAP.iter().enumerate() {
+                let language_name = if let Some(language_name) = language_name {
+                    *language_name
+                } else {
+                    continue;
+                };
+                let old = old_entry.and_then(|e| e.content.get(language)).cloned();
+                let new = new_entry.and_then(|e| e.content.get(language)).cloned();
+                if old != new {
+                    diff.msgs.push(MsgDiff {
+                        file: file.to_owned(),
+                        entry: name.to_owned(),
+                        language: language_name.to_owned(),
+                        old,
+                        new,
+                    })
+                }
+            }
+        }
+    }
+}
+
+pub fn gen_diff(old: &Pedia, old_ex: &PediaEx, new: &Pedia, new_ex: &PediaEx) -> Result<PediaDiff> {
+    let mut diff = PediaDiff {
+        monsters_added: vec![],
+        monsters_removed: vec![],
+        monsters: vec![],
+        quests_added: vec![],
+        quests_removed: vec![],
+        armors_added: vec![],
+        armors_removed: vec![],
+        armors: vec![],
+        item_prices: vec![],
+        msgs: vec![],
+    };
+
+    diff_monsters(old, new, &mut diff)?;
+    diff_quests(old_ex, new_ex, &mut diff);
+    diff_armors(old_ex, new_ex, &mut diff)?;
+    diff_items(old_ex, new_ex, &mut diff);
+    diff_msgs(old, new, &mut diff);
+
+    Ok(diff)
+}
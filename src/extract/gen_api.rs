@@ -0,0 +1,1033 @@
+use super::gen_item::item_page;
+use super::gen_map::map_page;
+use super::gen_pedia::item_icon_file_name;
+use super::gen_skill::skill_page;
+use super::gen_website::{quest_level_text, LANGUAGE_CODES, LANGUAGE_MAP};
+use super::manifest::*;
+use super::pedia::*;
+use crate::msg::*;
+use crate::rsz::*;
+use anyhow::*;
+use serde::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::create_dir_all;
+use std::path::*;
+
+// Bump this on breaking changes to the JSON layout, so that API users can tell
+const API_VERSION: u32 = 1;
+
+type Names<'a> = BTreeMap<&'static str, &'a str>;
+
+fn names(msg: &MsgEntry) -> Names<'_> {
+    LANGUAGE_CODES
+        .iter()
+        .enumerate()
+        .filter_map(|(i, code)| {
+            let content = msg.content.get(i)?;
+            if content.is_empty() {
+                return None;
+            }
+            Some(((*code)?, content.as_str()))
+        })
+        .collect()
+}
+
+pub fn api_index_url() -> String {
+    format!("/api/v{}/index.json", API_VERSION)
+}
+
+fn api_url(folder: &str, id: &str) -> String {
+    format!("/api/v{}/{}/{}.json", API_VERSION, folder, id)
+}
+
+#[derive(Serialize, Clone)]
+struct Reference<'a> {
+    id: String,
+    url: String,
+    page: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<Names<'a>>,
+}
+
+impl<'a> Reference<'a> {
+    fn new(folder: &str, id: String, name: Option<&'a MsgEntry>) -> Reference<'a> {
+        Reference {
+            url: api_url(folder, &id),
+            page: format!("/{}/{}.html", folder, id),
+            id,
+            name: name.map(names),
+        }
+    }
+}
+
+fn monster_name<'a>(pedia: &'a Pedia, monster: &Monster) -> Option<&'a MsgEntry> {
+    let enemy_type = monster.boss_init_set_data.as_ref()?.enemy_type;
+    pedia
+        .monster_names
+        .get_entry(&format!("EnemyIndex{:03}", enemy_type))
+}
+
+fn monster_ref(pedia: &Pedia, em_type: EmTypes) -> Reference<'_> {
+    let (folder, id, monsters) = match em_type {
+        EmTypes::Em(id) => ("monster", id, &pedia.monsters),
+        EmTypes::Ems(id) => ("small-monster", id, &pedia.small_monsters),
+    };
+    let name = monsters
+        .iter()
+        .find(|m| (m.id | m.sub_id << 8) == id)
+        .and_then(|monster| monster_name(pedia, monster));
+    Reference::new(folder, format!("{:03}_{:02}", id & 0xFF, id >> 8), name)
+}
+
+fn quest_ref(quest: &Quest) -> Reference<'_> {
+    Reference::new(
+        "quest",
+        format!("{:06}", quest.param.quest_no),
+        quest.name.as_ref(),
+    )
+}
+
+fn armor_ref<'a>(series: &'a ArmorSeries) -> Reference<'a> {
+    Reference::new(
+        "armor",
+        format!("{:03}", series.series.armor_series.0),
+        series.name.as_ref(),
+    )
+}
+
+fn skill_ref<'a>(pedia_ex: &'a PediaEx, id: PlEquipSkillId) -> Reference<'a> {
+    Reference::new(
+        "skill",
+        skill_page(id).trim_end_matches(".html").to_owned(),
+        pedia_ex.skills.get(&id).map(|skill| &skill.name),
+    )
+}
+
+fn item_ref<'a>(pedia_ex: &'a PediaEx, id: ItemId) -> Reference<'a> {
+    Reference::new(
+        "item",
+        item_page(id).trim_end_matches(".html").to_owned(),
+        pedia_ex.items.get(&id).map(|item| &item.name),
+    )
+}
+
+#[derive(Serialize)]
+struct ItemCount<'a> {
+    item: Reference<'a>,
+    num: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    probability: Option<u32>,
+}
+
+fn item_counts<'a>(
+    pedia_ex: &'a PediaEx,
+    item: &[ItemId],
+    num: &[u32],
+    probability: Option<&[u32]>,
+) -> Vec<ItemCount<'a>> {
+    item.iter()
+        .zip(num)
+        .enumerate()
+        .filter(|&(_, (&item, _))| item != ItemId::None && item != ItemId::Null)
+        .map(|(i, (&item, &num))| ItemCount {
+            item: item_ref(pedia_ex, item),
+            num,
+            probability: probability.and_then(|p| p.get(i).copied()),
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct RewardGroup<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pop_type: Option<EnemyRewardPopTypes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part: Option<Names<'a>>,
+    items: Vec<ItemCount<'a>>,
+}
+
+fn grouped_rewards<'a, T: Copy + PartialEq>(
+    pedia_ex: &'a PediaEx,
+    group: &[T],
+    none: T,
+    item: &[ItemId],
+    num: &[u32],
+    probability: &[u32],
+) -> Vec<(T, Vec<ItemCount<'a>>)> {
+    group
+        .iter()
+        .zip(item.chunks(10))
+        .zip(num.chunks(10))
+        .zip(probability.chunks(10))
+        .filter(|&(((&group, _), _), _)| group != none)
+        .map(|(((&group, item), num), probability)| {
+            (group, item_counts(pedia_ex, item, num, Some(probability)))
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct MonsterRewards<'a> {
+    rank: QuestRank,
+    target: Vec<ItemCount<'a>>,
+    carves: Vec<RewardGroup<'a>>,
+    capture: Vec<ItemCount<'a>>,
+    parts_break: Vec<RewardGroup<'a>>,
+    drop: Vec<RewardGroup<'a>>,
+    buddy: Vec<ItemCount<'a>>,
+}
+
+fn monster_rewards<'a>(
+    pedia_ex: &'a PediaEx,
+    em_type: EmTypes,
+    rank: QuestRank,
+) -> Option<MonsterRewards<'a>> {
+    let lot = pedia_ex.monster_lot.get(&(em_type, rank))?;
+    let pop_groups = |groups: Vec<(EnemyRewardPopTypes, Vec<ItemCount<'a>>)>| {
+        groups
+            .into_iter()
+            .map(|(pop_type, items)| RewardGroup {
+                pop_type: Some(pop_type),
+                part: None,
+                items,
+            })
+            .collect()
+    };
+
+    Some(MonsterRewards {
+        rank,
+        target: item_counts(
+            pedia_ex,
+            &lot.target_reward_item_id_list,
+            &lot.target_reward_num_list,
+            Some(&lot.target_reward_probability_list[..]),
+        ),
+        carves: pop_groups(grouped_rewards(
+            pedia_ex,
+            &lot.enemy_reward_type_list,
+            EnemyRewardPopTypes::None,
+            &lot.hagitory_reward_item_id_list,
+            &lot.hagitory_reward_num_list,
+            &lot.hagitory_reward_probability_list,
+        )),
+        capture: item_counts(
+            pedia_ex,
+            &lot.capture_reward_item_id_list,
+            &lot.capture_reward_num_list,
+            Some(&lot.capture_reward_probability_list[..]),
+        ),
+        parts_break: grouped_rewards(
+            pedia_ex,
+            &lot.parts_break_list,
+            BrokenPartsTypes::None,
+            &lot.parts_break_reward_item_id_list,
+            &lot.parts_break_reward_num_list,
+            &lot.parts_break_reward_probability_list,
+        )
+        .into_iter()
+        .map(|(part, items)| RewardGroup {
+            pop_type: None,
+            part: pedia_ex.parts_dictionary.get(&(em_type, part)).map(names),
+            items,
+        })
+        .collect(),
+        drop: pop_groups(grouped_rewards(
+            pedia_ex,
+            &lot.drop_reward_type_list,
+            EnemyRewardPopTypes::None,
+            &lot.drop_reward_item_id_list,
+            &lot.drop_reward_num_list,
+            &lot.drop_reward_probability_list,
+        )),
+        buddy: item_counts(
+            pedia_ex,
+            &lot.otomo_reward_item_id_list,
+            &lot.otomo_reward_num_list,
+            Some(&lot.otomo_reward_probability_list[..]),
+        ),
+    })
+}
+
+#[derive(Serialize)]
+struct MonsterApi<'a> {
+    #[serde(flatten)]
+    reference: Reference<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alias: Option<Names<'a>>,
+    icon: String,
+    quests: Vec<Reference<'a>>,
+    rewards: Vec<MonsterRewards<'a>>,
+    hitzones: Vec<HitzoneApi<'a>>,
+    parts: Vec<PartApi<'a>>,
+    conditions: Vec<ConditionApi>,
+}
+
+#[derive(Serialize)]
+struct HitzoneApi<'a> {
+    hitzone: usize,
+    phase: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<Names<'a>>,
+    colliders: Vec<&'a str>,
+    slash: u16,
+    impact: u16,
+    shot: u16,
+    fire: u16,
+    water: u16,
+    ice: u16,
+    thunder: u16,
+    dragon: u16,
+    stun: u16,
+}
+
+fn colliders(map: &BTreeMap<usize, BTreeSet<String>>, index: usize) -> Vec<&str> {
+    map.get(&index)
+        .map(|names| names.iter().map(|s| s.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn hitzones<'a>(
+    pedia_ex: &'a PediaEx,
+    monster: &'a Monster,
+    em_type: EmTypes,
+) -> Vec<HitzoneApi<'a>> {
+    let mut hitzones = vec![];
+    for (part, meats) in monster.meat_data.meat_container.iter().enumerate() {
+        for (phase, meat) in meats.meat_group_info.iter().enumerate() {
+            hitzones.push(HitzoneApi {
+                hitzone: part,
+                phase,
+                name: pedia_ex
+                    .meat_names
+                    .get(&MeatKey {
+                        em_type,
+                        part,
+                        phase,
+                    })
+                    .map(names),
+                colliders: colliders(&monster.collider_mapping.meat_map, part),
+                slash: meat.slash,
+                impact: meat.strike,
+                shot: meat.shell,
+                fire: meat.fire,
+                water: meat.water,
+                ice: meat.ice,
+                thunder: meat.elect,
+                dragon: meat.dragon,
+                stun: meat.piyo,
+            })
+        }
+    }
+    hitzones
+}
+
+#[derive(Serialize)]
+struct PartBreakApi {
+    level: i32,
+    hp: i32,
+}
+
+#[derive(Serialize)]
+struct PartSeverApi {
+    attribute: &'static str,
+    hp: i32,
+}
+
+#[derive(Serialize)]
+struct PartApi<'a> {
+    part: usize,
+    colliders: Vec<&'a str>,
+    hp: i32,
+    extract: &'static str,
+    breaks: Vec<PartBreakApi>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sever: Option<PartSeverApi>,
+}
+
+fn parts(monster: &Monster) -> Vec<PartApi<'_>> {
+    let data_tune = &monster.data_tune;
+    data_tune
+        .enemy_parts_data
+        .iter()
+        .enumerate()
+        .map(|(index, part)| {
+            let group = |parts_group: u16| usize::from(parts_group) == index;
+            let breaks = data_tune
+                .enemy_parts_break_data_list
+                .iter()
+                .filter(|p| group(p.parts_group))
+                .flat_map(|p| &p.parts_break_data_list)
+                .map(|p| PartBreakApi {
+                    level: p.break_level,
+                    hp: p.vital,
+                })
+                .collect();
+            let sever = data_tune
+                .enemy_parts_loss_data_list
+                .iter()
+                .find(|p| group(p.parts_group))
+                .map(|p| PartSeverApi {
+                    attribute: match p.parts_loss_data.permit_damage_attr {
+                        PermitDamageAttrEnum::Slash => "slash",
+                        PermitDamageAttrEnum::Strike => "impact",
+                        PermitDamageAttrEnum::All => "all",
+                    },
+                    hp: p.parts_loss_data.vital,
+                });
+            PartApi {
+                part: index,
+                colliders: colliders(&monster.collider_mapping.part_map, index),
+                hp: part.vital,
+                extract: match part.extractive_type {
+                    ExtractiveType::Red => "red",
+                    ExtractiveType::White => "white",
+                    ExtractiveType::Orange => "orange",
+                    ExtractiveType::None => "none",
+                },
+                breaks,
+                sever,
+            }
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct StockApi {
+    initial: f32,
+    increase: f32,
+    max: f32,
+    decay: f32,
+    decay_interval: f32,
+}
+
+impl StockApi {
+    fn new(stock: &StockData) -> StockApi {
+        StockApi {
+            initial: stock.default_limit,
+            increase: stock.add_limit,
+            max: stock.max_limit,
+            decay: stock.sub_value,
+            decay_interval: stock.sub_interval,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ConditionApi {
+    kind: &'static str,
+    enabled: bool,
+    threshold: StockApi,
+    ride_threshold: StockApi,
+    max_stock: f32,
+    duration: f32,
+    duration_reduction: f32,
+    min_duration: f32,
+    add_tired_time: f32,
+    damage: f32,
+    damage_interval: f32,
+}
+
+impl ConditionApi {
+    fn new(
+        kind: &'static str,
+        base: &ConditionDamageDataBase,
+        used: ConditionDamageDataUsed,
+    ) -> ConditionApi {
+        ConditionApi {
+            kind,
+            enabled: matches!(used, ConditionDamageDataUsed::Use),
+            threshold: StockApi::new(&base.default_stock),
+            ride_threshold: StockApi::new(&base.ride_stock),
+            max_stock: base.max_stock,
+            duration: base.active_time,
+            duration_reduction: base.sub_active_time,
+            min_duration: base.min_active_time,
+            add_tired_time: base.add_tired_time,
+            damage: base.damage,
+            damage_interval: base.damage_interval,
+        }
+    }
+}
+
+// Condition parameters with the shared presets already applied
+fn conditions(
+    data: &EnemyConditionDamageData,
+    preset: &EnemyConditionPresetData,
+) -> Result<Vec<ConditionApi>> {
+    let shock_trap = <ShockTrapDamageData as ConditionDamage<PresetShockTrapData>>::or_preset(
+        &data.shock_trap_data,
+        preset,
+    )?;
+    let shock_otomo_trap =
+        <ShockTrapDamageData as ConditionDamage<PresetShockOtomoTrapData>>::or_preset(
+            &data.shock_otomo_trap_data,
+            preset,
+        )?;
+    Ok(vec![
+        ConditionApi::new(
+            "paralyze",
+            &data.paralyze_data.or_preset(preset)?.base,
+            data.use_paralyze,
+        ),
+        ConditionApi::new(
+            "sleep",
+            &data.sleep_data.or_preset(preset)?.base,
+            data.use_sleep,
+        ),
+        ConditionApi::new(
+            "stun",
+            &data.stun_data.or_preset(preset)?.base,
+            data.use_stun,
+        ),
+        ConditionApi::new(
+            "exhaust",
+            &data.stamina_data.or_preset(preset)?.base,
+            data.use_stamina,
+        ),
+        ConditionApi::new(
+            "flash",
+            &data.flash_data.or_preset(preset)?.base,
+            data.use_flash,
+        ),
+        ConditionApi::new(
+            "poison",
+            &data.poison_data.or_preset(preset)?.base,
+            data.use_poison,
+        ),
+        ConditionApi::new(
+            "blast",
+            &data.blast_data.or_preset(preset)?.base,
+            data.use_blast,
+        ),
+        ConditionApi::new("ride", &data.marionette_data.base, data.use_ride),
+        ConditionApi::new(
+            "water",
+            &data.water_data.or_preset(preset)?.base,
+            data.use_water,
+        ),
+        ConditionApi::new(
+            "fire",
+            &data.fire_data.or_preset(preset)?.base,
+            data.use_fire,
+        ),
+        ConditionApi::new("ice", &data.ice_data.or_preset(preset)?.base, data.use_ice),
+        ConditionApi::new(
+            "thunder",
+            &data.thunder_data.or_preset(preset)?.base,
+            data.use_thunder,
+        ),
+        ConditionApi::new(
+            "fall_trap",
+            &data.fall_trap_data.or_preset(preset)?.base,
+            data.use_fall_trap,
+        ),
+        ConditionApi::new(
+            "quick_sand",
+            &data.fall_quick_sand_data.or_preset(preset)?.base,
+            data.use_fall_quick_sand,
+        ),
+        ConditionApi::new(
+            "buddy_fall_trap",
+            &data.fall_otomo_trap_data.or_preset(preset)?.base,
+            data.use_fall_otomo_trap,
+        ),
+        ConditionApi::new("shock_trap", &shock_trap.base, data.use_shock_trap),
+        ConditionApi::new(
+            "buddy_shock_trap",
+            &shock_otomo_trap.base,
+            data.use_shock_otomo_trap,
+        ),
+        ConditionApi::new(
+            "capture",
+            &data.capture_data.or_preset(preset)?.base,
+            data.use_capture,
+        ),
+        ConditionApi::new(
+            "dung",
+            &data.koyashi_data.or_preset(preset)?.base,
+            data.use_dung,
+        ),
+        ConditionApi::new(
+            "steel_fang",
+            &data.steel_fang_data.or_preset(preset)?.base,
+            data.use_steel_fang,
+        ),
+    ])
+}
+
+#[derive(Serialize)]
+struct QuestMonsterApi<'a> {
+    monster: Reference<'a>,
+    target: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<&'a QuestMonsterStats>,
+}
+
+#[derive(Serialize)]
+struct RewardLotApi<'a> {
+    kind: &'static str,
+    rule: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    items: Vec<ItemCount<'a>>,
+}
+
+#[derive(Serialize)]
+struct QuestApi<'a> {
+    #[serde(flatten)]
+    reference: Reference<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<Names<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition: Option<Names<'a>>,
+    rank: &'static str,
+    level: &'static str,
+    map: String,
+    monsters: Vec<QuestMonsterApi<'a>>,
+    supply: Vec<ItemCount<'a>>,
+    rewards: Vec<RewardLotApi<'a>>,
+}
+
+#[derive(Serialize)]
+struct SkillLevel<'a> {
+    skill: Reference<'a>,
+    level: i32,
+}
+
+#[derive(Serialize)]
+struct ArmorApi<'a> {
+    part: &'static str,
+    name: Names<'a>,
+    rarity: &'a RareTypes,
+    defense: i32,
+    fire: i32,
+    water: i32,
+    thunder: i32,
+    ice: i32,
+    dragon: i32,
+    slots: &'a [u32; 3],
+    skills: Vec<SkillLevel<'a>>,
+    materials: Vec<ItemCount<'a>>,
+    layered_materials: Vec<ItemCount<'a>>,
+}
+
+#[derive(Serialize)]
+struct ArmorSeriesApi<'a> {
+    #[serde(flatten)]
+    reference: Reference<'a>,
+    pieces: Vec<ArmorApi<'a>>,
+}
+
+#[derive(Serialize)]
+struct SkillApi<'a> {
+    #[serde(flatten)]
+    reference: Reference<'a>,
+    explain: Names<'a>,
+    levels: Vec<Names<'a>>,
+    armors: Vec<Reference<'a>>,
+}
+
+#[derive(Serialize)]
+struct ItemApi<'a> {
+    #[serde(flatten)]
+    reference: Reference<'a>,
+    icon: String,
+    #[serde(rename = "type")]
+    type_: &'a ItemTypes,
+    carriable_filter: &'a CarriableFilter,
+    rarity: &'a RareTypes,
+    sell_price: u32,
+    buy_price: u32,
+    material_categories: Vec<Names<'a>>,
+    monsters: Vec<Reference<'a>>,
+    quests: Vec<Reference<'a>>,
+    armors: Vec<Reference<'a>>,
+}
+
+#[derive(Serialize)]
+struct LanguageApi {
+    index: usize,
+    code: &'static str,
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+struct IndexApi {
+    version: u32,
+    languages: Vec<LanguageApi>,
+    monsters: String,
+    small_monsters: String,
+    quests: String,
+    armors: String,
+    skills: String,
+    items: String,
+}
+
+fn armor_part(id: PlArmorId) -> &'static str {
+    match id {
+        PlArmorId::Head(_) => "head",
+        PlArmorId::Chest(_) => "chest",
+        PlArmorId::Arm(_) => "arm",
+        PlArmorId::Waist(_) => "waist",
+        PlArmorId::Leg(_) => "leg",
+        _ => "unknown",
+    }
+}
+
+fn add_reference<'a>(references: &mut Vec<Reference<'a>>, reference: &Reference<'a>) {
+    if references.iter().all(|r| r.url != reference.url) {
+        references.push(reference.clone());
+    }
+}
+
+#[derive(Default)]
+struct ItemUsage<'a> {
+    monsters: Vec<Reference<'a>>,
+    quests: Vec<Reference<'a>>,
+    armors: Vec<Reference<'a>>,
+}
+
+impl<'a> ItemUsage<'a> {
+    fn add(
+        usages: &mut BTreeMap<ItemId, ItemUsage<'a>>,
+        items: &[ItemId],
+        f: impl Fn(&mut ItemUsage<'a>) -> &mut Vec<Reference<'a>>,
+        reference: &Reference<'a>,
+    ) {
+        for &item in items {
+            add_reference(f(usages.entry(item).or_default()), reference);
+        }
+    }
+}
+
+fn lot_rule(rule: LotRule) -> &'static str {
+    match rule {
+        LotRule::Random => "random",
+        LotRule::RandomOut1 => "random_out_1",
+        LotRule::RandomOut2 => "random_out_2",
+        LotRule::RandomOut3 => "random_out_3",
+        LotRule::FirstFix => "first_fixed",
+    }
+}
+
+fn write_json<T: Serialize + ?Sized>(
+    manifest: &mut Manifest,
+    path: &Path,
+    value: &T,
+) -> Result<()> {
+    manifest.write(path, serde_json::to_string(value)?)
+}
+
+pub fn gen_api(
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let api_path = root.join("api").join(format!("v{}", API_VERSION));
+    for folder in &[
+        "monster",
+        "small-monster",
+        "quest",
+        "armor",
+        "skill",
+        "item",
+    ] {
+        create_dir_all(api_path.join(folder))?;
+    }
+
+    let mut usages: BTreeMap<ItemId, ItemUsage> = BTreeMap::new();
+
+    for &(is_large, folder, monsters) in &[
+        (true, "monster", &pedia.monsters),
+        (false, "small-monster", &pedia.small_monsters),
+    ] {
+        let mut list = vec![];
+        for monster in monsters {
+            let id = monster.id | (monster.sub_id << 8);
+            let em_type = if is_large { EmTypes::Em } else { EmTypes::Ems }(id);
+            let reference = monster_ref(pedia, em_type);
+            let alias = monster
+                .boss_init_set_data
+                .as_ref()
+                .and_then(|data| {
+                    pedia
+                        .monster_aliases
+                        .get_entry(&format!("Alias_EnemyIndex{:03}", data.enemy_type))
+                })
+                .map(names);
+            let quests = pedia_ex
+                .quests
+                .iter()
+                .filter(|quest| quest.param.boss_em_type.contains(&em_type))
+                .map(quest_ref)
+                .collect();
+            let rewards: Vec<_> = [QuestRank::Low, QuestRank::High]
+                .iter()
+                .filter_map(|&rank| monster_rewards(pedia_ex, em_type, rank))
+                .collect();
+            for rank in &[QuestRank::Low, QuestRank::High] {
+                let lot = if let Some(lot) = pedia_ex.monster_lot.get(&(em_type, *rank)) {
+                    lot
+                } else {
+                    continue;
+                };
+                for items in &[
+                    &lot.target_reward_item_id_list,
+                    &lot.hagitory_reward_item_id_list,
+                    &lot.capture_reward_item_id_list,
+                    &lot.parts_break_reward_item_id_list,
+                    &lot.drop_reward_item_id_list,
+                    &lot.otomo_reward_item_id_list,
+                ] {
+                    ItemUsage::add(&mut usages, items, |u| &mut u.monsters, &reference);
+                }
+            }
+
+            let monster_api = MonsterApi {
+                reference: reference.clone(),
+                alias,
+                icon: format!(
+                    "/resources/{}{:03}_{:02}_icon.png",
+                    if is_large { "em" } else { "ems" },
+                    monster.id,
+                    monster.sub_id
+                ),
+                quests,
+                rewards,
+                hitzones: hitzones(pedia_ex, monster, em_type),
+                parts: parts(monster),
+                conditions: conditions(&monster.condition_damage_data, &pedia.condition_preset)?,
+            };
+            write_json(
+                manifest,
+                &api_path.join(folder).join(format!("{}.json", reference.id)),
+                &monster_api,
+            )?;
+            list.push(reference);
+        }
+        write_json(manifest, &api_path.join(format!("{}.json", folder)), &list)?;
+    }
+
+    let mut list = vec![];
+    for quest in &pedia_ex.quests {
+        let reference = quest_ref(quest);
+        let monsters = quest
+            .param
+            .boss_em_type
+            .iter()
+            .enumerate()
+            .filter(|&(_, &em_type)| em_type != EmTypes::Em(0))
+            .map(|(i, &em_type)| QuestMonsterApi {
+                monster: monster_ref(pedia, em_type),
+                target: quest.param.tgt_em_type.contains(&em_type),
+                stats: quest.monster_stats.iter().find(|stats| stats.index == i),
+            })
+            .collect();
+        let supply = quest
+            .supply
+            .as_ref()
+            .map(|supply| item_counts(pedia_ex, &supply.item_id, &supply.num, None))
+            .unwrap_or_default();
+
+        let mut rewards = vec![];
+        if let Some(reward) = &quest.reward {
+            let lots = reward
+                .additional_target_reward
+                .iter()
+                .map(|lot| ("main", None, lot))
+                .chain(
+                    reward
+                        .common_material_reward
+                        .iter()
+                        .map(|lot| ("sub", Some(reward.param.common_material_reward_num), lot)),
+                )
+                .chain(
+                    reward
+                        .additional_quest_reward
+                        .iter()
+                        .map(|lot| ("additional", None, lot)),
+                );
+            for (kind, count, lot) in lots {
+                ItemUsage::add(
+                    &mut usages,
+                    &lot.item_id_list,
+                    |u| &mut u.quests,
+                    &reference,
+                );
+                rewards.push(RewardLotApi {
+                    kind,
+                    rule: lot_rule(lot.lot_rule),
+                    count,
+                    items: item_counts(
+                        pedia_ex,
+                        &lot.item_id_list,
+                        &lot.num_list,
+                        Some(&lot.probability_list[..]),
+                    ),
+                });
+            }
+        }
+
+        let quest_api = QuestApi {
+            reference: reference.clone(),
+            target: quest.target.as_ref().map(names),
+            condition: quest.condition.as_ref().map(names),
+            rank: match quest.param.enemy_level {
+                EnemyLevel::Village => "village",
+                EnemyLevel::Low => "low",
+                EnemyLevel::High => "high",
+            },
+            level: quest_level_text(quest.param.quest_level),
+            map: format!("/map/{}", map_page(quest.param.map_no)),
+            monsters,
+            supply,
+            rewards,
+        };
+        write_json(
+            manifest,
+            &api_path
+                .join("quest")
+                .join(format!("{}.json", reference.id)),
+            &quest_api,
+        )?;
+        list.push(reference);
+    }
+    write_json(manifest, &api_path.join("quest.json"), &list)?;
+
+    let mut skill_armors: BTreeMap<PlEquipSkillId, Vec<Reference>> = BTreeMap::new();
+    let mut list = vec![];
+    for series in &pedia_ex.armors {
+        let reference = armor_ref(series);
+        let mut pieces = vec![];
+        for piece in series.pieces.iter().flatten() {
+            let data = piece.data;
+            let skills = data
+                .skill_list
+                .iter()
+                .zip(&data.skill_lv_list)
+                .filter(|&(&skill, _)| skill != PlEquipSkillId::None)
+                .map(|(&skill, &level)| {
+                    add_reference(skill_armors.entry(skill).or_default(), &reference);
+                    SkillLevel {
+                        skill: skill_ref(pedia_ex, skill),
+                        level,
+                    }
+                })
+                .collect();
+            let materials = piece.product.map_or_else(Vec::new, |product| {
+                ItemUsage::add(&mut usages, &product.item, |u| &mut u.armors, &reference);
+                item_counts(pedia_ex, &product.item, &product.item_num, None)
+            });
+            let layered_materials = piece.overwear_product.map_or_else(Vec::new, |product| {
+                ItemUsage::add(&mut usages, &product.item, |u| &mut u.armors, &reference);
+                item_counts(pedia_ex, &product.item, &product.item_num, None)
+            });
+            pieces.push(ArmorApi {
+                part: armor_part(data.pl_armor_id),
+                name: names(&piece.name),
+                rarity: &data.rare,
+                defense: data.def_val,
+                fire: data.fire_reg_val,
+                water: data.water_reg_val,
+                thunder: data.thunder_reg_val,
+                ice: data.ice_reg_val,
+                dragon: data.dragon_reg_val,
+                slots: &data.decorations_num_list,
+                skills,
+                materials,
+                layered_materials,
+            });
+        }
+        let series_api = ArmorSeriesApi {
+            reference: reference.clone(),
+            pieces,
+        };
+        write_json(
+            manifest,
+            &api_path
+                .join("armor")
+                .join(format!("{}.json", reference.id)),
+            &series_api,
+        )?;
+        list.push(reference);
+    }
+    write_json(manifest, &api_path.join("armor.json"), &list)?;
+
+    let mut list = vec![];
+    for (&id, skill) in &pedia_ex.skills {
+        let reference = skill_ref(pedia_ex, id);
+        let skill_api = SkillApi {
+            reference: reference.clone(),
+            explain: names(&skill.explain),
+            levels: skill.levels.iter().map(names).collect(),
+            armors: skill_armors.remove(&id).unwrap_or_default(),
+        };
+        write_json(
+            manifest,
+            &api_path
+                .join("skill")
+                .join(format!("{}.json", reference.id)),
+            &skill_api,
+        )?;
+        list.push(reference);
+    }
+    write_json(manifest, &api_path.join("skill.json"), &list)?;
+
+    let mut list = vec![];
+    for (&id, item) in &pedia_ex.items {
+        let reference = item_ref(pedia_ex, id);
+        let usage = usages.remove(&id).unwrap_or_default();
+        let item_api = ItemApi {
+            reference: reference.clone(),
+            icon: format!("/resources/item_icon/{}", item_icon_file_name(item.param)),
+            type_: &item.param.type_,
+            carriable_filter: &item.param.cariable_filter,
+            rarity: &item.param.rare,
+            sell_price: item.param.sell_price,
+            buy_price: item.param.buy_price,
+            material_categories: item
+                .param
+                .material_category
+                .iter()
+                .filter_map(|category| pedia_ex.material_categories.get(category))
+                .map(names)
+                .collect(),
+            monsters: usage.monsters,
+            quests: usage.quests,
+            armors: usage.armors,
+        };
+        write_json(
+            manifest,
+            &api_path.join("item").join(format!("{}.json", reference.id)),
+            &item_api,
+        )?;
+        list.push(reference);
+    }
+    write_json(manifest, &api_path.join("item.json"), &list)?;
+
+    let index = IndexApi {
+        version: API_VERSION,
+        languages: LANGUAGE_CODES
+            .iter()
+            .zip(LANGUAGE_MAP.iter())
+            .enumerate()
+            .filter_map(|(index, (code, name))| {
+                Some(LanguageApi {
+                    index,
+                    code: (*code)?,
+                    name: (*name)?,
+                })
+            })
+            .collect(),
+        monsters: format!("/api/v{}/monster.json", API_VERSION),
+        small_monsters: format!("/api/v{}/small-monster.json", API_VERSION),
+        quests: format!("/api/v{}/quest.json", API_VERSION),
+        armors: format!("/api/v{}/armor.json", API_VERSION),
+        skills: format!("/api/v{}/skill.json", API_VERSION),
+        items: format!("/api/v{}/item.json", API_VERSION),
+    };
+    write_json(manifest, &api_path.join("index.json"), &index)?;
+
+    Ok(())
+}
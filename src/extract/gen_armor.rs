@@ -1,33 +1,95 @@
 use super::gen_item::*;
+use super::gen_pedia::{armor_preview_file_name, armor_sexes};
 use super::gen_skill::*;
 use super::gen_website::*;
+use super::manifest::*;
 use super::pedia::*;
 use crate::rsz::*;
 use anyhow::*;
-use std::fs::{create_dir, write};
+use std::collections::BTreeSet;
+use std::fs::create_dir_all;
 use std::path::*;
-use typed_html::{dom::*, html, text};
+use typed_html::{dom::*, elements::*, html, text};
 
-pub fn gen_armor_list(serieses: &[ArmorSeries], root: &Path) -> Result<()> {
+// Preview images that gen_resources actually wrote for the model, with their sex
+fn armor_previews(
+    id: PlArmorId,
+    model_id: u32,
+    sexual_equipable: &SexualEquipableFlag,
+    previews: &BTreeSet<String>,
+) -> Vec<(bool, String)> {
+    armor_sexes(sexual_equipable)
+        .iter()
+        .filter_map(|&female| {
+            let file_name = armor_preview_file_name(id, model_id, female)?;
+            previews.contains(&file_name).then(|| (female, file_name))
+        })
+        .collect()
+}
+
+fn piece_previews(
+    piece: &Armor<'_>,
+    previews: &BTreeSet<String>,
+) -> (Vec<(bool, String)>, Vec<(bool, String)>) {
+    let armor = armor_previews(
+        piece.data.pl_armor_id,
+        piece.data.model_id,
+        &piece.data.sexual_equipable,
+        previews,
+    );
+    let layered = piece.overwear.map_or_else(Vec::new, |overwear| {
+        armor_previews(
+            overwear.relative_id,
+            overwear.model_id,
+            &overwear.sexual_equipable,
+            previews,
+        )
+    });
+    (armor, layered)
+}
+
+fn gen_armor_previews(lang: Option<usize>, previews: Vec<(bool, String)>) -> Box<td<String>> {
+    if previews.is_empty() {
+        return html!(<td>"-"</td>);
+    }
+    html!(<td><div class="mh-armor-previews">{
+        previews.into_iter().map(|(female, file_name)| {
+            let alt = ui_text(lang, if female { "Female" } else { "Male" });
+            html!(<figure class="mh-armor-preview">
+                <img src={format!("/resources/armor/{}", file_name)} alt={alt.as_str()} />
+                <figcaption>{text!("{}", alt)}</figcaption>
+            </figure>)
+        })
+    }</div></td>)
+}
+
+pub fn gen_armor_list(
+    lang: Option<usize>,
+    serieses: &[ArmorSeries],
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let armor_path = root.join("armor.html");
+    let alternates = manifest.alternate_links(&armor_path)?;
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Armors - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Armors"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"Armors"</h1>
+                <h1 class="title">{ui(lang, "Armors")}</h1>
                 <article class="message is-warning">
                     <div class="message-body">
-                        "Armor names are probably incorrect."
+                        {ui(lang, "Armor names are probably incorrect.")}
                     </div>
                 </article>
                 <ul class="mh-armor-series-list">{
                     serieses.into_iter().map(|series|{
                         let series_name = if let Some(name) = series.name.as_ref() {
-                            gen_multi_lang(name)
+                            gen_multi_lang(lang, name)
                         } else {
                             html!(<span>"<Unknown>"</span>)
                         };
@@ -40,7 +102,7 @@ pub fn gen_armor_list(serieses: &[ArmorSeries], root: &Path) -> Result<()> {
                             <ul class="mh-armor-list"> {
                                 series.pieces.iter().map(|piece| {
                                     let piece_name = if let Some(piece) = piece {
-                                        gen_multi_lang(&piece.name)
+                                        gen_multi_lang(lang, &piece.name)
                                     } else {
                                         html!(<span>"-"</span>)
                                     };
@@ -56,18 +118,23 @@ pub fn gen_armor_list(serieses: &[ArmorSeries], root: &Path) -> Result<()> {
         </html>
     );
 
-    let armor_path = root.join("armor.html");
-    write(&armor_path, doc.to_string())?;
+    manifest.write(&armor_path, doc.to_string())?;
 
     Ok(())
 }
 
-fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()> {
+fn gen_armor(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
+    series: &ArmorSeries,
+    pedia_ex: &PediaEx,
+    previews: &BTreeSet<String>,
+) -> Result<String> {
     let gen_category = |material_category, material_category_num| {
         let category = if material_category == MaterialCategory(0) {
             return html!(<td>"-"</td>);
         } else if let Some(name) = pedia_ex.material_categories.get(&material_category) {
-            html!(<span>{gen_multi_lang(name)}" "</span>)
+            html!(<span>{gen_multi_lang(lang, name)}" "</span>)
         } else {
             html!(<span>{text!("{:?} ", material_category)}</span>)
         };
@@ -81,12 +148,12 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                 .filter(|&(&item, _)| item != ItemId::None)
                 .map(|(&item, num)|{
                 let key = if item == item_flag {
-                    Some(html!(<span class="tag is-primary">"Key"</span>))
+                    Some(html!(<span class="tag is-primary">{ui(lang, "Key")}</span>))
                 } else {
                     None
                 };
                 let item = if let Some(item) = pedia_ex.items.get(&item) {
-                    html!(<span>{gen_item_label(item)}</span>)
+                    html!(<span>{gen_item_label(lang, item)}</span>)
                 } else {
                     html!(<span>{text!("{:?}", item)}</span>)
                 };
@@ -100,35 +167,36 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
     };
 
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Armor {:03}", series.series.armor_series.0)}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text_args(lang, "Armor {}",
+                    &[&format!("{:03}", series.series.armor_series.0)]))}</title>
+                { head_common(lang, alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
                 <h1 class="title"> {
                     if let Some(name) = series.name.as_ref() {
-                        gen_multi_lang(name)
+                        gen_multi_lang(lang, name)
                     } else {
                         html!(<span>"<Unknown>"</span>)
                     }
                 } </h1>
                 <section class="section">
-                <h2 class="title">"Stat"</h2>
+                <h2 class="title">{ui(lang, "Stat")}</h2>
                 <table>
                     <thead><tr>
-                        <th>"Name"</th>
-                        <th>"Value (Sell / Buy)"</th>
-                        <th>"Defense"</th>
-                        <th>"Fire"</th>
-                        <th>"Water"</th>
-                        <th>"Ice"</th>
-                        <th>"Thunder"</th>
-                        <th>"Dragon"</th>
-                        <th>"Slots"</th>
-                        <th>"Skills"</th>
+                        <th>{ui(lang, "Name")}</th>
+                        <th>{ui(lang, "Value (Sell / Buy)")}</th>
+                        <th>{ui(lang, "Defense")}</th>
+                        <th>{ui(lang, "Fire")}</th>
+                        <th>{ui(lang, "Water")}</th>
+                        <th>{ui(lang, "Ice")}</th>
+                        <th>{ui(lang, "Thunder")}</th>
+                        <th>{ui(lang, "Dragon")}</th>
+                        <th>{ui(lang, "Slots")}</th>
+                        <th>{ui(lang, "Skills")}</th>
                     </tr></thead>
                     <tbody> {
                         series.pieces.iter().map(|piece| {
@@ -157,7 +225,7 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                                         html!(<span><a href={format!("/skill/{}", skill_page(skill))}
                                             class="mh-icon-text">
                                             {gen_colored_icon(skill_data.icon_color, "/resources/skill", &[])}
-                                            {gen_multi_lang(&skill_data.name)}
+                                            {gen_multi_lang(lang, &skill_data.name)}
                                         </a></span>)
                                     } else {
                                         html!(<span>"<UNKNOWN>"</span>)
@@ -170,7 +238,7 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                             } </ul>);
 
                             html!(<tr>
-                                <td>{gen_multi_lang(&piece.name)}</td>
+                                <td>{gen_multi_lang(lang, &piece.name)}</td>
                                 <td>{text!("{} / {}", piece.data.value, piece.data.buy_value)}</td>
                                 <td>{text!("{}", piece.data.def_val)}</td>
                                 <td>{text!("{}", piece.data.fire_reg_val)}</td>
@@ -187,13 +255,39 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                 </section>
 
                 <section class="section">
-                <h2 class="title">"Crafting"</h2>
+                <h2 class="title">{ui(lang, "Preview")}</h2>
                 <table>
                     <thead><tr>
-                        <th>"Name"</th>
-                        <th>"Categorized Material"</th>
-                        <th>"Material"</th>
-                        <th>"Output"</th>
+                        <th>{ui(lang, "Name")}</th>
+                        <th>{ui(lang, "Armor")}</th>
+                        <th>{ui(lang, "Layered")}</th>
+                    </tr></thead>
+                    <tbody> {
+                        series.pieces.iter().map(|piece| {
+                            let piece = if let Some(piece) = piece {
+                                piece
+                            } else {
+                                return html!(<tr><td colspan="3">"-"</td></tr>)
+                            };
+                            let (armor, layered) = piece_previews(piece, previews);
+                            html!(<tr>
+                                <td>{gen_multi_lang(lang, &piece.name)}</td>
+                                {gen_armor_previews(lang, armor)}
+                                {gen_armor_previews(lang, layered)}
+                            </tr>)
+                        })
+                    } </tbody>
+                </table>
+                </section>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Crafting")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Name")}</th>
+                        <th>{ui(lang, "Categorized Material")}</th>
+                        <th>{ui(lang, "Material")}</th>
+                        <th>{ui(lang, "Output")}</th>
                     </tr></thead>
                     <tbody> {
                         series.pieces.iter().map(|piece| {
@@ -215,7 +309,7 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                                     .filter(|&(&item, _)| item != ItemId::None)
                                     .map(|(&item, num)|{
                                     let item = if let Some(item) = pedia_ex.items.get(&item) {
-                                        html!(<span>{gen_item_label(item)}</span>)
+                                        html!(<span>{gen_item_label(lang, item)}</span>)
                                     } else {
                                         html!(<span>{text!("{:?}", item)}</span>)
                                     };
@@ -227,7 +321,7 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                             } </ul></td>);
 
                             html!(<tr>
-                                <td>{gen_multi_lang(&name)}</td>
+                                <td>{gen_multi_lang(lang, &name)}</td>
                                 {category}
                                 {materials}
                                 {output}
@@ -239,12 +333,12 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                 </section>
 
                 <section class="section">
-                <h2 class="title">"Layered crafting"</h2>
+                <h2 class="title">{ui(lang, "Layered crafting")}</h2>
                 <table>
                     <thead><tr>
-                        <th>"Name"</th>
-                        <th>"Categorized Material"</th>
-                        <th>"Material"</th>
+                        <th>{ui(lang, "Name")}</th>
+                        <th>{ui(lang, "Categorized Material")}</th>
+                        <th>{ui(lang, "Material")}</th>
                     </tr></thead>
                     <tbody> {
                         series.pieces.iter().map(|piece| {
@@ -262,7 +356,7 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
                                 &product.item_num, product.item_flag);
 
                             html!(<tr>
-                                <td>{gen_multi_lang(&name)}</td>
+                                <td>{gen_multi_lang(lang, &name)}</td>
                                 {category}
                                 {materials}
                             </tr>)
@@ -277,16 +371,30 @@ fn gen_armor(series: &ArmorSeries, pedia_ex: &PediaEx, path: &Path) -> Result<()
         </html>
     );
 
-    write(&path, doc.to_string())?;
-    Ok(())
+    Ok(doc.to_string())
 }
 
-pub fn gen_armors(pedia_ex: &PediaEx<'_>, root: &Path) -> Result<()> {
+pub fn gen_armors(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx<'_>,
+    previews: &BTreeSet<String>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
     let armor_path = root.join("armor");
-    create_dir(&armor_path)?;
+    create_dir_all(&armor_path)?;
     for series in &pedia_ex.armors {
         let path = armor_path.join(format!("{:03}.html", series.series.armor_series.0));
-        gen_armor(series, &pedia_ex, &path)?
+        let series_previews: Vec<_> = series
+            .pieces
+            .iter()
+            .flatten()
+            .map(|piece| piece_previews(piece, previews))
+            .collect();
+        let alternates = manifest.alternate_links(&path)?;
+        manifest.page(&path, &(series, series_previews), || {
+            gen_armor(lang, &alternates, series, &pedia_ex, previews)
+        })?
     }
     Ok(())
 }
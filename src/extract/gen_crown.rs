@@ -0,0 +1,179 @@
+use super::crown::*;
+use super::gen_website::*;
+use super::manifest::*;
+use super::pedia::*;
+use anyhow::*;
+use std::fs::create_dir_all;
+use std::path::*;
+use typed_html::{dom::*, elements::*, html, text};
+
+pub fn crown_page(id: u32) -> String {
+    format!("{:03}_{:02}.html", id & 0xFF, id >> 8)
+}
+
+pub fn gen_crown_tags(lang: Option<usize>, odds: &CrownOdds) -> Vec<Box<span<String>>> {
+    let mut tags = vec![];
+    if odds.mini > 0.0 {
+        tags.push(html!(<span class="tag">
+            <img src="/resources/small_crown.png" />
+            {text!("{:.1}%", odds.mini)}
+        </span>));
+    }
+    if odds.silver > 0.0 {
+        tags.push(html!(<span class="tag">
+            {ui_args(lang, "Silver {}%", &[&format!("{:.1}", odds.silver)])}
+        </span>));
+    }
+    if odds.gold > 0.0 {
+        tags.push(html!(<span class="tag">
+            <img src="/resources/king_crown.png" />
+            {text!("{:.1}%", odds.gold)}
+        </span>));
+    }
+    tags
+}
+
+pub fn gen_monster_name(lang: Option<usize>, pedia: &Pedia, id: u32) -> Box<span<String>> {
+    let monster = pedia.monsters.iter().find(|m| (m.id | m.sub_id << 8) == id);
+    (|| {
+        let name_name = format!(
+            "EnemyIndex{:03}",
+            monster?.boss_init_set_data.as_ref()?.enemy_type
+        );
+        Some(gen_multi_lang(
+            lang,
+            pedia.monster_names.get_entry(&name_name)?,
+        ))
+    })()
+    .unwrap_or_else(|| {
+        ui_args(
+            lang,
+            "Monster {}",
+            &[&format!("{:03}_{:02}", id & 0xFF, id >> 8)],
+        )
+    })
+}
+
+fn gen_ranking_table(
+    lang: Option<usize>,
+    ranking: &[CrownRank],
+    kind: CrownKind,
+) -> Box<table<String>> {
+    html!(<table>
+        <thead><tr>
+            <th>{ui(lang, "Quest")}</th>
+            <th>{ui(lang, "Mini crown")}</th>
+            <th>{ui(lang, "Silver crown")}</th>
+            <th>{ui(lang, "Gold crown")}</th>
+        </tr></thead>
+        <tbody>{
+            sort_crown_ranking(ranking, kind).into_iter().map(|rank| {
+                let quest = rank.quest;
+                html!(<tr>
+                    <td><a href={format!("/quest/{:06}.html", quest.param.quest_no)}>
+                        {gen_quest_tag(lang, quest.param.enemy_level, quest.param.quest_level)}
+                        {quest.name.as_ref().map_or(
+                            ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                            |msg| gen_multi_lang(lang, msg)
+                        )}
+                    </a></td>
+                    <td>{text!("{:.1}%", rank.odds.mini)}</td>
+                    <td>{text!("{:.1}%", rank.odds.silver)}</td>
+                    <td>{text!("{:.1}%", rank.odds.gold)}</td>
+                </tr>)
+            })
+        }</tbody>
+    </table>)
+}
+
+fn gen_crown_ranking(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    id: u32,
+    ranking: &[CrownRank],
+    path: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(path)?;
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} {:03}_{:02} - MHRice", ui_text(lang, "Crowns"), id & 0xFF, id >> 8)}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Crown hunting: ")} {gen_monster_name(lang, pedia, id)}</h1>
+                <p><a href={format!("/monster/{}", crown_page(id))}>{ui(lang, "Back to monster")}</a></p>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Best quests for gold crown")}</h2>
+                { gen_ranking_table(lang, ranking, CrownKind::Gold) }
+                </section>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Best quests for silver crown")}</h2>
+                { gen_ranking_table(lang, ranking, CrownKind::Silver) }
+                </section>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Best quests for mini crown")}</h2>
+                { gen_ranking_table(lang, ranking, CrownKind::Mini) }
+                </section>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(path, doc.to_string())?;
+    Ok(())
+}
+
+pub fn gen_crowns(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("crown.html"))?;
+    let crown_path = root.join("crown");
+    create_dir_all(&crown_path)?;
+
+    let rankings = crown_rankings(pedia_ex);
+    for (&id, ranking) in &rankings {
+        gen_crown_ranking(
+            lang,
+            pedia,
+            id,
+            ranking,
+            &crown_path.join(crown_page(id)),
+            manifest,
+        )?;
+    }
+
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Crowns"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Crown hunting")}</h1>
+                <ul class="mh-list-monster">{
+                    rankings.keys().map(|&id| html!(<li class="mh-list-monster">
+                        <a href={format!("/crown/{}", crown_page(id))}>
+                            <img class="mh-list-monster-icon"
+                                src={format!("/resources/em{:03}_{:02}_icon.png", id & 0xFF, id >> 8)} />
+                            <div>{gen_monster_name(lang, pedia, id)}</div>
+                        </a>
+                    </li>))
+                }</ul>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(&root.join("crown.html"), doc.to_string())?;
+    Ok(())
+}
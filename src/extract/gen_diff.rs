@@ -0,0 +1,172 @@
+use super::diff::*;
+use super::gen_item::*;
+use super::gen_website::*;
+use super::manifest::*;
+use anyhow::*;
+use std::path::*;
+use typed_html::{dom::*, elements::*, html, text};
+
+fn gen_field_changes(lang: Option<usize>, changes: &[FieldChange]) -> Box<table<String>> {
+    html!(<table>
+        <thead><tr>
+            <th>{ui(lang, "Field")}</th>
+            <th>{ui(lang, "Old")}</th>
+            <th>{ui(lang, "New")}</th>
+        </tr></thead>
+        <tbody>{
+            changes.iter().map(|change| html!(<tr>
+                <td class="is-family-monospace">{text!("{}", change.field)}</td>
+                <td>{text!("{}", change.old)}</td>
+                <td>{text!("{}", change.new)}</td>
+            </tr>))
+        }</tbody>
+    </table>)
+}
+
+fn monster_link(
+    lang: Option<usize>,
+    id: u32,
+    sub_id: u32,
+    is_large: bool,
+    name: Option<&str>,
+) -> Box<a<String>> {
+    let folder = if is_large { "monster" } else { "small-monster" };
+    html!(<a href={format!("/{}/{:03}_{:02}.html", folder, id, sub_id)}>{
+        text!("{}", name.map_or_else(
+            || ui_text_args(lang, "Monster {}", &[&format!("{:03}_{:02}", id, sub_id)]),
+            str::to_owned
+        ))
+    }</a>)
+}
+
+fn quest_link(lang: Option<usize>, quest: &QuestSummary) -> Box<li<String>> {
+    html!(<li><a href={format!("/quest/{:06}.html", quest.quest_no)}>{
+        text!("{}", quest.name.clone().unwrap_or_else(
+            || ui_text_args(lang, "Quest {}", &[&format!("{:06}", quest.quest_no)])
+        ))
+    }</a></li>)
+}
+
+fn armor_link(armor: &ArmorSummary) -> Box<a<String>> {
+    html!(<a href={format!("/armor/{:03}.html", armor.series)}>{
+        text!("{} ({})", armor.name, armor.id)
+    }</a>)
+}
+
+pub fn gen_patch_notes(
+    lang: Option<usize>,
+    diff: &PediaDiff,
+    path: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(path)?;
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Patch notes"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Patch notes")}</h1>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Monsters")}</h2>
+                <ul>
+                    { diff.monsters_added.iter().map(|m| html!(<li>{ui(lang, "Added: ")}
+                        {monster_link(lang, m.id, m.sub_id, m.is_large, None)}</li>)) }
+                    { diff.monsters_removed.iter().map(|m| html!(<li>{
+                        ui_args(lang, "Removed: Monster {}", &[&format!("{:03}_{:02}", m.id, m.sub_id)])
+                    }</li>)) }
+                </ul>
+                {
+                    diff.monsters.iter().map(|monster| html!(<div>
+                        <h3 class="title">{
+                            monster_link(lang, monster.id, monster.sub_id, monster.is_large, monster.name.as_deref())
+                        }</h3>
+                        { monster.hp.map(|(old, new)| html!(<p>{ui_args(lang, "Base HP: {} → {}", &[&old, &new])}</p>)) }
+                        { (!monster.hitzone_changes.is_empty()).then(|| html!(<div>
+                            <h4>{ui(lang, "Hitzones")}</h4>
+                            {gen_field_changes(lang, &monster.hitzone_changes)}
+                        </div>)) }
+                        { (!monster.condition_changes.is_empty()).then(|| html!(<div>
+                            <h4>{ui(lang, "Abnormal status")}</h4>
+                            {gen_field_changes(lang, &monster.condition_changes)}
+                        </div>)) }
+                    </div>))
+                }
+                </section>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Quests")}</h2>
+                <h3 class="title">{ui(lang, "New quests")}</h3>
+                <ul>{ diff.quests_added.iter().map(|quest| quest_link(lang, quest)) }</ul>
+                <h3 class="title">{ui(lang, "Removed quests")}</h3>
+                <ul>{ diff.quests_removed.iter().map(|quest| quest_link(lang, quest)) }</ul>
+                </section>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Armors")}</h2>
+                <h3 class="title">{ui(lang, "New armors")}</h3>
+                <ul>{ diff.armors_added.iter().map(|armor| html!(<li>{armor_link(armor)}</li>)) }</ul>
+                <h3 class="title">{ui(lang, "Removed armors")}</h3>
+                <ul>{ diff.armors_removed.iter().map(|armor| html!(<li>{armor_link(armor)}</li>)) }</ul>
+                {
+                    diff.armors.iter().map(|armor| html!(<div>
+                        <h3 class="title">{armor_link(&armor.armor)}</h3>
+                        {gen_field_changes(lang, &armor.changes)}
+                    </div>))
+                }
+                </section>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Item prices")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Item")}</th>
+                        <th>{ui(lang, "Sell price")}</th>
+                        <th>{ui(lang, "Buy price")}</th>
+                    </tr></thead>
+                    <tbody>{
+                        diff.item_prices.iter().map(|item| html!(<tr>
+                            <td><a href={format!("/item/{}", item_page(item.id))}>
+                                {text!("{}", item.name)}
+                            </a></td>
+                            <td>{text!("{} → {}", item.sell_price.0, item.sell_price.1)}</td>
+                            <td>{text!("{} → {}", item.buy_price.0, item.buy_price.1)}</td>
+                        </tr>))
+                    }</tbody>
+                </table>
+                </section>
+
+                <section class="section">
+                <h2 class="title">{ui(lang, "Text")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "File")}</th>
+                        <th>{ui(lang, "Entry")}</th>
+                        <th>{ui(lang, "Language")}</th>
+                        <th>{ui(lang, "Old")}</th>
+                        <th>{ui(lang, "New")}</th>
+                    </tr></thead>
+                    <tbody>{
+                        diff.msgs.iter().map(|msg| html!(<tr>
+                            <td>{text!("{}", msg.file)}</td>
+                            <td class="is-family-monospace">{text!("{}", msg.entry)}</td>
+                            <td>{text!("{}", msg.language)}</td>
+                            <td>{text!("{}", msg.old.as_deref().unwrap_or("-"))}</td>
+                            <td>{text!("{}", msg.new.as_deref().unwrap_or("-"))}</td>
+                        </tr>))
+                    }</tbody>
+                </table>
+                </section>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(path, doc.to_string())?;
+
+    Ok(())
+}
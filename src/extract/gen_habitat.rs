@@ -0,0 +1,275 @@
+use super::gen_map::{map_name_text, map_page};
+use super::gen_quest::gen_monster_link;
+use super::gen_website::*;
+use super::manifest::*;
+use super::pedia::*;
+use crate::msg::*;
+use crate::rsz::*;
+use anyhow::*;
+use std::collections::BTreeMap;
+use std::fs::create_dir_all;
+use std::path::*;
+use typed_html::{dom::*, elements::*, html, text};
+
+pub fn habitat_page(habitat: usize) -> String {
+    format!("{:02}.html", habitat)
+}
+
+// No message file with habitat names is known, so habitats are named after their map
+fn habitat_name(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    habitat: usize,
+) -> String {
+    match pedia_ex.habitat_maps.get(&habitat) {
+        Some(&map_no) => map_name_text(lang, pedia, map_no),
+        None => ui_text_args(lang, "Habitat {}", &[&habitat]),
+    }
+}
+
+// Entry names are assumed to follow snow.data.monsterList.FamilyType. Families without
+// such an entry fall back to their raw index and are counted in gen_habitats
+fn family_name_entry(pedia: &Pedia, family: i32) -> Option<&MsgEntry> {
+    let name = format!("HN_FamilyType_{:02}", family);
+    pedia
+        .hunter_note_msg
+        .entries
+        .iter()
+        .find(|entry| entry.name == name)
+}
+
+fn gen_family_name(lang: Option<usize>, pedia: &Pedia, family: i32) -> Box<span<String>> {
+    match family_name_entry(pedia, family) {
+        Some(entry) => gen_multi_lang(lang, entry),
+        None => ui_args(lang, "Family {}", &[&family]),
+    }
+}
+
+fn gen_habitat_link(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    habitat: usize,
+) -> Box<a<String>> {
+    html!(<a href={format!("/habitat/{}", habitat_page(habitat))}>
+        {text!("{}", habitat_name(lang, pedia, pedia_ex, habitat))}
+    </a>)
+}
+
+fn gen_progress(cond: i32) -> String {
+    // Same encoding as snow.progress.VillageProgress, where 1 is progress 0 and 0 is never
+    if cond <= 0 {
+        "-".to_owned()
+    } else {
+        format!("{}★", cond - 1)
+    }
+}
+
+pub fn gen_unlock_conditions(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    discovery: &DiscoverEmSetDataParam,
+) -> Box<ul<String>> {
+    let maps = pedia_ex
+        .tour_maps
+        .get(&discovery.em_type)
+        .into_iter()
+        .flatten()
+        .map(|&map_no| map_name_text(lang, pedia, map_no))
+        .collect::<Vec<_>>()
+        .join(", ");
+    html!(<ul>
+        <li>{ui_args(lang, "Village tour: {}", &[&gen_progress(discovery.cond_village)])}</li>
+        <li>{ui_args(lang, "Low rank hub tour: {}", &[&gen_progress(discovery.cond_low)])}</li>
+        <li>{ui_args(lang, "High rank hub tour: {}", &[&gen_progress(discovery.cond_high)])}</li>
+        <li>{ui_args(lang, "Tour maps: {}", &[&maps])}</li>
+    </ul>)
+}
+
+/// Family, habitat links and tour unlock conditions shown on the monster page
+pub fn gen_ecology(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    em_type: EmTypes,
+) -> Option<Box<section<String>>> {
+    let boss = pedia
+        .monster_list
+        .data_list
+        .iter()
+        .find(|boss| boss.em_type == em_type)?;
+    Some(html!(<section class="section">
+        <h2 class="title">{ui(lang, "Ecology")}</h2>
+        <p><a href="/habitat.html">
+            {gen_family_name(lang, pedia, boss.family_type)}
+        </a></p>
+        <p>{ui(lang, "Habitats: ")} {
+            boss.habitat_area.habitats().into_iter().map(|habitat| html!(<span class="tag">
+                {gen_habitat_link(lang, pedia, pedia_ex, habitat)}
+            </span>))
+        }</p>
+        { pedia_ex.discoveries.get(&em_type).map(|discovery| html!(<div>
+            <p>{ui(lang, "Tour unlocks:")}</p>
+            { gen_unlock_conditions(lang, pedia, pedia_ex, discovery) }
+        </div>)) }
+    </section>))
+}
+
+fn monster_id(em_type: EmTypes) -> Option<u32> {
+    match em_type {
+        EmTypes::Em(id) => Some(id),
+        EmTypes::Ems(_) => None,
+    }
+}
+
+fn gen_habitat(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    habitat: usize,
+    monsters: &[&BossMonsterData],
+    path: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(path)?;
+    let map_no = pedia_ex.habitat_maps.get(&habitat).copied();
+    let title = habitat_name(lang, pedia, pedia_ex, habitat);
+    let quests = pedia_ex
+        .quests
+        .iter()
+        .filter(|quest| Some(quest.param.map_no) == map_no);
+
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", title)}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{text!("{}", title)}</h1>
+                { map_no.map(|map_no| html!(<p>
+                    <a href={format!("/map/{}", map_page(map_no))}>{ui(lang, "Starting areas on this map")}</a>
+                </p>)) }
+                <section class="section">
+                <h2 class="title">{ui(lang, "Monsters")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "Family")}</th>
+                        <th>{ui(lang, "Tour unlocks")}</th>
+                    </tr></thead>
+                    <tbody>{
+                        monsters.iter().filter_map(|boss| {
+                            let id = monster_id(boss.em_type)?;
+                            Some(html!(<tr>
+                                <td>{gen_monster_link(lang, pedia, id)}</td>
+                                <td>{gen_family_name(lang, pedia, boss.family_type)}</td>
+                                <td>{ pedia_ex.discoveries.get(&boss.em_type)
+                                    .map(|discovery| gen_unlock_conditions(lang, pedia, pedia_ex, discovery)) }</td>
+                            </tr>))
+                        })
+                    }</tbody>
+                </table>
+                </section>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Quests")}</h2>
+                <ul>{
+                    quests.map(|quest| html!(<li>
+                        <a href={format!("/quest/{:06}.html", quest.param.quest_no)}>
+                        {quest.name.as_ref().map_or(
+                            ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                            |msg| gen_multi_lang(lang, msg)
+                        )}
+                        </a>
+                    </li>))
+                }</ul>
+                </section>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(path, doc.to_string())?;
+    Ok(())
+}
+
+pub fn gen_habitats(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("habitat.html"))?;
+    let mut habitats: BTreeMap<usize, Vec<&BossMonsterData>> = BTreeMap::new();
+    let mut families: BTreeMap<i32, Vec<&BossMonsterData>> = BTreeMap::new();
+    for boss in &pedia.monster_list.data_list {
+        for habitat in boss.habitat_area.habitats() {
+            habitats.entry(habitat).or_default().push(boss);
+        }
+        families.entry(boss.family_type).or_default().push(boss);
+    }
+    let unnamed = families
+        .keys()
+        .filter(|&&family| family_name_entry(pedia, family).is_none())
+        .count();
+    if unnamed != 0 {
+        eprintln!("{} families without a name", unnamed);
+    }
+
+    let habitat_path = root.join("habitat");
+    create_dir_all(&habitat_path)?;
+    for (&habitat, monsters) in &habitats {
+        gen_habitat(
+            lang,
+            pedia,
+            pedia_ex,
+            habitat,
+            monsters,
+            &habitat_path.join(habitat_page(habitat)),
+            manifest,
+        )?;
+    }
+
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Habitats"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Habitats")}</h1>
+                <ul>{
+                    habitats.iter().map(|(&habitat, monsters)| html!(<li>
+                        {gen_habitat_link(lang, pedia, pedia_ex, habitat)}
+                        {ui_args(lang, " ({} monsters)", &[&monsters.len()])}
+                    </li>))
+                }</ul>
+                <h1 class="title">{ui(lang, "Families")}</h1>
+                {
+                    families.iter().map(|(&family, monsters)| html!(<section class="section">
+                        <h2 class="title">
+                            {gen_family_name(lang, pedia, family)}
+                        </h2>
+                        <ul>{
+                            monsters.iter().filter_map(|boss| {
+                                let id = monster_id(boss.em_type)?;
+                                Some(html!(<li>{gen_monster_link(lang, pedia, id)}</li>))
+                            })
+                        }</ul>
+                    </section>))
+                }
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(&root.join("habitat.html"), doc.to_string())?;
+    Ok(())
+}
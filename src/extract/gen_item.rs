@@ -1,8 +1,9 @@
 use super::gen_website::*;
+use super::manifest::*;
 use super::pedia::*;
 use crate::rsz::*;
 use anyhow::*;
-use std::fs::{create_dir, write};
+use std::fs::create_dir_all;
 use std::path::*;
 use typed_html::{dom::*, elements::*, html, text};
 
@@ -15,6 +16,40 @@ pub fn item_page(item: ItemId) -> String {
     }
 }
 
+fn carriable_filter_text(filter: &CarriableFilter) -> &'static str {
+    match filter {
+        CarriableFilter::All => "All",
+        CarriableFilter::Quest => "Quest",
+        CarriableFilter::Hyakuryu => "Hyakuryu",
+        CarriableFilter::Lobby => "Lobby",
+    }
+}
+
+fn item_type_text(type_: &ItemTypes) -> &'static str {
+    match type_ {
+        ItemTypes::Consume => "Consume",
+        ItemTypes::Tool => "Tool",
+        ItemTypes::Material => "Material",
+        ItemTypes::OffcutsMaterial => "Offcuts material",
+        ItemTypes::Bullet => "Bullet",
+        ItemTypes::Bottle => "Bottle",
+        ItemTypes::Present => "Present",
+        ItemTypes::PayOff => "Pay off",
+        ItemTypes::CarryPayOff => "Carry pay off",
+        ItemTypes::Carry => "Carry",
+        ItemTypes::Judge => "Judge",
+        ItemTypes::Antique => "Antique",
+    }
+}
+
+fn item_group_text(group: &ItemGroupTypes) -> &'static str {
+    match group {
+        ItemGroupTypes::Drink => "Drink",
+        ItemGroupTypes::Food => "Food",
+        ItemGroupTypes::Others => "Others",
+    }
+}
+
 fn gen_item_icon(item: &Item) -> Box<div<String>> {
     let icon = format!("/resources/item/{:03}", item.param.icon_chara);
 
@@ -34,24 +69,29 @@ fn gen_item_icon(item: &Item) -> Box<div<String>> {
     gen_colored_icon(item.param.icon_color, &icon, &addons)
 }
 
-pub fn gen_item_label(item: &Item) -> Box<a<String>> {
+pub fn gen_item_label(lang: Option<usize>, item: &Item) -> Box<a<String>> {
     let link = format!("/item/{}", item_page(item.param.id));
     html!(
         <a href={link} class="mh-icon-text">
             {gen_item_icon(item)}
-            <span>{gen_multi_lang(&item.name)}</span>
+            <span>{gen_multi_lang(lang, &item.name)}</span>
         </a>
     )
 }
 
-pub fn gen_item(item: &Item, pedia_ex: &PediaEx<'_>, path: &Path) -> Result<()> {
+pub fn gen_item(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
+    item: &Item,
+    pedia_ex: &PediaEx<'_>,
+) -> Result<String> {
     let material_categories = item.param.material_category.iter().filter_map(|&category| {
         if category == MaterialCategory(0) {
             return None;
         }
         Some(
             if let Some(name) = pedia_ex.material_categories.get(&category) {
-                html!(<span>{gen_multi_lang(name)}" "</span>)
+                html!(<span>{gen_multi_lang(lang, name)}" "</span>)
             } else {
                 html!(<span>{text!("{:?} ", category)}</span>)
             },
@@ -59,58 +99,58 @@ pub fn gen_item(item: &Item, pedia_ex: &PediaEx<'_>, path: &Path) -> Result<()>
     });
 
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>"Item - MHRice"</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Item"))}</title>
+                { head_common(lang, alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
                 <div class="mh-title-icon">
                     {gen_item_icon(item)}
                 </div>
                 <h1 class="title">
-                    {gen_multi_lang(&item.name)}
+                    {gen_multi_lang(lang, &item.name)}
                 </h1>
 
                 <section class="section">
-                <h2 class="title">"Basic data"</h2>
+                <h2 class="title">{ui(lang, "Basic data")}</h2>
                 <div class="mh-kvlist">
-                <p class="mh-kv"><span>"Carriable filter"</span>
-                <span>{text!("{:?}", item.param.cariable_filter)}</span></p>
-                <p class="mh-kv"><span>"Type"</span>
-                <span>{text!("{:?}", item.param.type_)}</span></p>
-                <p class="mh-kv"><span>"Rarity"</span>
+                <p class="mh-kv"><span>{ui(lang, "Carriable filter")}</span>
+                {ui(lang, carriable_filter_text(&item.param.cariable_filter))}</p>
+                <p class="mh-kv"><span>{ui(lang, "Type")}</span>
+                {ui(lang, item_type_text(&item.param.type_))}</p>
+                <p class="mh-kv"><span>{ui(lang, "Rarity")}</span>
                 <span>{text!("{}", item.param.rare.0)}</span></p>
-                <p class="mh-kv"><span>"Maximum carry"</span>
+                <p class="mh-kv"><span>{ui(lang, "Maximum carry")}</span>
                 <span>{text!("{}", item.param.pl_max_count)}</span></p>
-                <p class="mh-kv"><span>"Maximum carry by buddy"</span>
+                <p class="mh-kv"><span>{ui(lang, "Maximum carry by buddy")}</span>
                 <span>{text!("{}", item.param.ot_max_count)}</span></p>
-                <p class="mh-kv"><span>"In item bar"</span>
+                <p class="mh-kv"><span>{ui(lang, "In item bar")}</span>
                 <span>{text!("{}", item.param.show_item_window)}</span></p>
-                <p class="mh-kv"><span>"In action bar"</span>
+                <p class="mh-kv"><span>{ui(lang, "In action bar")}</span>
                 <span>{text!("{}", item.param.show_action_window)}</span></p>
-                <p class="mh-kv"><span>"Infinite"</span>
+                <p class="mh-kv"><span>{ui(lang, "Infinite")}</span>
                 <span>{text!("{}", item.param.infinite)}</span></p>
-                <p class="mh-kv"><span>"Fixed item"</span>
+                <p class="mh-kv"><span>{ui(lang, "Fixed item")}</span>
                 <span>{text!("{}", item.param.default)}</span></p>
-                /*<p class="mh-kv"><span>"SE type"</span>
+                /*<p class="mh-kv"><span>{ui(lang, "SE type")}</span>
                 <span>{text!("{:?}", item.param.se_type)}</span></p>*/
-                <p class="mh-kv"><span>"Sell price"</span>
+                <p class="mh-kv"><span>{ui(lang, "Sell price")}</span>
                 <span>{text!("{}", item.param.sell_price)}</span></p>
-                <p class="mh-kv"><span>"Buy price"</span>
+                <p class="mh-kv"><span>{ui(lang, "Buy price")}</span>
                 <span>{text!("{}", item.param.buy_price)}</span></p>
-                /*<p class="mh-kv"><span>"Rank type"</span>
+                /*<p class="mh-kv"><span>{ui(lang, "Rank type")}</span>
                 <span>{text!("{:?}", item.param.rank_type)}</span></p>*/
-                <p class="mh-kv"><span>"Item group"</span>
-                <span>{text!("{:?}", item.param.item_group)}</span></p>
-                <p class="mh-kv"><span>"Material category"</span>
+                <p class="mh-kv"><span>{ui(lang, "Item group")}</span>
+                {ui(lang, item_group_text(&item.param.item_group))}</p>
+                <p class="mh-kv"><span>{ui(lang, "Material category")}</span>
                 <span>
                     {material_categories}
-                    {text!("{} pt", item.param.category_worth)}
+                    {ui_args(lang, "{} pt", &[&item.param.category_worth])}
                 </span></p>
-                <p class="mh-kv"><span>"Evaluation value"</span>
+                <p class="mh-kv"><span>{ui(lang, "Evaluation value")}</span>
                 <span>{text!("{}",item.param.evaluation_value)}</span></p>
                 </div>
                 </section>
@@ -119,29 +159,33 @@ pub fn gen_item(item: &Item, pedia_ex: &PediaEx<'_>, path: &Path) -> Result<()>
             </body>
         </html>
     );
-    write(&path, doc.to_string())?;
-
-    Ok(())
+    Ok(doc.to_string())
 }
 
-pub fn gen_item_list(pedia_ex: &PediaEx<'_>, root: &Path) -> Result<()> {
+pub fn gen_item_list(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("item.html"))?;
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Items - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Items"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"Item"</h1>
+                <h1 class="title">{ui(lang, "Item")}</h1>
                 <ul class="mh-list-skill">
                 {
                     pedia_ex.items.iter().map(|(&id, item)|{
                         let link = format!("/item/{}", item_page(id));
                         let icon = format!("/resources/item/{:03}", item.param.icon_chara);
                         html!(<li class="mh-list-skill">
-                            {gen_item_label(&item)}
+                            {gen_item_label(lang, &item)}
                         </li>)
                     })
                 }
@@ -151,17 +195,23 @@ pub fn gen_item_list(pedia_ex: &PediaEx<'_>, root: &Path) -> Result<()> {
         </html>
     );
     let quests_path = root.join("item.html");
-    write(&quests_path, doc.to_string())?;
+    manifest.write(&quests_path, doc.to_string())?;
 
     Ok(())
 }
 
-pub fn gen_items(pedia_ex: &PediaEx, root: &Path) -> Result<()> {
+pub fn gen_items(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
     let item_path = root.join("item");
-    create_dir(&item_path)?;
+    create_dir_all(&item_path)?;
     for (&id, item) in &pedia_ex.items {
         let path = item_path.join(item_page(id));
-        gen_item(item, pedia_ex, &path)?
+        let alternates = manifest.alternate_links(&path)?;
+        manifest.page(&path, item, || gen_item(lang, &alternates, item, pedia_ex))?
     }
     Ok(())
 }
@@ -0,0 +1,267 @@
+use super::gen_crown::gen_monster_name;
+use super::gen_website::*;
+use super::manifest::*;
+use super::pedia::*;
+use crate::msg::*;
+use crate::rsz::*;
+use anyhow::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::create_dir_all;
+use std::path::*;
+use typed_html::{dom::*, elements::*, html, text};
+
+pub fn map_page(map_no: i32) -> String {
+    format!("{:02}.html", map_no)
+}
+
+// Unverified: entry names are assumed to follow the map numbering
+pub fn map_name_entry(pedia: &Pedia, map_no: i32) -> Option<&MsgEntry> {
+    let name = format!("Stage_Name_{:02}", map_no);
+    pedia
+        .stage_name_msg
+        .as_ref()?
+        .entries
+        .iter()
+        .find(|entry| entry.name == name)
+}
+
+pub fn gen_map_label(lang: Option<usize>, pedia: &Pedia, map_no: i32) -> Box<span<String>> {
+    match map_name_entry(pedia, map_no) {
+        Some(entry) => gen_multi_lang(lang, entry),
+        None => ui_args(lang, "Map {}", &[&format!("{:02}", map_no)]),
+    }
+}
+
+pub fn map_name_text(lang: Option<usize>, pedia: &Pedia, map_no: i32) -> String {
+    match map_name_entry(pedia, map_no) {
+        Some(entry) => entry.content[lang.unwrap_or(1)].clone(),
+        None => ui_text_args(lang, "Map {}", &[&format!("{:02}", map_no)]),
+    }
+}
+
+fn find_stage_info(monster: &Monster, map_no: i32) -> Option<&StageInfo> {
+    monster
+        .boss_init_set_data
+        .as_ref()?
+        .stage_info_list
+        .iter()
+        .find(|stage| stage.map_type == map_no)
+}
+
+pub fn find_set_info<'a>(
+    pedia: &'a Pedia,
+    em_type: EmTypes,
+    map_no: i32,
+    set_name: &str,
+) -> Option<&'a SetInfo> {
+    let id = match em_type {
+        EmTypes::Em(id) => id,
+        EmTypes::Ems(_) => return None,
+    };
+    let monster = pedia
+        .monsters
+        .iter()
+        .find(|m| (m.id | m.sub_id << 8) == id)?;
+    find_stage_info(monster, map_no)?
+        .set_info_list
+        .iter()
+        .find(|set| set.set_name == set_name)
+}
+
+pub fn gen_spawn_areas(lang: Option<usize>, set: &SetInfo) -> Box<ul<String>> {
+    let total: i32 = set.info.iter().map(|info| info.lot).sum();
+    html!(<ul class="mh-spawn-areas">{
+        set.info.iter().filter(|info| info.lot != 0).map(|info| {
+            let chance = if total != 0 {
+                info.lot as f32 * 100.0 / total as f32
+            } else {
+                0.0
+            };
+            html!(<li>{ui_args(lang, "Area {}: {}%", &[&info.block, &format!("{:.1}", chance)])}</li>)
+        })
+    }</ul>)
+}
+
+// Markers are fitted to their bounding box on the x/z plane, as there is no map picture
+fn gen_spawn_map(lang: Option<usize>, pedia: &Pedia, map_no: i32) -> Option<Box<section<String>>> {
+    let stage = pedia.stages.get(&map_no)?;
+    if stage.spawn_points.is_empty() {
+        return None;
+    }
+
+    let mut min = (f32::MAX, f32::MAX);
+    let mut max = (f32::MIN, f32::MIN);
+    for point in stage.spawn_points.values() {
+        min.0 = min.0.min(point.x);
+        min.1 = min.1.min(point.z);
+        max.0 = max.0.max(point.x);
+        max.1 = max.1.max(point.z);
+    }
+    let extent = (max.0 - min.0).max(max.1 - min.1);
+    let to_percent = |x: f32, z: f32| {
+        if extent > 0.0 {
+            ((x - min.0) * 100.0 / extent, (z - min.1) * 100.0 / extent)
+        } else {
+            (50.0, 50.0)
+        }
+    };
+
+    let markers = stage.spawn_points.iter().map(|(set_name, point)| {
+        let (x, y) = to_percent(point.x, point.z);
+        let style = format!("left: {:.2}%; top: {:.2}%;", x, y);
+        let areas = pedia
+            .monsters
+            .iter()
+            .filter_map(|monster| find_stage_info(monster, map_no))
+            .flat_map(|stage| stage.set_info_list.iter())
+            .filter(|set| &set.set_name == set_name)
+            .flat_map(|set| set.info.iter().filter(|info| info.lot != 0))
+            .map(|info| info.block)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|block| block.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        html!(<div class="mh-spawn-map-marker" style={style.as_str()}>
+            <div class="mh-spawn-map-tooltip">
+                <p class="is-family-monospace">{text!("{}", set_name)}</p>
+                <p>{ui_args(lang, "Areas: {}", &[&areas])}</p>
+                <p>{text!("({:.1}, {:.1}, {:.1})", point.x, point.y, point.z)}</p>
+            </div>
+        </div>)
+    });
+
+    Some(html!(<section class="section">
+        <h2 class="title">{ui(lang, "Spawn positions")}</h2>
+        <div class="mh-spawn-map">{markers}</div>
+    </section>))
+}
+
+fn gen_map(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    map_no: i32,
+    quests: &[&Quest],
+    path: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(path)?;
+    let monsters = pedia.monsters.iter().filter_map(|monster| {
+        let stage = find_stage_info(monster, map_no)?;
+        let id = monster.id | monster.sub_id << 8;
+        Some(html!(<tr>
+            <td><a href={format!("/monster/{:03}_{:02}.html", monster.id, monster.sub_id)}>
+                {gen_monster_name(lang, pedia, id)}
+            </a></td>
+            <td><ul>{
+                stage.set_info_list.iter().map(|set| html!(<li>
+                    <span class="is-family-monospace">{text!("{}", set.set_name)}</span>
+                    {gen_spawn_areas(lang, set)}
+                </li>))
+            }</ul></td>
+        </tr>))
+    });
+
+    let map_name = map_name_text(lang, pedia, map_no);
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", map_name)}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{gen_map_label(lang, pedia, map_no)}</h1>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Monster starting areas")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "Spawn sets")}</th>
+                    </tr></thead>
+                    <tbody>{ monsters }</tbody>
+                </table>
+                </section>
+                { gen_spawn_map(lang, pedia, map_no) }
+                <section class="section">
+                <h2 class="title">{ui(lang, "Quests")}</h2>
+                <ul>{
+                    quests.iter().map(|quest| html!(<li>
+                        <a href={format!("/quest/{:06}.html", quest.param.quest_no)}>
+                        {quest.name.as_ref().map_or(
+                            ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                            |msg| gen_multi_lang(lang, msg)
+                        )}
+                        </a>
+                    </li>))
+                }</ul>
+                </section>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(path, doc.to_string())?;
+    Ok(())
+}
+
+pub fn gen_maps(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("map.html"))?;
+    let mut maps: BTreeMap<i32, Vec<&Quest>> = BTreeMap::new();
+    for quest in &pedia_ex.quests {
+        maps.entry(quest.param.map_no).or_default().push(quest);
+    }
+    let stage_maps: BTreeSet<i32> = pedia
+        .monsters
+        .iter()
+        .filter_map(|monster| monster.boss_init_set_data.as_ref())
+        .flat_map(|data| data.stage_info_list.iter().map(|stage| stage.map_type))
+        .collect();
+    for map_no in stage_maps {
+        maps.entry(map_no).or_default();
+    }
+    maps.remove(&0);
+
+    let map_path = root.join("map");
+    create_dir_all(&map_path)?;
+    for (&map_no, quests) in &maps {
+        gen_map(
+            lang,
+            pedia,
+            map_no,
+            quests,
+            &map_path.join(map_page(map_no)),
+            manifest,
+        )?;
+    }
+
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Maps"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Maps")}</h1>
+                <ul>{
+                    maps.keys().map(|&map_no| html!(<li>
+                        <a href={format!("/map/{}", map_page(map_no))}>{gen_map_label(lang, pedia, map_no)}</a>
+                    </li>))
+                }</ul>
+                </div> </div> </main>
+            </body>
+        </html>
+    );
+
+    manifest.write(&root.join("map.html"), doc.to_string())?;
+    Ok(())
+}
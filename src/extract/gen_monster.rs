@@ -1,23 +1,37 @@
 #![allow(clippy::unnecessary_wraps)]
 
+use super::behavior::*;
+use super::crown::has_crown_ranking;
+use super::gen_crown::crown_page;
+use super::gen_habitat::gen_ecology;
 use super::gen_item::*;
 use super::gen_quest::*;
-use super::gen_website::{gen_multi_lang, head_common, navbar};
+use super::gen_website::{
+    enemy_level_text, gen_multi_lang, gen_quest_tag, head_common, html_lang, navbar, ui, ui_args,
+    ui_text, ui_text_args, AlternateLink,
+};
 use super::pedia::*;
 use crate::rsz::*;
 use anyhow::*;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::fs::write;
-use std::path::*;
 use typed_html::{dom::*, elements::*, html, text};
 
-fn gen_extractive_type(extractive_type: ExtractiveType) -> Result<Box<span<String>>> {
+fn gen_extractive_type(
+    lang: Option<usize>,
+    extractive_type: ExtractiveType,
+) -> Result<Box<span<String>>> {
     match extractive_type {
-        ExtractiveType::Red => Ok(html!(<span class="mh-extract-red">"Red"</span>)),
-        ExtractiveType::White => Ok(html!(<span class="mh-extract-white">"White"</span>)),
-        ExtractiveType::Orange => Ok(html!(<span class="mh-extract-orange">"Orange"</span>)),
-        ExtractiveType::None => Ok(html!(<span class="mh-extract-unknown">"None"</span>)),
+        ExtractiveType::Red => Ok(html!(<span class="mh-extract-red">{ui(lang, "Red")}</span>)),
+        ExtractiveType::White => {
+            Ok(html!(<span class="mh-extract-white">{ui(lang, "White")}</span>))
+        }
+        ExtractiveType::Orange => {
+            Ok(html!(<span class="mh-extract-orange">{ui(lang, "Orange")}</span>))
+        }
+        ExtractiveType::None => {
+            Ok(html!(<span class="mh-extract-unknown">{ui(lang, "None")}</span>))
+        }
     }
 }
 
@@ -30,7 +44,260 @@ fn safe_float(v: f32) -> String {
     }
 }
 
-fn gen_condition_base(data: &ConditionDamageDataBase) -> Vec<Box<dyn TableColumnContent<String>>> {
+fn gen_part_break(data_tune: &EnemyDataTune, index: usize) -> Result<String> {
+    let index = u16::try_from(index)?;
+    let mut part_break_iter = data_tune
+        .enemy_parts_break_data_list
+        .iter()
+        .filter(|p| p.parts_group == index);
+    let part_break = if let Some(part_break) = part_break_iter.next() {
+        part_break
+    } else {
+        return Ok("".to_string());
+    };
+    if part_break_iter.next().is_some() {
+        bail!("Duplicated part break data found");
+    }
+    Ok(part_break
+        .parts_break_data_list
+        .iter()
+        .map(|p| format!("(x{}) {}", p.break_level, p.vital))
+        .collect::<Vec<_>>()
+        .join(" / "))
+}
+
+fn gen_part_loss(lang: Option<usize>, data_tune: &EnemyDataTune, index: usize) -> Result<String> {
+    let index = u16::try_from(index)?;
+    let mut part_loss_iter = data_tune
+        .enemy_parts_loss_data_list
+        .iter()
+        .filter(|p| p.parts_group == index);
+    let part_loss = if let Some(part_loss) = part_loss_iter.next() {
+        part_loss
+    } else {
+        return Ok("".to_string());
+    };
+    if part_loss_iter.next().is_some() {
+        bail!("Duplicated part loss data found");
+    }
+    let attr = match part_loss.parts_loss_data.permit_damage_attr {
+        PermitDamageAttrEnum::Slash => format!("({}) ", ui_text(lang, "Slash")),
+        PermitDamageAttrEnum::Strike => format!("({}) ", ui_text(lang, "Impact")),
+        PermitDamageAttrEnum::All => String::new(),
+    };
+    Ok(format!("{}{}", attr, part_loss.parts_loss_data.vital))
+}
+
+// Parts sharing a collider with the hitzone
+fn meat_parts(collider_mapping: &ColliderMapping, meat: usize) -> Vec<usize> {
+    let meat_colliders = if let Some(colliders) = collider_mapping.meat_map.get(&meat) {
+        colliders
+    } else {
+        return vec![];
+    };
+    collider_mapping
+        .part_map
+        .iter()
+        .filter(|(_, part_colliders)| !part_colliders.is_disjoint(meat_colliders))
+        .map(|(&index, _)| index)
+        .collect()
+}
+
+fn gen_part_map_circle(
+    lang: Option<usize>,
+    monster: &Monster,
+    em_type: EmTypes,
+    part_data: &PartData,
+    to_percent: impl Fn(f32, f32) -> (f32, f32),
+    diameter_percent: f32,
+    pedia_ex: &PediaEx<'_>,
+) -> Result<Box<div<String>>> {
+    let meat = usize::try_from(part_data.em_meat)?;
+    let phase = usize::try_from(part_data.em_meat_group_index)?;
+    let name = pedia_ex
+        .meat_names
+        .get(&MeatKey {
+            em_type,
+            part: meat,
+            phase,
+        })
+        .map_or(ui_args(lang, "Part {}", &[&part_data.part]), |msg| {
+            gen_multi_lang(lang, msg)
+        });
+
+    let groups = monster
+        .meat_data
+        .meat_container
+        .get(meat)
+        .map_or(&[][..], |meats| &meats.meat_group_info[..]);
+    let phases = groups.iter().enumerate().map(|(i, group_info)| {
+        let selected = if i == phase { "is-selected" } else { "" };
+        html!(<tr class=selected>
+            <td>{text!("{}", i)}</td>
+            <td>{text!("{}", group_info.slash)}</td>
+            <td>{text!("{}", group_info.strike)}</td>
+            <td>{text!("{}", group_info.shell)}</td>
+            <td>{text!("{}", group_info.fire)}</td>
+            <td>{text!("{}", group_info.water)}</td>
+            <td>{text!("{}", group_info.ice)}</td>
+            <td>{text!("{}", group_info.elect)}</td>
+            <td>{text!("{}", group_info.dragon)}</td>
+        </tr>)
+    });
+
+    let mut parts = vec![];
+    for index in meat_parts(&monster.collider_mapping, meat) {
+        let stagger = monster
+            .data_tune
+            .enemy_parts_data
+            .get(index)
+            .map_or(0, |part| part.vital);
+        let part_break = gen_part_break(&monster.data_tune, index)?;
+        let part_loss = gen_part_loss(lang, &monster.data_tune, index)?;
+        parts.push(html!(<li>
+            {ui_args(lang, "[{}] Stagger {}", &[&index, &stagger])}
+            {(!part_break.is_empty()).then(|| ui_args(lang, " / Break {}", &[&part_break]))}
+            {(!part_loss.is_empty()).then(|| ui_args(lang, " / Sever {}", &[&part_loss]))}
+        </li>));
+    }
+
+    let (x, y) = to_percent(part_data.circle_pos.x, part_data.circle_pos.y);
+    let style = format!(
+        "left: {:.2}%; top: {:.2}%; width: {:.2}%; height: {:.2}%;",
+        x, y, diameter_percent, diameter_percent
+    );
+    let style = style.as_str();
+
+    Ok(html!(<div class="mh-part-map-circle" style={style}>
+        <div class="mh-part-map-tooltip">
+            <p>{name}</p>
+            <table>
+                <thead><tr>
+                    <th>{ui(lang, "Phase")}</th>
+                    <th>{ui(lang, "Slash")}</th>
+                    <th>{ui(lang, "Impact")}</th>
+                    <th>{ui(lang, "Shot")}</th>
+                    <th>{ui(lang, "Fire")}</th>
+                    <th>{ui(lang, "Water")}</th>
+                    <th>{ui(lang, "Ice")}</th>
+                    <th>{ui(lang, "Thunder")}</th>
+                    <th>{ui(lang, "Dragon")}</th>
+                </tr></thead>
+                <tbody>{phases}</tbody>
+            </table>
+            <ul>{parts}</ul>
+        </div>
+    </div>))
+}
+
+// The hunter's notes part circles, drawn on their own without the notes picture they
+// refer to, which is not extracted. The circles are scaled to fit their bounding box.
+fn gen_part_map(
+    lang: Option<usize>,
+    monster: &Monster,
+    em_type: EmTypes,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+) -> Result<Option<Box<section<String>>>> {
+    let boss = pedia
+        .monster_list
+        .data_list
+        .iter()
+        .find(|boss| boss.em_type == em_type);
+    let part_table = match boss {
+        Some(boss) if !boss.part_table_data.is_empty() => &boss.part_table_data,
+        _ => return Ok(None),
+    };
+
+    let mut min = (f32::MAX, f32::MAX);
+    let mut max = (f32::MIN, f32::MIN);
+    for part_data in part_table {
+        let radius = part_data.circle_size as f32 / 2.0;
+        min.0 = min.0.min(part_data.circle_pos.x - radius);
+        min.1 = min.1.min(part_data.circle_pos.y - radius);
+        max.0 = max.0.max(part_data.circle_pos.x + radius);
+        max.1 = max.1.max(part_data.circle_pos.y + radius);
+    }
+    let extent = (max.0 - min.0).max(max.1 - min.1);
+    if extent <= 0.0 {
+        return Ok(None);
+    }
+    let to_percent = |x: f32, y: f32| ((x - min.0) * 100.0 / extent, (y - min.1) * 100.0 / extent);
+
+    let circles = part_table
+        .iter()
+        .map(|part_data| {
+            let diameter = part_data.circle_size as f32 * 100.0 / extent;
+            gen_part_map_circle(
+                lang, monster, em_type, part_data, to_percent, diameter, pedia_ex,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(html!(<section class="section">
+        <h2 class="title">{ui(lang, "Part map")}</h2>
+        <div class="mh-part-map">
+            {circles}
+        </div>
+    </section>)))
+}
+
+fn gen_behavior(lang: Option<usize>, behavior: &MonsterBehavior) -> Box<section<String>> {
+    let enrage = &behavior.enrage;
+    let sleep = &behavior.sleep_recovery;
+    let exhaust = &behavior.exhaust;
+    let thresholds = enrage
+        .thresholds
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(" / ");
+    html!(<section class="section">
+        <h2 class="title">{ui(lang, "Behavior")}</h2>
+        <h3 class="title">{ui(lang, "Enrage")}</h3>
+        <p>{ui_args(lang, "Thresholds: {}", &[&thresholds])}</p>
+        <p>{ui_args(lang, "Duration: {} (+{} when extended)",
+            &[&enrage.duration, &enrage.duration_extension])}</p>
+        <p>{ui_args(lang, "Attack x{} / Defense x{} / Speed x{}",
+            &[&enrage.attack_multiplier, &enrage.defense_multiplier, &enrage.speed_multiplier])}</p>
+        <h3 class="title">{ui(lang, "HP thresholds")}</h3>
+        <table>
+            <thead><tr>
+                <th>{ui(lang, "Level")}</th>
+                <th>{ui(lang, "Limping")}</th>
+                <th>{ui(lang, "Capturing")}</th>
+            </tr></thead>
+            <tbody>{
+                behavior.hp_thresholds.iter().map(|t| html!(<tr>
+                    <td>{ui(lang, enemy_level_text(t.enemy_level))}</td>
+                    <td>{text!("{}%", t.limp_hp_percent)}</td>
+                    <td>{text!("{}%", t.capture_hp_percent)}</td>
+                </tr>))
+            }</tbody>
+        </table>
+        <p>{ui_args(lang, "Sleep recovering: {} seconds / recover {}% HP",
+            &[&sleep.duration_sec, &sleep.recover_hp_percent])}
+            { sleep.in_combat.then(|| ui(lang, " / can sleep in combat")) }</p>
+        <h3 class="title">{ui(lang, "Exhaust")}</h3>
+        { if exhaust.enabled {
+            html!(<div>
+                <p>{ui_args(lang, "Threshold: {} (+{} each time, up to {})",
+                    &[&exhaust.threshold, &exhaust.threshold_increment, &exhaust.max_threshold])}</p>
+                <p>{ui_args(lang, "Decay: {} every {} seconds",
+                    &[&exhaust.decay, &exhaust.decay_interval_sec])}</p>
+                <p>{ui_args(lang, "Exhausted for {} seconds, losing {} stamina",
+                    &[&exhaust.duration_sec, &exhaust.stamina_drain])}</p>
+            </div>)
+        } else {
+            html!(<div><p>{ui(lang, "Doesn't get exhausted")}</p></div>)
+        }}
+    </section>)
+}
+
+fn gen_condition_base(
+    lang: Option<usize>,
+    data: &ConditionDamageDataBase,
+) -> Vec<Box<dyn TableColumnContent<String>>> {
     vec![
         html!(<td>
             <span class="mh-default-cond">{text!("{} (+{}) → {}",
@@ -41,16 +308,16 @@ fn gen_condition_base(data: &ConditionDamageDataBase) -> Vec<Box<dyn TableColumn
             </span>
         </td>),
         html!(<td>
-            <span class="mh-default-cond">{text!("{} / {} sec",
-                data.default_stock.sub_value, data.default_stock.sub_interval)}</span>
-            <span class="mh-ride-cond">{text!("{} / {} sec",
-                data.ride_stock.sub_value, data.ride_stock.sub_interval)}</span>
+            <span class="mh-default-cond">{ui_args(lang, "{} / {} sec",
+                &[&data.default_stock.sub_value, &data.default_stock.sub_interval])}</span>
+            <span class="mh-ride-cond">{ui_args(lang, "{} / {} sec",
+                &[&data.ride_stock.sub_value, &data.ride_stock.sub_interval])}</span>
         </td>),
         html!(<td>{text!("{}", data.max_stock)}</td>),
-        html!(<td>{text!("{} sec (-{} sec) → {} sec",
-            safe_float(data.active_time), data.sub_active_time, data.min_active_time)}</td>),
-        html!(<td>{text!("+{} sec", data.add_tired_time)}</td>),
-        html!(<td>{text!("{} / {} sec", data.damage, data.damage_interval)}</td>),
+        html!(<td>{ui_args(lang, "{} sec (-{} sec) → {} sec",
+            &[&safe_float(data.active_time), &data.sub_active_time, &data.min_active_time])}</td>),
+        html!(<td>{ui_args(lang, "+{} sec", &[&data.add_tired_time])}</td>),
+        html!(<td>{ui_args(lang, "{} / {} sec", &[&data.damage, &data.damage_interval])}</td>),
     ]
 }
 
@@ -68,66 +335,71 @@ fn gen_disabled(used: ConditionDamageDataUsed, is_preset: Option<bool>) -> Strin
 }
 
 fn gen_condition_paralyze(
+    lang: Option<usize>,
     is_preset: bool,
     data: &ParalyzeDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Paralyze"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset={}", data.preset_type)} </td>
+            <td>{ui(lang, "Paralyze")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_sleep(
+    lang: Option<usize>,
     is_preset: bool,
     data: &SleepDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Sleep"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Sleep")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_stun(
+    lang: Option<usize>,
     is_preset: bool,
     data: &StunDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Stun"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Stun")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_stamina(
+    lang: Option<usize>,
     is_preset: bool,
     data: &StaminaDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Exhaust"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Stamina reduction = {}, Preset={}", data.sub_stamina, data.preset_type)} </td>
+            <td>{ui(lang, "Exhaust")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Stamina reduction = {}, Preset = {}", &[&data.sub_stamina, &data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_flash(
+    lang: Option<usize>,
     is_preset: bool,
     data: &FlashDamageData,
     used: ConditionDamageDataUsed,
@@ -137,48 +409,48 @@ fn gen_condition_flash(
         .ignore_refresh_stance
         .contains(StanceStatusFlags::STAND)
     {
-        ignore_refresh_stance.push("stand");
+        ignore_refresh_stance.push(ui_text(lang, "Stand"));
     }
 
     if data.ignore_refresh_stance.contains(StanceStatusFlags::FLY) {
-        ignore_refresh_stance.push("fly");
+        ignore_refresh_stance.push(ui_text(lang, "Fly"));
     }
 
     if data
         .ignore_refresh_stance
         .contains(StanceStatusFlags::DIVING)
     {
-        ignore_refresh_stance.push("diving");
+        ignore_refresh_stance.push(ui_text(lang, "Diving"));
     }
 
     if data.ignore_refresh_stance.contains(StanceStatusFlags::WALL) {
-        ignore_refresh_stance.push("wall");
+        ignore_refresh_stance.push(ui_text(lang, "Wall"));
     }
 
     if data
         .ignore_refresh_stance
         .contains(StanceStatusFlags::CEILING)
     {
-        ignore_refresh_stance.push("ceiling");
+        ignore_refresh_stance.push(ui_text(lang, "Ceiling"));
     }
 
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Flash"</td>
-            { gen_condition_base(&data.base) }
+            <td>{ui(lang, "Flash")}</td>
+            { gen_condition_base(lang, &data.base) }
             <td>
             { data.damage_lvs.iter().map(|lv| {
                 html!(<div> {
-                    text!("Activate count = {}, Active time = {}",
-                    lv.activate_count, lv.active_time)
+                    ui_args(lang, "Activate count = {}, Active time = {}",
+                    &[&lv.activate_count, &lv.active_time])
                 } </div>)
             }) }
             <br />
-            {text!("Ignore refresh stance = {}", ignore_refresh_stance.join(", "))}
+            {ui_args(lang, "Ignore refresh stance = {}", &[&ignore_refresh_stance.join(", ")])}
             <br />
-            {text!("Distance = {} ~ {}, Angle = {}", data.min_distance, data.max_distance, data.angle)}
+            {ui_args(lang, "Distance = {} ~ {}, Angle = {}", &[&data.min_distance, &data.max_distance, &data.angle])}
             <br />
-            {text!("Preset = {}", data.preset_type)}
+            {ui_args(lang, "Preset = {}", &[&data.preset_type])}
             </td>
         </tr>
     );
@@ -186,76 +458,80 @@ fn gen_condition_flash(
 }
 
 fn gen_condition_poison(
+    lang: Option<usize>,
     is_preset: bool,
     data: &PoisonDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Poison"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Poison")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_blast(
+    lang: Option<usize>,
     is_preset: bool,
     data: &BlastDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Blast"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Blast damage = {}, Preset = {}", data.blast_damage, data.preset_type)} </td>
+            <td>{ui(lang, "Blast")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Blast damage = {}, Preset = {}", &[&data.blast_damage, &data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_ride(
+    lang: Option<usize>,
     data: &MarionetteStartDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let use_data = match data.use_data {
-        UseDataType::Common => "common",
-        UseDataType::Unique => "unique",
+        UseDataType::Common => "Common data",
+        UseDataType::Unique => "Unique data",
     };
     let content = html!(
         <tr class={gen_disabled(used, None).as_str()}>
-            <td>"Ride"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("{}, Nora first limit = {}", use_data, data.nora_first_limit)} </td>
+            <td>{ui(lang, "Ride")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "{}, Nora first limit = {}", &[&ui_text(lang, use_data), &data.nora_first_limit])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_water(
+    lang: Option<usize>,
     is_preset: bool,
     data: &WaterDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Water"</td>
-            { gen_condition_base(&data.base) }
+            <td>{ui(lang, "Water")}</td>
+            { gen_condition_base(lang, &data.base) }
             <td>
-            {text!("Melee hzv adjust: hard = {}, soft = {}, judge = {}",
-                data.slash_strike_adjust.hard_meat_adjust_value,
-                data.slash_strike_adjust.soft_meat_adjust_value,
-                data.slash_strike_adjust.judge_meat_value
-            )}
+            {ui_args(lang, "Melee hzv adjust: hard = {}, soft = {}, judge = {}", &[
+                &data.slash_strike_adjust.hard_meat_adjust_value,
+                &data.slash_strike_adjust.soft_meat_adjust_value,
+                &data.slash_strike_adjust.judge_meat_value
+            ])}
             <br />
-            {text!("Shot hzv adjust: hard = {}, soft = {}, judge = {}",
-                data.shell_adjust.hard_meat_adjust_value,
-                data.shell_adjust.soft_meat_adjust_value,
-                data.shell_adjust.judge_meat_value
-            )}
+            {ui_args(lang, "Shot hzv adjust: hard = {}, soft = {}, judge = {}", &[
+                &data.shell_adjust.hard_meat_adjust_value,
+                &data.shell_adjust.soft_meat_adjust_value,
+                &data.shell_adjust.judge_meat_value
+            ])}
             <br />
-            {text!("Preset = {}", data.preset_type)}
+            {ui_args(lang, "Preset = {}", &[&data.preset_type])}
             </td>
         </tr>
     );
@@ -263,61 +539,64 @@ fn gen_condition_water(
 }
 
 fn gen_condition_fire(
+    lang: Option<usize>,
     is_preset: bool,
     data: &FireDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Fire"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Hit-damage rate = {}, Preset = {}", data.hit_damage_rate, data.preset_type)} </td>
+            <td>{ui(lang, "Fire")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Hit-damage rate = {}, Preset = {}", &[&data.hit_damage_rate, &data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_ice(
+    lang: Option<usize>,
     is_preset: bool,
     data: &IceDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Ice"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Motion speed rate = {}, Preset = {}", data.motion_speed_rate, data.preset_type)} </td>
+            <td>{ui(lang, "Ice")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Motion speed rate = {}, Preset = {}", &[&data.motion_speed_rate, &data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_thunder(
+    lang: Option<usize>,
     is_preset: bool,
     data: &ThunderDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Thunder"</td>
-            { gen_condition_base(&data.base) }
+            <td>{ui(lang, "Thunder")}</td>
+            { gen_condition_base(lang, &data.base) }
             <td>
-            {text!("Stun hzv adjust: rate = {}, min = {}, max = {}, default = {}",
-                data.stun_meat_adjust.hit_damage_to_stun_rate,
-                data.stun_meat_adjust.hit_damage_to_stun_min,
-                data.stun_meat_adjust.hit_damage_to_stun_max,
-                data.stun_meat_adjust.default_stun_damage_rate
-            )}
+            {ui_args(lang, "Stun hzv adjust: rate = {}, min = {}, max = {}, default = {}", &[
+                &data.stun_meat_adjust.hit_damage_to_stun_rate,
+                &data.stun_meat_adjust.hit_damage_to_stun_min,
+                &data.stun_meat_adjust.hit_damage_to_stun_max,
+                &data.stun_meat_adjust.default_stun_damage_rate
+            ])}
             <br />
-            {text!("Normal hzv adjust: rate = {}, min = {}, max = {}, default = {}",
-                data.normal_meat_adjust.hit_damage_to_stun_rate,
-                data.normal_meat_adjust.hit_damage_to_stun_min,
-                data.normal_meat_adjust.hit_damage_to_stun_max,
-                data.normal_meat_adjust.default_stun_damage_rate
-            )}
+            {ui_args(lang, "Normal hzv adjust: rate = {}, min = {}, max = {}, default = {}", &[
+                &data.normal_meat_adjust.hit_damage_to_stun_rate,
+                &data.normal_meat_adjust.hit_damage_to_stun_min,
+                &data.normal_meat_adjust.hit_damage_to_stun_max,
+                &data.normal_meat_adjust.default_stun_damage_rate
+            ])}
             <br />
-            {text!("Stun active limit = {}, Preset = {}",
-                data.stun_active_limit, data.preset_type)}
+            {ui_args(lang, "Stun active limit = {}, Preset = {}",
+                &[&data.stun_active_limit, &data.preset_type])}
             </td>
         </tr>
     );
@@ -325,132 +604,141 @@ fn gen_condition_thunder(
 }
 
 fn gen_condition_fall_trap(
+    lang: Option<usize>,
     is_preset: bool,
     data: &FallTrapDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Fall trap"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Fall trap")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_fall_quick_sand(
+    lang: Option<usize>,
     is_preset: bool,
     data: &FallQuickSandDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Quick sand"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Quick sand")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_fall_otomo_trap(
+    lang: Option<usize>,
     is_preset: bool,
     data: &FallOtomoTrapDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Buddy fall trap"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Poison stacking = {}, Preset = {}",
-                data.already_poison_stock_value, data.preset_type)} </td>
+            <td>{ui(lang, "Buddy fall trap")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Poison stacking = {}, Preset = {}",
+                &[&data.already_poison_stock_value, &data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_shock_trap(
+    lang: Option<usize>,
     is_preset: bool,
     data: &ShockTrapDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Shock trap"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Shock trap")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_shock_otomo_trap(
+    lang: Option<usize>,
     is_preset: bool,
     data: &ShockTrapDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Buddy shock trap"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Buddy shock trap")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_capture(
+    lang: Option<usize>,
     is_preset: bool,
     data: &CaptureDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Capture"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Capture")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_dung(
+    lang: Option<usize>,
     is_preset: bool,
     data: &KoyashiDamageData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Dung"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Preset = {}", data.preset_type)} </td>
+            <td>{ui(lang, "Dung")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Preset = {}", &[&data.preset_type])} </td>
         </tr>
     );
     Ok(content)
 }
 
 fn gen_condition_steel_fang(
+    lang: Option<usize>,
     is_preset: bool,
     data: &SteelFangData,
     used: ConditionDamageDataUsed,
 ) -> Result<Box<tr<String>>> {
     let content = html!(
         <tr class={gen_disabled(used, Some(is_preset)).as_str()}>
-            <td>"Steel fang"</td>
-            { gen_condition_base(&data.base) }
-            <td> {text!("Active limit = {}, Preset = {}, Unique target param = {}",
-                data.active_limit_count, data.preset_type, data.is_unique_target_param)}
+            <td>{ui(lang, "Steel fang")}</td>
+            { gen_condition_base(lang, &data.base) }
+            <td> {ui_args(lang, "Active limit = {}, Preset = {}, Unique target param = {}",
+                &[&data.active_limit_count, &data.preset_type, &data.is_unique_target_param])}
                 <br />
-                {text!("Distance = {} ~ {}, Angle = {}",
-                data.min_distance, data.max_distance, data.angle)}
+                {ui_args(lang, "Distance = {} ~ {}, Angle = {}",
+                &[&data.min_distance, &data.max_distance, &data.angle])}
             </td>
         </tr>
     );
     Ok(content)
 }
 
-fn gen_reward_table<'a>(
+pub fn gen_reward_table<'a>(
+    lang: Option<usize>,
     pedia_ex: &'a PediaEx,
     item: &'a [ItemId],
     num: &'a [u32],
@@ -462,7 +750,7 @@ fn gen_reward_table<'a>(
         .filter(|&((&item, _), _)| item != ItemId::None)
         .map(move |((&item, &num), probability)| {
             let item = if let Some(item) = pedia_ex.items.get(&item) {
-                html!(<span>{gen_item_label(item)}</span>)
+                html!(<span>{gen_item_label(lang, item)}</span>)
             } else {
                 html!(<span>{text!("{:?}", item)}</span>)
             };
@@ -475,6 +763,7 @@ fn gen_reward_table<'a>(
 }
 
 fn gen_grouped_reward_table<'a>(
+    lang: Option<usize>,
     pedia_ex: &'a PediaEx,
     drop_dictionary: &'a HashMap<EnemyRewardPopTypes, Vec<String>>,
     reward_type: &'a [EnemyRewardPopTypes],
@@ -497,7 +786,7 @@ fn gen_grouped_reward_table<'a>(
                 .enumerate()
                 .map(move |(i, ((&item, &num), &probability))| {
                     let item = if let Some(item) = pedia_ex.items.get(&item) {
-                        html!(<span>{gen_item_label(item)}</span>)
+                        html!(<span>{gen_item_label(lang, item)}</span>)
                     } else {
                         html!(<span>{text!("{:?}", item)}</span>)
                     };
@@ -521,6 +810,7 @@ fn gen_grouped_reward_table<'a>(
 }
 
 pub fn gen_lot(
+    lang: Option<usize>,
     monster: &Monster,
     em_type: EmTypes,
     rank: QuestRank,
@@ -533,21 +823,24 @@ pub fn gen_lot(
     };
 
     let mut drop_dictionary = HashMap::new();
-    drop_dictionary.insert(EnemyRewardPopTypes::MainBody, vec!["Main body".to_string()]);
+    drop_dictionary.insert(
+        EnemyRewardPopTypes::MainBody,
+        vec![ui_text(lang, "Main body")],
+    );
     drop_dictionary.insert(
         EnemyRewardPopTypes::PartsLoss1,
-        vec!["Severed part A".to_string()],
+        vec![ui_text(lang, "Severed part A")],
     );
     drop_dictionary.insert(
         EnemyRewardPopTypes::PartsLoss2,
-        vec!["Severed part B".to_string()],
+        vec![ui_text(lang, "Severed part B")],
     );
-    drop_dictionary.insert(EnemyRewardPopTypes::Unique1, vec!["Special".to_string()]);
+    drop_dictionary.insert(EnemyRewardPopTypes::Unique1, vec![ui_text(lang, "Special")]);
 
     drop_dictionary
         .entry(monster.drop_item.marionette_rewad_pop_type)
         .or_default()
-        .push("Riding".to_string());
+        .push(ui_text(lang, "Riding"));
 
     for (i, entry) in monster
         .drop_item
@@ -559,7 +852,7 @@ pub fn gen_lot(
             drop_dictionary
                 .entry(drop.enemy_reward_pop_type)
                 .or_default()
-                .push(format!("Drop {} - {}%", i, drop.percentage))
+                .push(ui_text_args(lang, "Drop {} - {}%", &[&i, &drop.percentage]))
         }
     }
 
@@ -569,17 +862,17 @@ pub fn gen_lot(
     };
 
     html!(<section>
-        <h2 class="title">{text!("{}", header)}</h2>
+        <h2 class="title">{ui(lang, header)}</h2>
         <div class="mh-reward-tables">
 
         <div class="box">
         <table>
             <thead><tr>
-                <th>"Target rewards"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "Target rewards")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
-                gen_reward_table(pedia_ex,
+                gen_reward_table(lang, pedia_ex,
                     &lot.target_reward_item_id_list,
                     &lot.target_reward_num_list,
                     &lot.target_reward_probability_list)
@@ -590,12 +883,12 @@ pub fn gen_lot(
         <div class="box">
         <table>
             <thead><tr>
-                <th>"Part"</th>
-                <th>"Carves"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "Part")}</th>
+                <th>{ui(lang, "Carves")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
-                gen_grouped_reward_table(pedia_ex,
+                gen_grouped_reward_table(lang, pedia_ex,
                     &drop_dictionary,
                     &lot.enemy_reward_type_list,
                     &lot.hagitory_reward_item_id_list,
@@ -608,11 +901,11 @@ pub fn gen_lot(
         <div class="box">
         <table>
             <thead><tr>
-                <th>"Capture rewards"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "Capture rewards")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
-                gen_reward_table(pedia_ex,
+                gen_reward_table(lang, pedia_ex,
                     &lot.capture_reward_item_id_list,
                     &lot.capture_reward_num_list,
                     &lot.capture_reward_probability_list)
@@ -623,9 +916,9 @@ pub fn gen_lot(
         <div class="box">
         <table>
             <thead><tr>
-                <th>"Part"</th>
-                <th>"Broken part rewards"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "Part")}</th>
+                <th>{ui(lang, "Broken part rewards")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
                 lot.parts_break_list.iter()
@@ -640,14 +933,14 @@ pub fn gen_lot(
                             .enumerate()
                             .map(move |(i, ((&item, &num), &probability))|{
                                 let item = if let Some(item) = pedia_ex.items.get(&item) {
-                                    html!(<span>{gen_item_label(item)}</span>)
+                                    html!(<span>{gen_item_label(lang, item)}</span>)
                                 } else {
                                     html!(<span>{text!("{:?}", item)}</span>)
                                 };
 
                                 let part_name = if let Some(name) =
                                     pedia_ex.parts_dictionary.get(&(em_type, part)) {
-                                    gen_multi_lang(name)
+                                    gen_multi_lang(lang, name)
                                 } else {
                                     html!(<span>{text!("{:?}", part)}</span>)
                                 };
@@ -671,7 +964,7 @@ pub fn gen_lot(
                                                 EnemyPartsBreakRewardDataConditionType::Other => "Any of:"
                                             };
                                             html!(<div>
-                                                {text!("{}", operator)}
+                                                {ui(lang, operator)}
                                                 <ul>
                                                 {conds}
                                                 </ul>
@@ -700,12 +993,12 @@ pub fn gen_lot(
         <div class="box">
         <table>
             <thead><tr>
-                <th>"Part"</th>
-                <th>"Dropped materials"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "Part")}</th>
+                <th>{ui(lang, "Dropped materials")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
-                gen_grouped_reward_table(pedia_ex,
+                gen_grouped_reward_table(lang, pedia_ex,
                     &drop_dictionary,
                     &lot.drop_reward_type_list,
                     &lot.drop_reward_item_id_list,
@@ -718,11 +1011,11 @@ pub fn gen_lot(
         <div class="box">
         <table>
             <thead><tr>
-                <th>"From buddy"</th>
-                <th>"Probability"</th>
+                <th>{ui(lang, "From buddy")}</th>
+                <th>{ui(lang, "Probability")}</th>
             </tr></thead>
             <tbody> {
-                gen_reward_table(pedia_ex,
+                gen_reward_table(lang, pedia_ex,
                     &lot.otomo_reward_item_id_list,
                     &lot.otomo_reward_num_list,
                     &lot.otomo_reward_probability_list)
@@ -735,15 +1028,14 @@ pub fn gen_lot(
 }
 
 pub fn gen_monster(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
     is_large: bool,
     monster: &Monster,
     pedia: &Pedia,
     pedia_ex: &PediaEx<'_>,
-    folder: &Path,
-) -> Result<()> {
+) -> Result<String> {
     let collider_mapping = &monster.collider_mapping;
-    let enemy_parts_break_data_list = &monster.data_tune.enemy_parts_break_data_list;
-    let enemy_parts_loss_data_list = &monster.data_tune.enemy_parts_loss_data_list;
     let meat_figure = format!(
         "/resources/{}{:03}_{:02}_meat.png",
         if is_large { "em" } else { "ems" },
@@ -768,24 +1060,25 @@ pub fn gen_monster(
     let monster_em_type =
         if is_large { EmTypes::Em } else { EmTypes::Ems }(monster_id | (monster_sub_id << 8));
     let condition_preset = &pedia.condition_preset;
+    let part_map = gen_part_map(lang, monster, monster_em_type, pedia, pedia_ex)?;
 
     let quest_list = html!(
         <section class="section">
-        <h2 class="title">"Quests"</h2>
+        <h2 class="title">{ui(lang, "Quests")}</h2>
         <table>
             <thead><tr>
-                <th>"Quest"</th>
-                <th>"Size (?)"</th>
-                <th>"HP"</th>
-                <th>"Attack"</th>
-                <th>"Parts"</th>
-                <th>"Defense"</th>
-                <th>"Element A"</th>
-                <th>"Element B"</th>
-                <th>"Stun"</th>
-                <th>"Exhaust"</th>
-                <th>"Ride"</th>
-                <th>"Stamina"</th>
+                <th>{ui(lang, "Quest")}</th>
+                <th>{ui(lang, "Size (?)")}</th>
+                <th>{ui(lang, "HP")}</th>
+                <th>{ui(lang, "Attack")}</th>
+                <th>{ui(lang, "Parts")}</th>
+                <th>{ui(lang, "Defense")}</th>
+                <th>{ui(lang, "Element A")}</th>
+                <th>{ui(lang, "Element B")}</th>
+                <th>{ui(lang, "Stun")}</th>
+                <th>{ui(lang, "Exhaust")}</th>
+                <th>{ui(lang, "Ride")}</th>
+                <th>{ui(lang, "Stamina")}</th>
             </tr></thead>
             <tbody> {
                 pedia_ex.quests.iter().flat_map(|quest| {
@@ -795,23 +1088,23 @@ pub fn gen_monster(
                     .map(move |(i, em_type)|{
 
                         let target_tag = if quest.param.tgt_em_type.contains(&em_type) {
-                            html!(<span class="tag is-primary">"Target"</span>)
+                            html!(<span class="tag is-primary">{ui(lang, "Target")}</span>)
                         } else {
                             html!(<span />)
                         };
 
                         html!(<tr>
                             <td>
-                                <span class="tag">{text!("{:?}-{:?}", quest.param.enemy_level, quest.param.quest_level)}</span>
+                                {gen_quest_tag(lang, quest.param.enemy_level, quest.param.quest_level)}
                                 <a href={format!("/quest/{:06}.html", quest.param.quest_no)}>
                                 {quest.name.as_ref().map_or(
-                                    html!(<span>{text!("Quest {:06}", quest.param.quest_no)}</span>),
-                                    gen_multi_lang
+                                    ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                                    |msg| gen_multi_lang(lang, msg)
                                 )}
                                 </a>
                                 {target_tag}
                             </td>
-                            { gen_quest_monster_data(quest.enemy_param.as_ref().map(|p|&p.param),
+                            { gen_quest_monster_data(lang, quest.enemy_param.as_ref().map(|p|&p.param),
                                 em_type, i, pedia, pedia_ex) }
                         </tr>)
                     })
@@ -820,16 +1113,16 @@ pub fn gen_monster(
             {
                 if let Some(&discovery) = pedia_ex.discoveries.get(&monster_em_type) {
                     vec![
-                        html!(<tr><td>"Village tour"</td>{
-                            gen_quest_monster_data(Some(&discovery.param),
+                        html!(<tr><td>{ui(lang, "Village tour")}</td>{
+                            gen_quest_monster_data(lang, Some(&discovery.param),
                                 monster_em_type, 0, pedia, pedia_ex)
                         }</tr>),
-                        html!(<tr><td>"Low rank tour"</td>{
-                            gen_quest_monster_data(Some(&discovery.param),
+                        html!(<tr><td>{ui(lang, "Low rank tour")}</td>{
+                            gen_quest_monster_data(lang, Some(&discovery.param),
                                 monster_em_type, 1, pedia, pedia_ex)
                         }</tr>),
-                        html!(<tr><td>"High rank tour"</td>{
-                            gen_quest_monster_data(Some(&discovery.param),
+                        html!(<tr><td>{ui(lang, "High rank tour")}</td>{
+                            gen_quest_monster_data(lang, Some(&discovery.param),
                                 monster_em_type, 2, pedia, pedia_ex)
                         }</tr>)
                     ]
@@ -842,14 +1135,15 @@ pub fn gen_monster(
         </section>
     );
 
+    let monster_label = format!("{:03}_{:02}", monster.id, monster.sub_id);
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Monster {:03}_{:02} - MHRice", monster.id, monster.sub_id)}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text_args(lang, "Monster {}", &[&monster_label]))}</title>
+                { head_common(lang, alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
                 <div class="mh-monster-header">
                     <img src=icon />
@@ -861,57 +1155,52 @@ pub fn gen_monster(
                                     monster.id, monster.sub_id))?
                                 .enemy_type);
                             pedia.monster_aliases.get_entry(&name_name).map_or(
-                                html!(<span>{text!("Monster {:03}_{:02}", monster.id, monster.sub_id)}</span>),
-                                gen_multi_lang
+                                ui_args(lang, "Monster {}", &[&monster_label]),
+                                |msg| gen_multi_lang(lang, msg)
                             )
                         } else {
-                            html!(<span>{text!("Monster {:03}_{:02}", monster.id, monster.sub_id)}</span>)
+                            ui_args(lang, "Monster {}", &[&monster_label])
                         }
                     }</h1>
                 </div>
                 <section class="section">
-                <h2 class="title">"Basic data"</h2>
-                <p>{ text!("Base HP: {}", monster.data_tune.base_hp_vital) }</p>
-                <p>{ text!("Limping threshold: (village) {}% / (LR) {}% / (HR) {}%",
-                    monster.data_tune.dying_village_hp_vital_rate,
-                    monster.data_tune.dying_low_level_hp_vital_rate,
-                    monster.data_tune.dying_high_level_hp_vital_rate
-                ) }</p>
-                <p>{ text!("Capturing threshold: (village) {}% / (LR) {}% / (HR) {}%",
-                    monster.data_tune.capture_village_hp_vital_rate,
-                    monster.data_tune.capture_low_level_hp_vital_rate,
-                    monster.data_tune.capture_high_level_hp_vital_rate
-                ) }</p>
-                <p>{ text!("Sleep recovering: {} seconds / recover {}% HP",
-                    monster.data_tune.self_sleep_time,
-                    monster.data_tune.self_sleep_recover_hp_vital_rate
-                ) }</p>
+                <h2 class="title">{ui(lang, "Basic data")}</h2>
+                <p>{ ui_args(lang, "Base HP: {}", &[&monster.data_tune.base_hp_vital]) }</p>
+                { (is_large && has_crown_ranking(pedia_ex, monster_em_type)).then(|| html!(<p><a href={format!("/crown/{}", crown_page(monster_id | (monster_sub_id << 8)))}>
+                    {ui(lang, "Crown hunting")}
+                </a></p>)) }
                 </section>
 
+                { gen_ecology(lang, pedia, pedia_ex, monster_em_type) }
+
+                { gen_behavior(lang, &monster_behavior(monster)) }
+
                 { quest_list }
 
+                { part_map }
+
                 <section class="section">
-                <h2 class="title">"Hitzone data"</h2>
+                <h2 class="title">{ui(lang, "Hitzone data")}</h2>
                 <img src=meat_figure />
                 <div>
                     <input type="checkbox" onclick="onCheckDisplay(this, 'mh-invalid-meat', null)" id="mh-invalid-meat-check"/>
-                    <label for="mh-invalid-meat-check">"Display invalid parts"</label>
+                    <label for="mh-invalid-meat-check">{ui(lang, "Display invalid parts")}</label>
                 </div>
                 <table>
                     <thead>
                     <tr>
-                        <th>"Part"</th>
-                        <th>"Phase"</th>
-                        <th>"Name"</th>
-                        <th>"Slash"</th>
-                        <th>"Impact"</th>
-                        <th>"Shot"</th>
-                        <th>"Fire"</th>
-                        <th>"Water"</th>
-                        <th>"Ice"</th>
-                        <th>"Thunder"</th>
-                        <th>"Dragon"</th>
-                        <th>"Dizzy"</th>
+                        <th>{ui(lang, "Part")}</th>
+                        <th>{ui(lang, "Phase")}</th>
+                        <th>{ui(lang, "Name")}</th>
+                        <th>{ui(lang, "Slash")}</th>
+                        <th>{ui(lang, "Impact")}</th>
+                        <th>{ui(lang, "Shot")}</th>
+                        <th>{ui(lang, "Fire")}</th>
+                        <th>{ui(lang, "Water")}</th>
+                        <th>{ui(lang, "Ice")}</th>
+                        <th>{ui(lang, "Thunder")}</th>
+                        <th>{ui(lang, "Dragon")}</th>
+                        <th>{ui(lang, "Dizzy")}</th>
                     </tr>
                     </thead>
                     <tbody>{
@@ -960,7 +1249,7 @@ pub fn gen_monster(
                                         em_type: monster_em_type,
                                         part,
                                         phase
-                                    }).map_or(html!(<span></span>), gen_multi_lang);
+                                    }).map_or(html!(<span></span>), |msg| gen_multi_lang(lang, msg));
 
                                     let mut tds = part_common.take().unwrap_or_else(||vec![]);
                                     tds.extend(vec![
@@ -984,21 +1273,21 @@ pub fn gen_monster(
                 </section>
                 <section class="section">
                 <h2 class="title">
-                    "Parts"
+                    {ui(lang, "Parts")}
                 </h2>
                 <img src=parts_group_figure />
                 <div>
                     <input type="checkbox" onclick="onCheckDisplay(this, 'mh-invalid-part', null)" id="mh-invalid-part-check"/>
-                    <label for="mh-invalid-part-check">"Display invalid parts"</label>
+                    <label for="mh-invalid-part-check">{ui(lang, "Display invalid parts")}</label>
                 </div>
                 <table>
                     <thead>
                         <tr>
-                            <th>"Part"</th>
-                            <th>"Stagger"</th>
-                            <th>"Break"</th>
-                            <th>"Sever"</th>
-                            <th>"Extract"</th>
+                            <th>{ui(lang, "Part")}</th>
+                            <th>{ui(lang, "Stagger")}</th>
+                            <th>{ui(lang, "Break")}</th>
+                            <th>{ui(lang, "Sever")}</th>
+                            <th>{ui(lang, "Extract")}</th>
                         </tr>
                     </thead>
                     <tbody>{
@@ -1017,36 +1306,8 @@ pub fn gen_monster(
                                 ""
                             };
 
-                            let index_u16 = u16::try_from(index)?;
-
-                            let mut part_break_iter = enemy_parts_break_data_list.iter()
-                                .filter(|p| p.parts_group == index_u16);
-                            let part_break = if let Some(part_break) = part_break_iter.next() {
-                                if part_break_iter.next().is_some() {
-                                    bail!("Duplicated part break data found");
-                                }
-                                part_break.parts_break_data_list.iter().map(
-                                    |p| format!("(x{}) {}", p.break_level, p.vital)
-                                ).collect::<Vec<_>>().join(" / ")
-                            } else {
-                                "".to_string()
-                            };
-
-                            let mut part_loss_iter = enemy_parts_loss_data_list.iter()
-                                .filter(|p| p.parts_group == index_u16);
-                            let part_loss = if let Some(part_loss) = part_loss_iter.next() {
-                                if part_loss_iter.next().is_some() {
-                                    bail!("Duplicated part loss data found");
-                                }
-                                let attr = match part_loss.parts_loss_data.permit_damage_attr {
-                                    PermitDamageAttrEnum::Slash => "(Slash) ",
-                                    PermitDamageAttrEnum::Strike => "(Impact) ",
-                                    PermitDamageAttrEnum::All => "",
-                                };
-                                format!("{}{}", attr, part_loss.parts_loss_data.vital)
-                            } else {
-                                "".to_string()
-                            };
+                            let part_break = gen_part_break(&monster.data_tune, index)?;
+                            let part_loss = gen_part_loss(lang, &monster.data_tune, index)?;
 
                             Ok(html!(<tr class=hidden>
                                 <td>
@@ -1057,7 +1318,7 @@ pub fn gen_monster(
                                 <td>{ text!("{}", part.vital) }</td>
                                 <td>{ text!("{}", part_break) }</td>
                                 <td>{ text!("{}", part_loss) }</td>
-                                <td>{ gen_extractive_type(part.extractive_type) }</td>
+                                <td>{ gen_extractive_type(lang, part.extractive_type) }</td>
                             </tr>))
                         }).collect::<Result<Vec<_>>>()?
                     }</tbody>
@@ -1066,88 +1327,86 @@ pub fn gen_monster(
 
                 <section>
                 <h2 class="title">
-                    "Abnormal status"
+                    {ui(lang, "Abnormal status")}
                 </h2>
                 <div>
                     <input type="checkbox" onclick="onCheckDisplay(this, 'mh-ride-cond', 'mh-default-cond')" id="mh-ride-cond-check"/>
-                    <label for="mh-ride-cond-check">"Display data for riding"</label>
+                    <label for="mh-ride-cond-check">{ui(lang, "Display data for riding")}</label>
                 </div>
                 <div>
                     <input type="checkbox" onclick="onCheckDisplay(this, 'mh-no-preset', 'mh-preset')" id="mh-preset-check"/>
-                    <label for="mh-preset-check">"Don't override with preset data"</label>
+                    <label for="mh-preset-check">{ui(lang, "Don't override with preset data")}</label>
                 </div>
                 <table>
                     <thead>
                         <tr>
                             <th></th>
-                            <th>"Threshold"</th>
-                            <th>"Decay"</th>
-                            <th>"Max stock"</th>
-                            <th>"Active time"</th>
-                            <th>"Add tired time"</th>
-                            <th>"Damage"</th>
-                            <th>"Additional information"</th>
+                            <th>{ui(lang, "Threshold")}</th>
+                            <th>{ui(lang, "Decay")}</th>
+                            <th>{ui(lang, "Max stock")}</th>
+                            <th>{ui(lang, "Active time")}</th>
+                            <th>{ui(lang, "Add tired time")}</th>
+                            <th>{ui(lang, "Damage")}</th>
+                            <th>{ui(lang, "Additional information")}</th>
                         </tr>
                     </thead>
                     <tbody>
-                        {gen_condition_paralyze(false, &monster.condition_damage_data.paralyze_data, monster.condition_damage_data.use_paralyze)}
-                        {gen_condition_sleep(false, &monster.condition_damage_data.sleep_data, monster.condition_damage_data.use_sleep)}
-                        {gen_condition_stun(false, &monster.condition_damage_data.stun_data, monster.condition_damage_data.use_stun)}
-                        {gen_condition_stamina(false, &monster.condition_damage_data.stamina_data, monster.condition_damage_data.use_stamina)}
-
-                        {gen_condition_paralyze(true, monster.condition_damage_data.paralyze_data.or_preset(condition_preset)?, monster.condition_damage_data.use_paralyze)}
-                        {gen_condition_sleep(true, monster.condition_damage_data.sleep_data.or_preset(condition_preset)?, monster.condition_damage_data.use_sleep)}
-                        {gen_condition_stun(true, monster.condition_damage_data.stun_data.or_preset(condition_preset)?, monster.condition_damage_data.use_stun)}
-                        {gen_condition_stamina(true, monster.condition_damage_data.stamina_data.or_preset(condition_preset)?, monster.condition_damage_data.use_stamina)}
-
-                        {gen_condition_flash(false, &monster.condition_damage_data.flash_data, monster.condition_damage_data.use_flash)}
-                        {gen_condition_flash(true, monster.condition_damage_data.flash_data.or_preset(condition_preset)?, monster.condition_damage_data.use_flash)}
-
-                        {gen_condition_poison(false, &monster.condition_damage_data.poison_data, monster.condition_damage_data.use_poison)}
-                        {gen_condition_blast(false, &monster.condition_damage_data.blast_data, monster.condition_damage_data.use_blast)}
-
-                        {gen_condition_poison(true, monster.condition_damage_data.poison_data.or_preset(condition_preset)?, monster.condition_damage_data.use_poison)}
-                        {gen_condition_blast(true, monster.condition_damage_data.blast_data.or_preset(condition_preset)?, monster.condition_damage_data.use_blast)}
-
-                        {gen_condition_ride(&monster.condition_damage_data.marionette_data, monster.condition_damage_data.use_ride)}
-
-                        {gen_condition_water(false, &monster.condition_damage_data.water_data, monster.condition_damage_data.use_water)}
-                        {gen_condition_fire(false, &monster.condition_damage_data.fire_data, monster.condition_damage_data.use_fire)}
-                        {gen_condition_ice(false, &monster.condition_damage_data.ice_data, monster.condition_damage_data.use_ice)}
-                        {gen_condition_thunder(false, &monster.condition_damage_data.thunder_data, monster.condition_damage_data.use_thunder)}
-                        {gen_condition_fall_trap(false, &monster.condition_damage_data.fall_trap_data, monster.condition_damage_data.use_fall_trap)}
-                        {gen_condition_fall_quick_sand(false, &monster.condition_damage_data.fall_quick_sand_data, monster.condition_damage_data.use_fall_quick_sand)}
-                        {gen_condition_fall_otomo_trap(false, &monster.condition_damage_data.fall_otomo_trap_data, monster.condition_damage_data.use_fall_otomo_trap)}
-                        {gen_condition_shock_trap(false, &monster.condition_damage_data.shock_trap_data, monster.condition_damage_data.use_shock_trap)}
-                        {gen_condition_shock_otomo_trap(false, &monster.condition_damage_data.shock_otomo_trap_data, monster.condition_damage_data.use_shock_otomo_trap)}
-                        {gen_condition_capture(false, &monster.condition_damage_data.capture_data, monster.condition_damage_data.use_capture)}
-                        {gen_condition_dung(false, &monster.condition_damage_data.koyashi_data, monster.condition_damage_data.use_dung)}
-                        {gen_condition_steel_fang(false, &monster.condition_damage_data.steel_fang_data, monster.condition_damage_data.use_steel_fang)}
-
-                        {gen_condition_water(true, monster.condition_damage_data.water_data.or_preset(condition_preset)?, monster.condition_damage_data.use_water)}
-                        {gen_condition_fire(true, monster.condition_damage_data.fire_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fire)}
-                        {gen_condition_ice(true, monster.condition_damage_data.ice_data.or_preset(condition_preset)?, monster.condition_damage_data.use_ice)}
-                        {gen_condition_thunder(true, monster.condition_damage_data.thunder_data.or_preset(condition_preset)?, monster.condition_damage_data.use_thunder)}
-                        {gen_condition_fall_trap(true, monster.condition_damage_data.fall_trap_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_trap)}
-                        {gen_condition_fall_quick_sand(true, monster.condition_damage_data.fall_quick_sand_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_quick_sand)}
-                        {gen_condition_fall_otomo_trap(true, monster.condition_damage_data.fall_otomo_trap_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_otomo_trap)}
-                        {gen_condition_shock_trap(true, <ShockTrapDamageData as ConditionDamage<PresetShockTrapData>>::or_preset(&monster.condition_damage_data.shock_trap_data, condition_preset)?, monster.condition_damage_data.use_shock_trap)}
-                        {gen_condition_shock_otomo_trap(true, <ShockTrapDamageData as ConditionDamage<PresetShockOtomoTrapData>>::or_preset(&monster.condition_damage_data.shock_trap_data, condition_preset)?, monster.condition_damage_data.use_shock_otomo_trap)}
-                        {gen_condition_capture(true, monster.condition_damage_data.capture_data.or_preset(condition_preset)?, monster.condition_damage_data.use_capture)}
-                        {gen_condition_dung(true, monster.condition_damage_data.koyashi_data.or_preset(condition_preset)?, monster.condition_damage_data.use_dung)}
-                        {gen_condition_steel_fang(true, monster.condition_damage_data.steel_fang_data.or_preset(condition_preset)?, monster.condition_damage_data.use_steel_fang)}
+                        {gen_condition_paralyze(lang, false, &monster.condition_damage_data.paralyze_data, monster.condition_damage_data.use_paralyze)}
+                        {gen_condition_sleep(lang, false, &monster.condition_damage_data.sleep_data, monster.condition_damage_data.use_sleep)}
+                        {gen_condition_stun(lang, false, &monster.condition_damage_data.stun_data, monster.condition_damage_data.use_stun)}
+                        {gen_condition_stamina(lang, false, &monster.condition_damage_data.stamina_data, monster.condition_damage_data.use_stamina)}
+
+                        {gen_condition_paralyze(lang, true, monster.condition_damage_data.paralyze_data.or_preset(condition_preset)?, monster.condition_damage_data.use_paralyze)}
+                        {gen_condition_sleep(lang, true, monster.condition_damage_data.sleep_data.or_preset(condition_preset)?, monster.condition_damage_data.use_sleep)}
+                        {gen_condition_stun(lang, true, monster.condition_damage_data.stun_data.or_preset(condition_preset)?, monster.condition_damage_data.use_stun)}
+                        {gen_condition_stamina(lang, true, monster.condition_damage_data.stamina_data.or_preset(condition_preset)?, monster.condition_damage_data.use_stamina)}
+
+                        {gen_condition_flash(lang, false, &monster.condition_damage_data.flash_data, monster.condition_damage_data.use_flash)}
+                        {gen_condition_flash(lang, true, monster.condition_damage_data.flash_data.or_preset(condition_preset)?, monster.condition_damage_data.use_flash)}
+
+                        {gen_condition_poison(lang, false, &monster.condition_damage_data.poison_data, monster.condition_damage_data.use_poison)}
+                        {gen_condition_blast(lang, false, &monster.condition_damage_data.blast_data, monster.condition_damage_data.use_blast)}
+
+                        {gen_condition_poison(lang, true, monster.condition_damage_data.poison_data.or_preset(condition_preset)?, monster.condition_damage_data.use_poison)}
+                        {gen_condition_blast(lang, true, monster.condition_damage_data.blast_data.or_preset(condition_preset)?, monster.condition_damage_data.use_blast)}
+
+                        {gen_condition_ride(lang, &monster.condition_damage_data.marionette_data, monster.condition_damage_data.use_ride)}
+
+                        {gen_condition_water(lang, false, &monster.condition_damage_data.water_data, monster.condition_damage_data.use_water)}
+                        {gen_condition_fire(lang, false, &monster.condition_damage_data.fire_data, monster.condition_damage_data.use_fire)}
+                        {gen_condition_ice(lang, false, &monster.condition_damage_data.ice_data, monster.condition_damage_data.use_ice)}
+                        {gen_condition_thunder(lang, false, &monster.condition_damage_data.thunder_data, monster.condition_damage_data.use_thunder)}
+                        {gen_condition_fall_trap(lang, false, &monster.condition_damage_data.fall_trap_data, monster.condition_damage_data.use_fall_trap)}
+                        {gen_condition_fall_quick_sand(lang, false, &monster.condition_damage_data.fall_quick_sand_data, monster.condition_damage_data.use_fall_quick_sand)}
+                        {gen_condition_fall_otomo_trap(lang, false, &monster.condition_damage_data.fall_otomo_trap_data, monster.condition_damage_data.use_fall_otomo_trap)}
+                        {gen_condition_shock_trap(lang, false, &monster.condition_damage_data.shock_trap_data, monster.condition_damage_data.use_shock_trap)}
+                        {gen_condition_shock_otomo_trap(lang, false, &monster.condition_damage_data.shock_otomo_trap_data, monster.condition_damage_data.use_shock_otomo_trap)}
+                        {gen_condition_capture(lang, false, &monster.condition_damage_data.capture_data, monster.condition_damage_data.use_capture)}
+                        {gen_condition_dung(lang, false, &monster.condition_damage_data.koyashi_data, monster.condition_damage_data.use_dung)}
+                        {gen_condition_steel_fang(lang, false, &monster.condition_damage_data.steel_fang_data, monster.condition_damage_data.use_steel_fang)}
+
+                        {gen_condition_water(lang, true, monster.condition_damage_data.water_data.or_preset(condition_preset)?, monster.condition_damage_data.use_water)}
+                        {gen_condition_fire(lang, true, monster.condition_damage_data.fire_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fire)}
+                        {gen_condition_ice(lang, true, monster.condition_damage_data.ice_data.or_preset(condition_preset)?, monster.condition_damage_data.use_ice)}
+                        {gen_condition_thunder(lang, true, monster.condition_damage_data.thunder_data.or_preset(condition_preset)?, monster.condition_damage_data.use_thunder)}
+                        {gen_condition_fall_trap(lang, true, monster.condition_damage_data.fall_trap_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_trap)}
+                        {gen_condition_fall_quick_sand(lang, true, monster.condition_damage_data.fall_quick_sand_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_quick_sand)}
+                        {gen_condition_fall_otomo_trap(lang, true, monster.condition_damage_data.fall_otomo_trap_data.or_preset(condition_preset)?, monster.condition_damage_data.use_fall_otomo_trap)}
+                        {gen_condition_shock_trap(lang, true, <ShockTrapDamageData as ConditionDamage<PresetShockTrapData>>::or_preset(&monster.condition_damage_data.shock_trap_data, condition_preset)?, monster.condition_damage_data.use_shock_trap)}
+                        {gen_condition_shock_otomo_trap(lang, true, <ShockTrapDamageData as ConditionDamage<PresetShockOtomoTrapData>>::or_preset(&monster.condition_damage_data.shock_trap_data, condition_preset)?, monster.condition_damage_data.use_shock_otomo_trap)}
+                        {gen_condition_capture(lang, true, monster.condition_damage_data.capture_data.or_preset(condition_preset)?, monster.condition_damage_data.use_capture)}
+                        {gen_condition_dung(lang, true, monster.condition_damage_data.koyashi_data.or_preset(condition_preset)?, monster.condition_damage_data.use_dung)}
+                        {gen_condition_steel_fang(lang, true, monster.condition_damage_data.steel_fang_data.or_preset(condition_preset)?, monster.condition_damage_data.use_steel_fang)}
                     </tbody>
                 </table>
                 </section>
 
-                {gen_lot(monster, monster_em_type, QuestRank::Low, pedia_ex)}
-                {gen_lot(monster, monster_em_type, QuestRank::High, pedia_ex)}
+                {gen_lot(lang, monster, monster_em_type, QuestRank::Low, pedia_ex)}
+                {gen_lot(lang, monster, monster_em_type, QuestRank::High, pedia_ex)}
                 </div> </div> </main>
             </body>
         </html>: String
     );
 
-    let file = PathBuf::from(folder).join(format!("{:03}_{:02}.html", monster.id, monster.sub_id));
-    write(file, doc.to_string())?;
-    Ok(())
+    Ok(doc.to_string())
 }
@@ -1,12 +1,15 @@
 use super::pedia::*;
 use crate::gpu::*;
 use crate::gui::*;
+use crate::mdf2::Mdf2;
 use crate::mesh::*;
 use crate::msg::*;
-use crate::pak::PakReader;
+use crate::pak::{PakFileIndex, PakReader, ReadAt};
 use crate::pfb::Pfb;
 use crate::rcol::Rcol;
 use crate::rsz::*;
+use crate::scene::Scene;
+use crate::scn::Scn;
 use crate::tex::*;
 use crate::user::User;
 use crate::uvs::*;
@@ -178,11 +181,102 @@ pub fn gen_monsters(
     Ok(monsters)
 }
 
+// For tables whose path is not verified, so that a wrong path doesn't stop the whole pedia
+fn optional<T>(path: &str, result: Result<T>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            eprintln!("Failed to load {}: {:#}", path, e);
+            None
+        }
+    }
+}
+
 fn get_msg(pak: &mut PakReader<impl Read + Seek>, path: &str) -> Result<Msg> {
     let index = pak.find_file(path)?;
     Msg::new(Cursor::new(pak.read_file(index)?))
 }
 
+// Unverified: the boss init set markers are assumed to live in this scene, named after
+// their set. gen_stages only keeps a scene that has exactly one marker for every set.
+fn stage_scene_path(map_no: i32) -> String {
+    format!("stage/m{0:02}/scene/m{0:02}_enemy_set.scn", map_no)
+}
+
+fn gen_stages(
+    pak: &mut PakReader<impl Read + Seek>,
+    monsters: &[Monster],
+) -> Result<BTreeMap<i32, Stage>> {
+    let mut set_names: BTreeMap<i32, BTreeSet<&str>> = BTreeMap::new();
+    for data in monsters
+        .iter()
+        .filter_map(|m| m.boss_init_set_data.as_ref())
+    {
+        for stage in &data.stage_info_list {
+            set_names
+                .entry(stage.map_type)
+                .or_default()
+                .extend(stage.set_info_list.iter().map(|set| set.set_name.as_str()));
+        }
+    }
+
+    let mut stages = BTreeMap::new();
+    for (map_no, names) in set_names {
+        let path = stage_scene_path(map_no);
+        let index = if let Ok(index) = pak.find_file(&path) {
+            index
+        } else {
+            eprintln!("Scene for map {} not found: {}", map_no, path);
+            continue;
+        };
+        let scene = match Scn::new(Cursor::new(pak.read_file(index)?))
+            .and_then(|scn| Scene::from_scn(&scn))
+        {
+            Ok(scene) => scene,
+            Err(e) => {
+                eprintln!("Failed to load scene for map {}: {:#}", map_no, e);
+                continue;
+            }
+        };
+
+        let mut spawn_points = BTreeMap::new();
+        let mut duplicated = false;
+        for (i, node) in scene.nodes.iter().enumerate() {
+            let name = match node.name.as_deref() {
+                Some(name) if names.contains(name) => name,
+                _ => continue,
+            };
+            let [x, y, z] = scene.world_position(i);
+            if spawn_points
+                .insert(name.to_owned(), SpawnPoint { x, y, z })
+                .is_some()
+            {
+                duplicated = true;
+            }
+        }
+
+        if duplicated || spawn_points.len() != names.len() {
+            eprintln!(
+                "Scene for map {} does not match its {} sets ({} markers found{}), skipped",
+                map_no,
+                names.len(),
+                spawn_points.len(),
+                if duplicated { ", some duplicated" } else { "" }
+            );
+            continue;
+        }
+
+        stages.insert(
+            map_no,
+            Stage {
+                map_no,
+                spawn_points,
+            },
+        );
+    }
+    Ok(stages)
+}
+
 fn get_user<T: 'static>(pak: &mut PakReader<impl Read + Seek>, path: &'static str) -> Result<T> {
     let index = pak.find_file(path)?;
     User::new(Cursor::new(pak.read_file(index)?))?
@@ -268,6 +362,21 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
     let random_scale = get_user(pak, "enemy/user_data/system_boss_random_scale_data.user")?;
     let size_list = get_user(pak, "enemy/user_data/system_enemy_sizelist_data.user")?;
     let discover_em_set_data = get_user(pak, "Quest/QuestData/DiscoverEmSetData.user")?;
+    let supply_data_path = "Quest/SupplyData/SupplyData.user";
+    let supply_data = optional(supply_data_path, get_user(pak, supply_data_path));
+    let reward_id_lot_table_path = "data/System/RewardSystem/LotTable/RewardIdLotTableData.user";
+    let reward_id_lot_table = optional(
+        reward_id_lot_table_path,
+        get_user(pak, reward_id_lot_table_path),
+    );
+    let quest_data_for_reward_path =
+        "data/Define/Quest/System/QuestRewardSystem/QuestDataForRewardData.user";
+    let quest_data_for_reward = optional(
+        quest_data_for_reward_path,
+        get_user(pak, quest_data_for_reward_path),
+    );
+    // Rampage facility (installation) data is not decoded, as its layout is not known yet
+    let hyakuryu_quest_data = get_user(pak, "Quest/QuestData/HyakuryuQuestData.user")?;
     let quest_hall_msg = get_msg(pak, "Message/Quest/QuestData_Hall.msg")?;
     let quest_village_msg = get_msg(pak, "Message/Quest/QuestData_Village.msg")?;
     let quest_tutorial_msg = get_msg(pak, "Message/Quest/QuestData_Tutorial.msg")?;
@@ -314,6 +423,14 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
         pak,
         "data/Define/Player/Skill/PlHyakuryuSkill/HyakuryuSkillRecipeData.user",
     )?;
+    let hyakuryu_skill_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Skill/PlHyakuryuSkill/HyakuryuSkill_Name.msg",
+    )?;
+    let hyakuryu_skill_explain_msg = get_msg(
+        pak,
+        "data/Define/Player/Skill/PlHyakuryuSkill/HyakuryuSkill_Explain.msg",
+    )?;
 
     let alchemy_pattern = get_user(
         pak,
@@ -352,6 +469,94 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
         "data/System/ContentsIdSystem/Common/ItemCategoryType_Name.msg",
     )?;
 
+    // Unverified path for the stage names
+    let stage_name_path = "Message/Common_Msg/Stage/StageName.msg";
+    let stage_name_msg = optional(stage_name_path, get_msg(pak, stage_name_path));
+    let stages = gen_stages(pak, &monsters).context("Generating stages")?;
+
+    let great_sword = get_user(
+        pak,
+        "data/Define/Player/Weapon/GreatSword/GreatSwordBaseData.user",
+    )?;
+    let great_sword_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/GreatSword/GreatSword_Name.msg",
+    )?;
+    let short_sword = get_user(
+        pak,
+        "data/Define/Player/Weapon/ShortSword/ShortSwordBaseData.user",
+    )?;
+    let short_sword_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/ShortSword/ShortSword_Name.msg",
+    )?;
+    let dual_blades = get_user(
+        pak,
+        "data/Define/Player/Weapon/DualBlades/DualBladesBaseData.user",
+    )?;
+    let dual_blades_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/DualBlades/DualBlades_Name.msg",
+    )?;
+    let long_sword = get_user(
+        pak,
+        "data/Define/Player/Weapon/LongSword/LongSwordBaseData.user",
+    )?;
+    let long_sword_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/LongSword/LongSword_Name.msg",
+    )?;
+    let hammer = get_user(pak, "data/Define/Player/Weapon/Hammer/HammerBaseData.user")?;
+    let hammer_name_msg = get_msg(pak, "data/Define/Player/Weapon/Hammer/Hammer_Name.msg")?;
+    let horn = get_user(pak, "data/Define/Player/Weapon/Horn/HornBaseData.user")?;
+    let horn_name_msg = get_msg(pak, "data/Define/Player/Weapon/Horn/Horn_Name.msg")?;
+    let lance = get_user(pak, "data/Define/Player/Weapon/Lance/LanceBaseData.user")?;
+    let lance_name_msg = get_msg(pak, "data/Define/Player/Weapon/Lance/Lance_Name.msg")?;
+    let gun_lance = get_user(
+        pak,
+        "data/Define/Player/Weapon/GunLance/GunLanceBaseData.user",
+    )?;
+    let gun_lance_name_msg = get_msg(pak, "data/Define/Player/Weapon/GunLance/GunLance_Name.msg")?;
+    let slash_axe = get_user(
+        pak,
+        "data/Define/Player/Weapon/SlashAxe/SlashAxeBaseData.user",
+    )?;
+    let slash_axe_name_msg = get_msg(pak, "data/Define/Player/Weapon/SlashAxe/SlashAxe_Name.msg")?;
+    let charge_axe = get_user(
+        pak,
+        "data/Define/Player/Weapon/ChargeAxe/ChargeAxeBaseData.user",
+    )?;
+    let charge_axe_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/ChargeAxe/ChargeAxe_Name.msg",
+    )?;
+    let insect_glaive = get_user(
+        pak,
+        "data/Define/Player/Weapon/InsectGlaive/InsectGlaiveBaseData.user",
+    )?;
+    let insect_glaive_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/InsectGlaive/InsectGlaive_Name.msg",
+    )?;
+    let bow = get_user(pak, "data/Define/Player/Weapon/Bow/BowBaseData.user")?;
+    let bow_name_msg = get_msg(pak, "data/Define/Player/Weapon/Bow/Bow_Name.msg")?;
+    let light_bowgun = get_user(
+        pak,
+        "data/Define/Player/Weapon/LightBowgun/LightBowgunBaseData.user",
+    )?;
+    let light_bowgun_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/LightBowgun/LightBowgun_Name.msg",
+    )?;
+    let heavy_bowgun = get_user(
+        pak,
+        "data/Define/Player/Weapon/HeavyBowgun/HeavyBowgunBaseData.user",
+    )?;
+    let heavy_bowgun_name_msg = get_msg(
+        pak,
+        "data/Define/Player/Weapon/HeavyBowgun/HeavyBowgun_Name.msg",
+    )?;
+
     Ok(Pedia {
         monsters,
         small_monsters,
@@ -368,6 +573,10 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
         random_scale,
         size_list,
         discover_em_set_data,
+        supply_data,
+        reward_id_lot_table,
+        quest_data_for_reward,
+        hyakuryu_quest_data,
         quest_hall_msg,
         quest_village_msg,
         quest_tutorial_msg,
@@ -389,6 +598,8 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
         player_skill_name_msg,
         hyakuryu_skill,
         hyakuryu_skill_recipe,
+        hyakuryu_skill_name_msg,
+        hyakuryu_skill_explain_msg,
         alchemy_pattern,
         alchemy_pl_skill,
         alchemy_grade_worth,
@@ -400,11 +611,41 @@ pub fn gen_pedia(pak: &mut PakReader<impl Read + Seek>) -> Result<Pedia> {
         items,
         items_name_msg,
         material_category_msg,
+        stage_name_msg,
+        stages,
+        great_sword,
+        great_sword_name_msg,
+        short_sword,
+        short_sword_name_msg,
+        dual_blades,
+        dual_blades_name_msg,
+        long_sword,
+        long_sword_name_msg,
+        hammer,
+        hammer_name_msg,
+        horn,
+        horn_name_msg,
+        lance,
+        lance_name_msg,
+        gun_lance,
+        gun_lance_name_msg,
+        slash_axe,
+        slash_axe_name_msg,
+        charge_axe,
+        charge_axe_name_msg,
+        insect_glaive,
+        insect_glaive_name_msg,
+        bow,
+        bow_name_msg,
+        light_bowgun,
+        light_bowgun_name_msg,
+        heavy_bowgun,
+        heavy_bowgun_name_msg,
     })
 }
 
 fn gen_monster_hitzones(
-    pak: &mut PakReader<impl Read + Seek>,
+    pak: &PakReader<impl ReadAt + Sync>,
     output: &Path,
     collider_path_gen: fn(u32, u32) -> String,
     mesh_path_gen: fn(u32, u32) -> String,
@@ -424,8 +665,6 @@ fn gen_monster_hitzones(
             let collider = pak
                 .find_file(&collider_path)
                 .context("Found mesh but not collider")?;
-            let mesh = pak.read_file(mesh)?;
-            let collider = pak.read_file(collider)?;
             monsters.push((index, sub_id, mesh, collider));
         }
     }
@@ -433,8 +672,8 @@ fn gen_monster_hitzones(
     monsters
         .into_par_iter()
         .map(|(index, sub_id, mesh, collider)| {
-            let mesh = Mesh::new(Cursor::new(mesh))?;
-            let mut collider = Rcol::new(Cursor::new(collider), true)?;
+            let mesh = Mesh::new(Cursor::new(pak.read_file_shared(mesh)?))?;
+            let mut collider = Rcol::new(Cursor::new(pak.read_file_shared(collider)?), true)?;
             let meat_path = output.join(meat_file_name_gen(index, sub_id));
             let parts_group_path = output.join(parts_group_file_name_gen(index, sub_id));
             collider.apply_skeleton(&mesh)?;
@@ -473,12 +712,17 @@ static EM_ICON_MAP: Lazy<HashMap<(i32, i32), &'static str>> = Lazy::new(|| {
     m
 });
 
-pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Result<()> {
+/// Resource files that pages only link to if they were generated
+pub struct Resources {
+    pub armor_previews: BTreeSet<String>,
+}
+
+pub fn gen_resources(pak: &PakReader<impl ReadAt + Sync>, output: &Path) -> Result<Resources> {
     let root = PathBuf::from(output);
     if root.exists() {
         remove_dir_all(&root)?;
     }
-    create_dir(&root)?;
+    create_dir_all(&root)?;
 
     gen_monster_hitzones(
         pak,
@@ -503,6 +747,7 @@ pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Re
         |id, sub_id| format!("ems{0:03}_{1:02}_parts_group.png", id, sub_id),
     )?;
 
+    let mut icons = vec![];
     for index in 0..1000 {
         for sub_id in 0..10 {
             let icon_path = if let Some(name) = EM_ICON_MAP.get(&(index, sub_id)) {
@@ -513,17 +758,9 @@ pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Re
                     index, sub_id
                 )
             };
-            let icon = if let Ok(icon) = pak.find_file(&icon_path) {
-                icon
-            } else {
-                continue;
-            };
-            let icon = Tex::new(Cursor::new(pak.read_file(icon)?))?;
-            icon.save_png(
-                0,
-                0,
-                &root.join(format!("em{0:03}_{1:02}_icon.png", index, sub_id)),
-            )?;
+            if let Ok(icon) = pak.find_file(&icon_path) {
+                icons.push((icon, format!("em{0:03}_{1:02}_icon.png", index, sub_id)));
+            }
         }
     }
 
@@ -533,22 +770,22 @@ pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Re
                 "gui/80_Texture/boss_icon/ems{:03}_{1:02}_IAM.tex",
                 index, sub_id
             );
-            let icon = if let Ok(icon) = pak.find_file(&icon_path) {
-                icon
-            } else {
-                continue;
-            };
-            let icon = Tex::new(Cursor::new(pak.read_file(icon)?))?;
-            icon.save_png(
-                0,
-                0,
-                &root.join(format!("ems{0:03}_{1:02}_icon.png", index, sub_id)),
-            )?;
+            if let Ok(icon) = pak.find_file(&icon_path) {
+                icons.push((icon, format!("ems{0:03}_{1:02}_icon.png", index, sub_id)));
+            }
         }
     }
 
+    icons
+        .into_par_iter()
+        .map(|(icon, file_name)| {
+            let icon = Tex::new(Cursor::new(pak.read_file_shared(icon)?))?;
+            icon.save_png(0, 0, &root.join(file_name))
+        })
+        .collect::<Result<Vec<()>>>()?;
+
     let guild_card = pak.find_file("gui/80_Texture/GuildCard_IAM.tex")?;
-    let guild_card = Tex::new(Cursor::new(pak.read_file(guild_card)?))?.to_rgba(0, 0)?;
+    let guild_card = Tex::new(Cursor::new(pak.read_file_shared(guild_card)?))?.to_rgba(0, 0)?;
 
     guild_card
         .sub_image(302, 397, 24, 24)?
@@ -561,40 +798,44 @@ pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Re
     let item_icon_path = root.join("item");
     create_dir(&item_icon_path)?;
     let item_icon_uvs = pak.find_file("gui/70_UVSequence/cmn_icon.uvs")?;
-    let item_icon_uvs = Uvs::new(Cursor::new(pak.read_file(item_icon_uvs)?))?;
+    let item_icon_uvs = Uvs::new(Cursor::new(pak.read_file_shared(item_icon_uvs)?))?;
     if item_icon_uvs.textures.len() != 1 || item_icon_uvs.spriter_groups.len() != 1 {
         bail!("Broken cmn_icon.uvs");
     }
     let item_icon = pak.find_file(&item_icon_uvs.textures[0].path)?;
-    let item_icon = Tex::new(Cursor::new(pak.read_file(item_icon)?))?.to_rgba(0, 0)?;
+    let item_icon = Tex::new(Cursor::new(pak.read_file_shared(item_icon)?))?.to_rgba(0, 0)?;
+    let mut item_icon_masks = vec![];
     for (i, spriter) in item_icon_uvs.spriter_groups[0].spriters.iter().enumerate() {
         let (item_icon_r, item_icon_a) = item_icon
             .sub_image_f(spriter.p0, spriter.p1)?
             .gen_double_mask();
         item_icon_r.save_png(&item_icon_path.join(format!("{:03}.r.png", i)))?;
         item_icon_a.save_png(&item_icon_path.join(format!("{:03}.a.png", i)))?;
+        item_icon_masks.push((item_icon_r, item_icon_a));
     }
 
     let item_addon_uvs = pak.find_file("gui/70_UVSequence/Item_addonicon.uvs")?;
-    let item_addon_uvs = Uvs::new(Cursor::new(pak.read_file(item_addon_uvs)?))?;
+    let item_addon_uvs = Uvs::new(Cursor::new(pak.read_file_shared(item_addon_uvs)?))?;
     if item_addon_uvs.textures.len() != 1 || item_addon_uvs.spriter_groups.len() != 1 {
         bail!("Broken item_addonicon_IAM.uvs");
     }
     let item_addon = pak.find_file(&item_addon_uvs.textures[0].path)?;
-    let item_addon = Tex::new(Cursor::new(pak.read_file(item_addon)?))?.to_rgba(0, 0)?;
+    let item_addon = Tex::new(Cursor::new(pak.read_file_shared(item_addon)?))?.to_rgba(0, 0)?;
+    let mut item_addons = vec![];
     for (i, spriter) in item_addon_uvs.spriter_groups[0].spriters.iter().enumerate() {
-        item_addon
-            .sub_image_f(spriter.p0, spriter.p1)?
-            .save_png(&root.join(format!("item_addon_{}.png", i)))?;
+        let addon = item_addon.sub_image_f(spriter.p0, spriter.p1)?;
+        addon.save_png(&root.join(format!("item_addon_{}.png", i)))?;
+        item_addons.push(addon);
     }
 
     let message_window_uvs = pak.find_file("gui/70_UVSequence/message_window.uvs")?;
-    let message_window_uvs = Uvs::new(Cursor::new(pak.read_file(message_window_uvs)?))?;
+    let message_window_uvs = Uvs::new(Cursor::new(pak.read_file_shared(message_window_uvs)?))?;
     if message_window_uvs.textures.len() != 1 || message_window_uvs.spriter_groups.len() != 1 {
         bail!("Broken message_window.uvs");
     }
     let message_window = pak.find_file(&message_window_uvs.textures[0].path)?;
-    let message_window = Tex::new(Cursor::new(pak.read_file(message_window)?))?.to_rgba(0, 0)?;
+    let message_window =
+        Tex::new(Cursor::new(pak.read_file_shared(message_window)?))?.to_rgba(0, 0)?;
     let skill_icon = message_window_uvs.spriter_groups[0]
         .spriters
         .get(170)
@@ -605,16 +846,211 @@ pub fn gen_resources(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Re
     skill_r.save_png(&root.join("skill.r.png"))?;
     skill_a.save_png(&root.join("skill.a.png"))?;
 
+    let item_colors = get_item_colors(pak)?;
     let item_colors_path = root.join("item_color.css");
-    gen_item_colors(pak, &item_colors_path)?;
+    gen_item_colors(&item_colors, &item_colors_path)?;
 
-    Ok(())
+    gen_item_icons(
+        pak,
+        &root.join("item_icon"),
+        &item_icon_masks,
+        &item_addons,
+        &item_colors,
+    )?;
+
+    let armor_previews = gen_armor_previews(pak, &root.join("armor"))?;
+
+    Ok(Resources { armor_previews })
 }
 
-fn gen_item_colors(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Result<()> {
-    let mut file = File::create(output)?;
+fn get_user_shared<T: 'static>(
+    pak: &PakReader<impl ReadAt + Sync>,
+    path: &'static str,
+) -> Result<T> {
+    let index = pak.find_file(path)?;
+    User::new(Cursor::new(pak.read_file_shared(index)?))?
+        .rsz
+        .deserialize_single()
+        .context(path)
+}
+
+fn armor_part_name(id: PlArmorId) -> Option<&'static str> {
+    match id {
+        PlArmorId::Head(_) => Some("helm"),
+        PlArmorId::Chest(_) => Some("body"),
+        PlArmorId::Arm(_) => Some("arm"),
+        PlArmorId::Waist(_) => Some("wst"),
+        PlArmorId::Leg(_) => Some("leg"),
+        _ => None,
+    }
+}
+
+pub fn armor_sexes(flag: &SexualEquipableFlag) -> &'static [bool] {
+    match flag {
+        SexualEquipableFlag::MaleOnly => &[false],
+        SexualEquipableFlag::FemaleOnly => &[true],
+        SexualEquipableFlag::Both => &[false, true],
+    }
+}
+
+fn sex_name(female: bool) -> &'static str {
+    if female {
+        "f"
+    } else {
+        "m"
+    }
+}
+
+fn armor_mesh_path(id: PlArmorId, model_id: u32, female: bool) -> Option<String> {
+    Some(format!(
+        "player/mod/{0}/pl{2:03}/{0}_{1}{2:03}.mesh",
+        sex_name(female),
+        armor_part_name(id)?,
+        model_id
+    ))
+}
+
+/// File name of the preview under resources/armor, e.g. helm001_f.png
+pub fn armor_preview_file_name(id: PlArmorId, model_id: u32, female: bool) -> Option<String> {
+    Some(format!(
+        "{}{:03}_{}.png",
+        armor_part_name(id)?,
+        model_id,
+        sex_name(female)
+    ))
+}
+
+fn load_albedo(pak: &PakReader<impl ReadAt + Sync>, path: &str) -> Result<RgbaImage> {
+    let tex = Tex::new(Cursor::new(pak.read_file_shared(pak.find_file(path)?)?))?;
+    tex.to_rgba(0, tex.mipmap_for_size(512))
+}
+
+fn load_preview_mesh(
+    pak: &PakReader<impl ReadAt + Sync>,
+    mesh_path: &str,
+    mesh: PakFileIndex,
+) -> Result<PreviewMesh> {
+    let mesh = Mesh::new(Cursor::new(pak.read_file_shared(mesh)?))?;
+    let mdf_path = format!("{}.mdf2", mesh_path.trim_end_matches(".mesh"));
+    let mdf = if let Ok(mdf) = pak.find_file(&mdf_path) {
+        Some(Mdf2::new(Cursor::new(pak.read_file_shared(mdf)?))?)
+    } else {
+        None
+    };
+
+    let lod = mesh.main_model_lods.first().context("No LOD")?;
+    let mut material_indexs: BTreeMap<Option<&str>, Vec<u32>> = BTreeMap::new();
+    for model in lod.model_groups.iter().flat_map(|group| &group.models) {
+        material_indexs
+            .entry(mesh.material_name(model))
+            .or_default()
+            .extend(mesh.indices(model)?);
+    }
+
+    let parts = material_indexs
+        .into_iter()
+        .map(|(material, indexs)| {
+            let albedo = material
+                .and_then(|material| mdf.as_ref()?.material(material)?.albedo())
+                .and_then(|path| match load_albedo(pak, path) {
+                    Ok(albedo) => Some(albedo),
+                    Err(e) => {
+                        eprintln!("Failed to load albedo {} for {}: {:#}", path, mesh_path, e);
+                        None
+                    }
+                });
+            PreviewPart { indexs, albedo }
+        })
+        .collect();
+
+    let vertices = mesh.vertices()?;
+    Ok(PreviewMesh {
+        positions: vertices.positions,
+        uvs: vertices.uvs,
+        parts,
+    })
+}
+
+/// Renders a preview for every armor model found, returning the file names written
+fn gen_armor_previews(
+    pak: &PakReader<impl ReadAt + Sync>,
+    output: &Path,
+) -> Result<BTreeSet<String>> {
+    create_dir(output)?;
+
+    let armor: ArmorBaseUserData =
+        get_user_shared(pak, "data/Define/Player/Armor/ArmorBaseData.user")?;
+
+    let overwear: PlOverwearBaseUserData =
+        get_user_shared(pak, "data/Define/Player/Armor/PlOverwearBaseData.user")?;
+
+    let models = armor
+        .param
+        .iter()
+        .filter(|param| param.is_valid)
+        .map(|param| (param.pl_armor_id, param.model_id, &param.sexual_equipable))
+        .chain(
+            overwear
+                .param
+                .iter()
+                .filter(|param| param.is_valid)
+                .map(|param| (param.relative_id, param.model_id, &param.sexual_equipable)),
+        );
+
+    let mut jobs = BTreeMap::new();
+    for (id, model_id, sexual_equipable) in models {
+        for &female in armor_sexes(sexual_equipable) {
+            if let (Some(mesh_path), Some(file_name)) = (
+                armor_mesh_path(id, model_id, female),
+                armor_preview_file_name(id, model_id, female),
+            ) {
+                jobs.insert(file_name, mesh_path);
+            }
+        }
+    }
+
+    // The mesh path is derived from the model ID, so only meshes found in the pak are used
+    let mut found = vec![];
+    let mut missing = vec![];
+    for (file_name, mesh_path) in jobs {
+        match pak.find_file(&mesh_path) {
+            Ok(mesh) => found.push((file_name, mesh_path, mesh)),
+            Err(_) => missing.push(mesh_path),
+        }
+    }
+    if let Some(example) = missing.first() {
+        eprintln!(
+            "{} of {} armor meshes not found, e.g. {}",
+            missing.len(),
+            missing.len() + found.len(),
+            example
+        );
+    }
+
+    let written = found
+        .into_par_iter()
+        .map(|(file_name, mesh_path, mesh)| {
+            let preview = load_preview_mesh(pak, &mesh_path, mesh)
+                .and_then(|preview| gen_model_preview(preview, 512));
+            match preview {
+                Ok(preview) => {
+                    preview.save_png(&output.join(&file_name))?;
+                    Ok(Some(file_name))
+                }
+                Err(e) => {
+                    eprintln!("Failed to generate armor preview {}: {:#}", mesh_path, e);
+                    Ok(None)
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(written.into_iter().flatten().collect())
+}
+
+fn get_item_colors(pak: &PakReader<impl ReadAt + Sync>) -> Result<BTreeMap<i32, [u8; 3]>> {
     let item_icon_gui = pak.find_file("gui/01_Common/ItemIcon.gui")?;
-    let item_icon_gui = Gui::new(Cursor::new(pak.read_file(item_icon_gui)?))?;
+    let item_icon_gui = Gui::new(Cursor::new(pak.read_file_shared(item_icon_gui)?))?;
     let item_icon_color = item_icon_gui
         .controls
         .iter()
@@ -629,25 +1065,116 @@ fn gen_item_colors(pak: &mut PakReader<impl Read + Seek>, output: &Path) -> Resu
         Ok((value * 255.0).round() as u8)
     }
 
+    let mut colors = BTreeMap::new();
     for clips in &item_icon_color.clips {
         const NAME_PREFIX: &str = "ITEM_ICON_COLOR_";
         if !clips.name.starts_with(NAME_PREFIX) {
             bail!("Unexpected prefix");
         }
-        let id: u32 = clips.name[NAME_PREFIX.len()..].parse()?;
+        let id: i32 = clips.name[NAME_PREFIX.len()..].parse()?;
         if clips.variable_values.len() != 3 {
             bail!("Unexpected variable values len");
         }
         let r = color_tran(clips.variable_values[0].value)?;
         let g = color_tran(clips.variable_values[1].value)?;
         let b = color_tran(clips.variable_values[2].value)?;
+        colors.insert(id, [r, g, b]);
+    }
 
+    Ok(colors)
+}
+
+fn gen_item_colors(colors: &BTreeMap<i32, [u8; 3]>, output: &Path) -> Result<()> {
+    let mut file = File::create(output)?;
+    for (id, [r, g, b]) in colors {
         writeln!(
             file,
             ".mh-item-color-{} {{background-color: #{:02X}{:02X}{:02X}}}",
             id, r, g, b
         )?;
     }
+    Ok(())
+}
+
+/// File name of the rendered icon under resources/item_icon, e.g. 012_3_great.png
+pub fn item_icon_file_name(param: &ItemUserDataParam) -> String {
+    let rank = match param.icon_item_rank {
+        IconRank::Great => "_great",
+        IconRank::Lv1 => "_lv1",
+        IconRank::Lv2 => "_lv2",
+        IconRank::Lv3 => "_lv3",
+        IconRank::None => "",
+    };
+    let supply = if param.supply { "_supply" } else { "" };
+    format!(
+        "{:03}_{}{}{}.png",
+        param.icon_chara, param.icon_color, rank, supply
+    )
+}
+
+// Addon sprite index and its position relative to the icon size, matching mhrice.css
+fn item_icon_addons(param: &ItemUserDataParam) -> Vec<(usize, f32, f32)> {
+    let mut addons = vec![];
+    match param.icon_item_rank {
+        IconRank::Great => addons.push((1, -0.175, -0.175)),
+        IconRank::Lv1 => addons.push((3, -0.125, -0.1)),
+        IconRank::Lv2 => addons.push((4, -0.125, -0.1)),
+        IconRank::Lv3 => addons.push((5, -0.125, -0.1)),
+        IconRank::None => (),
+    }
+    if param.supply {
+        addons.push((2, 0.65, -0.1));
+    }
+    addons
+}
+
+fn gen_item_icons(
+    pak: &PakReader<impl ReadAt + Sync>,
+    output: &Path,
+    masks: &[(RgbaImage, RgbaImage)],
+    addons: &[RgbaImage],
+    colors: &BTreeMap<i32, [u8; 3]>,
+) -> Result<()> {
+    create_dir(output)?;
+    let items: ItemUserData = get_user_shared(
+        pak,
+        "data/System/ContentsIdSystem/Item/Normal/ItemData.user",
+    )?;
+
+    let mut jobs = BTreeMap::new();
+    for param in &items.param {
+        jobs.insert(item_icon_file_name(param), param);
+    }
+
+    jobs.into_par_iter()
+        .map(|(file_name, param)| {
+            let (mask_r, mask_a) = masks
+                .get(usize::try_from(param.icon_chara)?)
+                .with_context(|| format!("Icon {} not found", param.icon_chara))?;
+            let &color = colors
+                .get(&param.icon_color)
+                .with_context(|| format!("Color {} not found", param.icon_color))?;
+
+            // Leave room for the addons that stick out of the icon
+            let size = mask_r.width();
+            let pad = size / 5;
+            let mut icon = RgbaImage::empty(size + pad * 2, mask_r.height() + pad * 2)?;
+            icon.blend(&mask_a.fill([0, 0, 0]), pad as i32, pad as i32)?;
+            icon.blend(&mask_r.fill(color), pad as i32, pad as i32)?;
+            for (index, x, y) in item_icon_addons(param) {
+                let addon = addons
+                    .get(index)
+                    .with_context(|| format!("Addon {} not found", index))?
+                    .resize(size / 2, size / 2)?;
+                icon.blend(
+                    &addon,
+                    pad as i32 + (x * size as f32).round() as i32,
+                    pad as i32 + (y * size as f32).round() as i32,
+                )?;
+            }
+            icon.save_png(&output.join(file_name))
+        })
+        .collect::<Result<Vec<()>>>()?;
 
     Ok(())
 }
@@ -687,6 +1214,127 @@ fn prepare_size_dist_map(
     Ok(result)
 }
 
+fn resolve_quest_monster_stats(
+    pedia: &Pedia,
+    param: &NormalQuestDataParam,
+    enemy_param: Option<&NormalQuestDataForEnemyParam>,
+) -> Vec<QuestMonsterStats> {
+    let enemy_param = if let Some(enemy_param) = enemy_param {
+        &enemy_param.param
+    } else {
+        return vec![];
+    };
+    let rates = &pedia.difficulty_rate;
+
+    param
+        .boss_em_type
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, em_type)| em_type != EmTypes::Em(0))
+        .filter_map(|(index, em_type)| {
+            let id = match em_type {
+                EmTypes::Em(id) => id,
+                EmTypes::Ems(_) => return None,
+            };
+            let monster = pedia
+                .monsters
+                .iter()
+                .find(|m| (m.id | m.sub_id << 8) == id)?;
+            let table = |tables: &Vec<u8>| tables.get(index).map(|&t| usize::from(t));
+            let vital = rates
+                .vital_rate_table_list
+                .get(table(&enemy_param.vital_tbl)?)?;
+            let attack = rates
+                .attack_rate_table_list
+                .get(table(&enemy_param.attack_tbl)?)?;
+            let parts = rates
+                .parts_rate_table_list
+                .get(table(&enemy_param.parts_tbl)?)?;
+            let other = rates
+                .other_rate_table_list
+                .get(table(&enemy_param.other_tbl)?)?;
+
+            let solo = SoloStats {
+                hp: monster.data_tune.base_hp_vital as f32 * vital.vital_rate,
+                attack_rate: attack.attack_rate,
+                parts_vital: monster
+                    .data_tune
+                    .enemy_parts_data
+                    .iter()
+                    .map(|part| part.vital as f32 * parts.parts_vital_rate)
+                    .collect(),
+                defense_rate: other.defense_rate,
+                element_a_rate: other.damage_element_rate_a,
+                element_b_rate: other.damage_element_rate_b,
+                stun_rate: other.stun_rate,
+                exhaust_rate: other.tired_rate,
+                ride_rate: other.marionette_rate,
+            };
+
+            let condition = &monster.condition_damage_data;
+            let preset = &pedia.condition_preset;
+            let buildup = |status, base: &ConditionDamageDataBase, quest_rate| StatusBuildup {
+                status,
+                default_limit: base.default_stock.default_limit,
+                add_limit: base.default_stock.add_limit,
+                max_limit: base.default_stock.max_limit,
+                quest_rate,
+            };
+            let buildups = vec![
+                condition
+                    .poison_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Poison", &data.base, None)),
+                condition
+                    .paralyze_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Paralyze", &data.base, None)),
+                condition
+                    .sleep_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Sleep", &data.base, None)),
+                condition
+                    .blast_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Blast", &data.base, None)),
+                condition
+                    .stun_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Stun", &data.base, Some(other.stun_rate))),
+                condition
+                    .stamina_data
+                    .or_preset(preset)
+                    .ok()
+                    .map(|data| buildup("Exhaust", &data.base, Some(other.tired_rate))),
+                Some(buildup(
+                    "Ride",
+                    &condition.marionette_data.base,
+                    Some(other.marionette_rate),
+                )),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+
+            Some(QuestMonsterStats {
+                index,
+                em_type,
+                base_hp: monster.data_tune.base_hp_vital,
+                vital_rate: vital.vital_rate,
+                parts_vital_rate: parts.parts_vital_rate,
+                buildups,
+                solo,
+            })
+        })
+        .collect()
+}
+
 fn prepare_quests(pedia: &Pedia) -> Result<Vec<Quest>> {
     let mut all_msg: HashMap<String, MsgEntry> = pedia
         .quest_hall_msg
@@ -723,6 +1371,47 @@ fn prepare_quests(pedia: &Pedia) -> Result<Vec<Quest>> {
         .map(|param| (param.quest_no, param.clone()))
         .collect();
 
+    let supplies: HashMap<u32, &SupplyDataParam> = pedia
+        .supply_data
+        .iter()
+        .flat_map(|table| &table.param)
+        .map(|param| (param.id, param))
+        .collect();
+
+    let reward_lots: HashMap<u32, &RewardIdLotTableUserDataParam> = pedia
+        .reward_id_lot_table
+        .iter()
+        .flat_map(|table| &table.param)
+        .map(|param| (param.id, param))
+        .collect();
+    let reward_lot = |id: u32| reward_lots.get(&id).map(|&lot| lot.clone());
+
+    let mut rewards: HashMap<i32, QuestReward> = pedia
+        .quest_data_for_reward
+        .iter()
+        .flat_map(|table| &table.param)
+        .map(|param| {
+            let reward = QuestReward {
+                param: param.clone(),
+                additional_target_reward: reward_lot(param.additional_target_reward_table_index),
+                common_material_reward: reward_lot(param.common_material_reward_table_index),
+                additional_quest_reward: param
+                    .additional_quest_reward_table_index
+                    .iter()
+                    .filter_map(|&id| reward_lot(id))
+                    .collect(),
+            };
+            (param.quest_numer, reward)
+        })
+        .collect();
+
+    let mut hyakuryus: HashMap<i32, HyakuryuQuestData> = pedia
+        .hyakuryu_quest_data
+        .data_list
+        .iter()
+        .map(|data| (data.quest_no, data.clone()))
+        .collect();
+
     pedia
         .normal_quest_data
         .param
@@ -732,17 +1421,79 @@ fn prepare_quests(pedia: &Pedia) -> Result<Vec<Quest>> {
             let name_msg_name = format!("QN{:06}_01", param.quest_no);
             let target_msg_name = format!("QN{:06}_04", param.quest_no);
             let condition_msg_name = format!("QN{:06}_05", param.quest_no);
+            let enemy_param = enemy_params.remove(&param.quest_no);
+            let monster_stats = resolve_quest_monster_stats(pedia, param, enemy_param.as_ref());
             Ok(Quest {
                 param: param.clone(),
-                enemy_param: enemy_params.remove(&param.quest_no),
+                enemy_param,
                 name: all_msg.remove(&name_msg_name),
                 target: all_msg.remove(&target_msg_name),
                 condition: all_msg.remove(&condition_msg_name),
+                monster_stats,
+                supply: supplies
+                    .get(&param.supply_tbl)
+                    .map(|&supply| supply.clone()),
+                reward: rewards.remove(&param.quest_no),
+                hyakuryu: hyakuryus.remove(&param.quest_no),
             })
         })
         .collect::<Result<Vec<_>>>()
 }
 
+fn prepare_hyakuryu_skills<'a>(
+    pedia: &'a Pedia,
+    weapon_types: &[(&'static str, Vec<Weapon<'a>>)],
+) -> Result<BTreeMap<u32, HyakuryuSkill<'a>>> {
+    let mut name_msg: HashMap<String, MsgEntry> = pedia
+        .hyakuryu_skill_name_msg
+        .entries
+        .iter()
+        .map(|entry| (entry.name.clone(), entry.clone()))
+        .collect();
+
+    let mut explain_msg: HashMap<String, MsgEntry> = pedia
+        .hyakuryu_skill_explain_msg
+        .entries
+        .iter()
+        .map(|entry| (entry.name.clone(), entry.clone()))
+        .collect();
+
+    let mut recipes = HashMap::new();
+    for recipe in &pedia.hyakuryu_skill_recipe.param {
+        if recipes.insert(recipe.skill_id, recipe).is_some() {
+            bail!("Multiple recipe for rampage skill {}", recipe.skill_id);
+        }
+    }
+
+    let mut weapons: HashMap<u32, Vec<Weapon>> = HashMap::new();
+    for weapon in weapon_types.iter().flat_map(|(_, weapons)| weapons) {
+        for &skill_id in &weapon.main.hyakuryu_skill_id_list {
+            weapons.entry(skill_id).or_default().push(weapon.clone());
+        }
+    }
+
+    let mut result = BTreeMap::new();
+    for skill in &pedia.hyakuryu_skill.param {
+        if skill.id == 0 {
+            continue;
+        }
+        // ID 1 is ID_0 in the game, and the text follows the latter
+        let msg_id = skill.id - 1;
+        let hyakuryu_skill = HyakuryuSkill {
+            name: name_msg.remove(&format!("HyakuryuSkill_{:03}_Name", msg_id)),
+            explain: explain_msg.remove(&format!("HyakuryuSkill_{:03}_Explain", msg_id)),
+            data: skill,
+            recipe: recipes.remove(&skill.id),
+            weapons: weapons.remove(&skill.id).unwrap_or_default(),
+        };
+        if result.insert(skill.id, hyakuryu_skill).is_some() {
+            bail!("Multiple definition for rampage skill {}", skill.id);
+        }
+    }
+
+    Ok(result)
+}
+
 fn prepare_discoveries(pedia: &Pedia) -> Result<HashMap<EmTypes, &DiscoverEmSetDataParam>> {
     let mut result = HashMap::new();
     for discovery in &pedia.discover_em_set_data.param {
@@ -767,6 +1518,84 @@ fn prepare_discoveries(pedia: &Pedia) -> Result<HashMap<EmTypes, &DiscoverEmSetD
     Ok(result)
 }
 
+fn boss_init_stages(pedia: &Pedia, em_type: EmTypes) -> Option<&[StageInfo]> {
+    let id = match em_type {
+        EmTypes::Em(id) => id,
+        EmTypes::Ems(_) => return None,
+    };
+    pedia
+        .monsters
+        .iter()
+        .find(|m| (m.id | m.sub_id << 8) == id)
+        .and_then(|m| m.boss_init_set_data.as_ref())
+        .map(|data| &data.stage_info_list[..])
+}
+
+/// Maps each habitat bit to the one stage every monster of that habitat has a boss init
+/// set on. Habitats matching no single stage are left out.
+fn prepare_habitat_maps(pedia: &Pedia) -> BTreeMap<usize, i32> {
+    let mut candidates: BTreeMap<usize, BTreeSet<i32>> = BTreeMap::new();
+    for boss in &pedia.monster_list.data_list {
+        let stages: BTreeSet<i32> = match boss_init_stages(pedia, boss.em_type) {
+            Some(stages) => stages.iter().map(|s| s.map_type).collect(),
+            None => continue,
+        };
+        for habitat in boss.habitat_area.habitats() {
+            let maps = candidates.entry(habitat).or_insert_with(|| stages.clone());
+            *maps = maps.intersection(&stages).cloned().collect();
+        }
+    }
+    candidates
+        .into_iter()
+        .filter_map(|(habitat, maps)| {
+            let mut maps = maps.into_iter();
+            match (maps.next(), maps.next()) {
+                (Some(map_no), None) => Some((habitat, map_no)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Tour maps of each discovery, keeping a map flag only when its init set name is found
+/// on that map in the monster's boss init set data
+fn prepare_tour_maps(
+    pedia: &Pedia,
+    discoveries: &HashMap<EmTypes, &DiscoverEmSetDataParam>,
+) -> HashMap<EmTypes, Vec<i32>> {
+    let mut unconfirmed = 0;
+    let result = discoveries
+        .iter()
+        .map(|(&em_type, discovery)| {
+            let stages = boss_init_stages(pedia, em_type).unwrap_or_default();
+            let maps = discovery
+                .map_flag
+                .iter()
+                .enumerate()
+                .filter(|(_, flag)| **flag)
+                .filter_map(|(i, _)| {
+                    // Flag i is assumed to be map i + 1, and checked against the set name
+                    let map_no = i32::try_from(i + 1).ok()?;
+                    let set_name = discovery.param.init_set_name.get(i)?;
+                    let found = stages.iter().any(|stage| {
+                        stage.map_type == map_no
+                            && stage.set_info_list.iter().any(|s| &s.set_name == set_name)
+                    });
+                    if !found {
+                        unconfirmed += 1;
+                    }
+                    found.then(|| map_no)
+                })
+                .collect();
+            (em_type, maps)
+        })
+        .collect();
+    if unconfirmed != 0 {
+        eprintln!("{} tour map flags not matching any init set", unconfirmed);
+    }
+    result
+}
+
 fn prepare_skills(pedia: &Pedia) -> Result<BTreeMap<PlEquipSkillId, Skill>> {
     let mut result = BTreeMap::new();
 
@@ -1193,18 +2022,113 @@ fn prepare_parts_dictionary(
     Ok(result)
 }
 
+// Names are looked up by index, as for armors. Unverified for weapons
+fn weapon_list<'a, T: WeaponParam>(
+    weapon_type: &'static str,
+    params: &'a [T],
+    name_msg: &'a Msg,
+) -> (&'static str, Vec<Weapon<'a>>) {
+    let weapons = params
+        .iter()
+        .map(|param| Weapon {
+            weapon_type,
+            name: name_msg
+                .entries
+                .get((param.main().base.id & 0xFFFF) as usize),
+            main: param.main(),
+            close_range: param.close_range(),
+            elements: param.elements(),
+        })
+        .collect();
+    (weapon_type, weapons)
+}
+
+fn prepare_weapons(pedia: &Pedia) -> Vec<(&'static str, Vec<Weapon<'_>>)> {
+    let weapons = vec![
+        weapon_list(
+            "Great sword",
+            &pedia.great_sword.param,
+            &pedia.great_sword_name_msg,
+        ),
+        weapon_list(
+            "Sword & shield",
+            &pedia.short_sword.param,
+            &pedia.short_sword_name_msg,
+        ),
+        weapon_list(
+            "Dual blades",
+            &pedia.dual_blades.param,
+            &pedia.dual_blades_name_msg,
+        ),
+        weapon_list(
+            "Long sword",
+            &pedia.long_sword.param,
+            &pedia.long_sword_name_msg,
+        ),
+        weapon_list("Hammer", &pedia.hammer.param, &pedia.hammer_name_msg),
+        weapon_list("Hunting horn", &pedia.horn.param, &pedia.horn_name_msg),
+        weapon_list("Lance", &pedia.lance.param, &pedia.lance_name_msg),
+        weapon_list(
+            "Gunlance",
+            &pedia.gun_lance.param,
+            &pedia.gun_lance_name_msg,
+        ),
+        weapon_list(
+            "Switch axe",
+            &pedia.slash_axe.param,
+            &pedia.slash_axe_name_msg,
+        ),
+        weapon_list(
+            "Charge blade",
+            &pedia.charge_axe.param,
+            &pedia.charge_axe_name_msg,
+        ),
+        weapon_list(
+            "Insect glaive",
+            &pedia.insect_glaive.param,
+            &pedia.insect_glaive_name_msg,
+        ),
+        weapon_list("Bow", &pedia.bow.param, &pedia.bow_name_msg),
+        weapon_list(
+            "Light bowgun",
+            &pedia.light_bowgun.param,
+            &pedia.light_bowgun_name_msg,
+        ),
+        weapon_list(
+            "Heavy bowgun",
+            &pedia.heavy_bowgun.param,
+            &pedia.heavy_bowgun_name_msg,
+        ),
+    ];
+    let unnamed = weapons
+        .iter()
+        .flat_map(|(_, weapons)| weapons)
+        .filter(|weapon| weapon.name.is_none())
+        .count();
+    if unnamed != 0 {
+        eprintln!("{} weapons without a name", unnamed);
+    }
+    weapons
+}
+
 pub fn gen_pedia_ex(pedia: &Pedia) -> Result<PediaEx<'_>> {
+    let weapons = prepare_weapons(pedia);
+    let discoveries = prepare_discoveries(pedia)?;
     Ok(PediaEx {
         sizes: prepare_size_map(&pedia.size_list)?,
         size_dists: prepare_size_dist_map(&pedia.random_scale)?,
         quests: prepare_quests(pedia)?,
-        discoveries: prepare_discoveries(pedia)?,
+        tour_maps: prepare_tour_maps(pedia, &discoveries),
+        discoveries,
+        habitat_maps: prepare_habitat_maps(pedia),
         skills: prepare_skills(pedia)?,
+        hyakuryu_skills: prepare_hyakuryu_skills(pedia, &weapons)?,
         armors: prepare_armors(pedia)?,
         meat_names: prepare_meat_names(pedia)?,
         items: prepare_items(pedia)?,
         material_categories: prepare_material_categories(pedia),
         monster_lot: prepare_monster_lot(pedia)?,
         parts_dictionary: prepare_parts_dictionary(pedia)?,
+        weapons,
     })
 }
@@ -1,13 +1,26 @@
+use super::crown::*;
+use super::gen_crown::*;
+use super::gen_item::*;
+use super::gen_map::*;
+use super::gen_monster::gen_reward_table;
 use super::gen_website::*;
+use super::manifest::*;
 use super::pedia::*;
 use crate::rsz::*;
 use anyhow::*;
 use std::collections::BTreeMap;
-use std::fs::{create_dir, write};
+use std::convert::TryFrom;
+use std::fs::create_dir_all;
 use std::path::*;
 use typed_html::{dom::*, elements::*, html, text};
 
-pub fn gen_quest_list(quests: &[Quest], root: &Path) -> Result<()> {
+pub fn gen_quest_list(
+    lang: Option<usize>,
+    quests: &[Quest],
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("quest.html"))?;
     let mut quests_ordered: BTreeMap<_, BTreeMap<_, Vec<&Quest>>> = BTreeMap::new();
     for quest in quests {
         quests_ordered
@@ -19,31 +32,31 @@ pub fn gen_quest_list(quests: &[Quest], root: &Path) -> Result<()> {
     }
 
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Quests - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Quests"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"Quests"</h1>
+                <h1 class="title">{ui(lang, "Quests")}</h1>
                 {
                     quests_ordered.into_iter().map(|(enemy_level, quests)|{
                         html!(<section>
-                         <h2 class="title">{text!("{:?}", enemy_level)}</h2>
+                         <h2 class="title">{ui(lang, enemy_level_text(enemy_level))}</h2>
                          <ul class="mh-list-quest">{
                             quests.into_iter().map(|(quest_level, quests)|{
                                 html!(
                                     <li class="mh-list-quest">
-                                        <h3 class="title">{text!("{:?}", quest_level)}</h3>
+                                        <h3 class="title">{text!("{}", quest_level_text(quest_level))}</h3>
                                         <ul>{
                                             quests.into_iter().map(|quest|{
                                                 html!{<li>
                                                     <a href={format!("/quest/{:06}.html", quest.param.quest_no)}>
                                                     {quest.name.as_ref().map_or(
-                                                        html!(<span>{text!("Quest {:06}", quest.param.quest_no)}</span>),
-                                                        gen_multi_lang
+                                                        ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                                                        |msg| gen_multi_lang(lang, msg)
                                                     )}
                                                     </a>
                                                 </li>}
@@ -61,12 +74,13 @@ pub fn gen_quest_list(quests: &[Quest], root: &Path) -> Result<()> {
     );
 
     let quests_path = root.join("quest.html");
-    write(&quests_path, doc.to_string())?;
+    manifest.write(&quests_path, doc.to_string())?;
 
     Ok(())
 }
 
 pub fn gen_quest_monster_data(
+    lang: Option<usize>,
     enemy_param: Option<&SharedEnemyParam>,
     em_type: EmTypes,
     index: usize,
@@ -76,47 +90,11 @@ pub fn gen_quest_monster_data(
     let enemy_param = if let Some(enemy_param) = enemy_param.as_ref() {
         enemy_param
     } else {
-        return vec![html!(<td colspan=11>"[NO DATA]"</td>)];
+        return vec![html!(<td colspan=11>{ui(lang, "[NO DATA]")}</td>)];
     };
 
-    let size = if let (Some(scale_tbl_i), Some(base_scale)) = (
-        enemy_param.scale_tbl.get(index),
-        enemy_param.scale.get(index),
-    ) {
-        if let (Some(size), Some(size_dist)) = (
-            pedia_ex.sizes.get(&em_type),
-            pedia_ex.size_dists.get(scale_tbl_i),
-        ) {
-            let mut small_chance = 0;
-            let mut large_chance = 0;
-            for sample in *size_dist {
-                let scale = sample.scale * (*base_scale as f32) / 100.0;
-                if scale <= size.small_boarder {
-                    small_chance += sample.rate;
-                }
-                if scale >= size.king_boarder {
-                    large_chance += sample.rate;
-                }
-            }
-
-            let small = (small_chance != 0).then(|| {
-                html!(<span class="tag">
-                    <img src="/resources/small_crown.png" />
-                    {text!("{}%", small_chance)}
-                </span>)
-            });
-
-            let large = (large_chance != 0).then(|| {
-                html!(<span class="tag">
-                    <img src="/resources/king_crown.png" />
-                    {text!("{}%", large_chance)}
-                </span>)
-            });
-
-            html!(<span>{small}{large}</span>)
-        } else {
-            html!(<span>"-"</span>)
-        }
+    let size = if let Some(odds) = quest_crown_odds(pedia_ex, enemy_param, em_type, index) {
+        html!(<span>{gen_crown_tags(lang, &odds)}</span>)
     } else {
         html!(<span>"-"</span>)
     };
@@ -221,11 +199,12 @@ fn gen_multi_factor(data: &MultiData) -> Box<div<String>> {
 }
 
 fn gen_quest_monster_multi_player_data(
+    lang: Option<usize>,
     enemy_param: Option<&SharedEnemyParam>,
     index: usize,
     pedia: &Pedia,
 ) -> impl IntoIterator<Item = Box<td<String>>> {
-    let no_data = || vec![html!(<td colspan=9>"[NO DATA]"</td>)];
+    let no_data = || vec![html!(<td colspan=9>{ui(lang, "[NO DATA]")}</td>)];
 
     let enemy_param = if let Some(enemy_param) = enemy_param.as_ref() {
         enemy_param
@@ -255,89 +234,340 @@ fn gen_quest_monster_multi_player_data(
         .collect()
 }
 
-fn gen_monster_tag(quest: &Quest, pedia: &Pedia, em_type: EmTypes) -> Box<td<String>> {
-    let id = match em_type {
-        EmTypes::Em(id) => id,
-        EmTypes::Ems(_) => return html!(<td>"Unexpected small monster"</td>),
-    };
+fn gen_supply(
+    lang: Option<usize>,
+    supply: &SupplyDataParam,
+    pedia_ex: &PediaEx<'_>,
+) -> Box<div<String>> {
+    html!(<div class="box">
+        <table>
+            <thead><tr>
+                <th>{ui(lang, "Supply items")}</th>
+            </tr></thead>
+            <tbody> {
+                supply.item_id.iter().zip(&supply.num)
+                .filter(|&(&item, _)| item != ItemId::None)
+                .map(|(&item, &num)| {
+                    let item = if let Some(item) = pedia_ex.items.get(&item) {
+                        html!(<span>{gen_item_label(lang, item)}</span>)
+                    } else {
+                        html!(<span>{text!("{:?}", item)}</span>)
+                    };
+                    html!(<tr><td>{text!("{}x ", num)}{item}</td></tr>)
+                })
+            } </tbody>
+        </table>
+    </div>)
+}
 
+fn lot_rule_text(rule: LotRule) -> &'static str {
+    match rule {
+        LotRule::Random => "Random",
+        LotRule::RandomOut1 => "Random out 1",
+        LotRule::RandomOut2 => "Random out 2",
+        LotRule::RandomOut3 => "Random out 3",
+        LotRule::FirstFix => "First fixed",
+    }
+}
+
+fn gen_reward_lot(
+    lang: Option<usize>,
+    header: &str,
+    lot: &RewardIdLotTableUserDataParam,
+    pedia_ex: &PediaEx<'_>,
+) -> Box<div<String>> {
+    html!(<div class="box">
+        <table>
+            <thead><tr>
+                <th>{text!("{} ({})", header, ui_text(lang, lot_rule_text(lot.lot_rule)))}</th>
+                <th>{ui(lang, "Probability")}</th>
+            </tr></thead>
+            <tbody> {
+                gen_reward_table(lang, pedia_ex,
+                    &lot.item_id_list,
+                    &lot.num_list,
+                    &lot.probability_list)
+            } </tbody>
+        </table>
+    </div>)
+}
+
+pub fn gen_monster_link(lang: Option<usize>, pedia: &Pedia, id: u32) -> Box<a<String>> {
     let monster = pedia.monsters.iter().find(|m| (m.id | m.sub_id << 8) == id);
     let monster_name = (|| {
         let name_name = format!(
             "EnemyIndex{:03}",
             monster?.boss_init_set_data.as_ref()?.enemy_type
         );
-        Some(gen_multi_lang(pedia.monster_names.get_entry(&name_name)?))
+        Some(gen_multi_lang(
+            lang,
+            pedia.monster_names.get_entry(&name_name)?,
+        ))
     })()
-    .unwrap_or(html!(<span>{text!("Monster {0:03}_{1:02}",
-                                id & 0xFF, id >> 8)}</span>));
+    .unwrap_or_else(|| {
+        ui_args(
+            lang,
+            "Monster {}",
+            &[&format!("{:03}_{:02}", id & 0xFF, id >> 8)],
+        )
+    });
 
     let icon_path = format!("/resources/em{0:03}_{1:02}_icon.png", id & 0xFF, id >> 8);
 
+    html!(<a href={format!("/monster/{:03}_{1:02}.html", id & 0xFF, id >> 8)}>
+        <img class="mh-quest-list-monster-icon" src=icon_path />
+        <span  class="mh-quest-list-monster-name">
+            {monster_name}
+        </span>
+    </a>)
+}
+
+fn gen_monster_tag(
+    lang: Option<usize>,
+    quest: &Quest,
+    pedia: &Pedia,
+    em_type: EmTypes,
+) -> Box<td<String>> {
+    let id = match em_type {
+        EmTypes::Em(id) => id,
+        EmTypes::Ems(_) => return html!(<td>{ui(lang, "Unexpected small monster")}</td>),
+    };
+
     let target_tag = if quest.param.tgt_em_type.contains(&em_type) {
-        html!(<span class="tag is-primary">"Target"</span>)
+        html!(<span class="tag is-primary">{ui(lang, "Target")}</span>)
     } else {
         html!(<span />)
     };
     html!(<td>
-        <a href={format!("/monster/{:03}_{1:02}.html", id & 0xFF, id >> 8)}>
-            <img class="mh-quest-list-monster-icon" src=icon_path />
-            <span  class="mh-quest-list-monster-name">
-                {monster_name}
-            </span>
-        </a>
+        {gen_monster_link(lang, pedia, id)}
         {target_tag}
     </td>)
 }
 
-fn gen_quest(quest: &Quest, pedia: &Pedia, pedia_ex: &PediaEx<'_>, path: &Path) -> Result<()> {
+fn gen_quest_spawns(lang: Option<usize>, quest: &Quest, pedia: &Pedia) -> Box<section<String>> {
+    let init_set_name = quest
+        .enemy_param
+        .as_ref()
+        .map_or(&[][..], |p| &p.param.init_set_name[..]);
+    html!(<section class="section">
+        <h2 class="title">{ui(lang, "Starting areas")}</h2>
+        <table>
+            <thead><tr>
+                <th>{ui(lang, "Monster")}</th>
+                <th>{ui(lang, "Spawn set")}</th>
+                <th>{ui(lang, "Areas")}</th>
+            </tr></thead>
+            <tbody> {
+                quest.param.boss_em_type.iter().copied().enumerate()
+                .filter(|&(_, em_type)| em_type != EmTypes::Em(0))
+                .map(|(i, em_type)| {
+                    let set_name = init_set_name.get(i).map_or("", |name| name.as_str());
+                    let areas = find_set_info(pedia, em_type, quest.param.map_no, set_name)
+                        .map(|set| gen_spawn_areas(lang, set));
+                    html!(<tr>
+                        { gen_monster_tag(lang, quest, pedia, em_type) }
+                        <td class="is-family-monospace">{text!("{}", set_name)}</td>
+                        <td>{areas}</td>
+                    </tr>)
+                })
+            } </tbody>
+        </table>
+    </section>)
+}
+
+// The apex is the extra monster of the quest that no wave has as its major monster.
+// Falls back to the major monster of the apex wave.
+fn hyakuryu_apex(quest: &Quest, hyakuryu: &HyakuryuQuestData) -> Option<EmTypes> {
+    let wave_bosses: Vec<EmTypes> = hyakuryu.wave_data.iter().map(|wave| wave.boss_em).collect();
+    quest
+        .param
+        .boss_em_type
+        .iter()
+        .copied()
+        .find(|&em_type| em_type != EmTypes::Em(0) && !wave_bosses.contains(&em_type))
+        .or_else(|| {
+            let wave = usize::try_from(hyakuryu.extra_em_wave_no)
+                .ok()?
+                .checked_sub(1)?;
+            Some(hyakuryu.wave_data.get(wave)?.boss_em)
+        })
+        .filter(|&em_type| em_type != EmTypes::Em(0))
+}
+
+fn gen_hyakuryu_waves(
+    lang: Option<usize>,
+    quest: &Quest,
+    hyakuryu: &HyakuryuQuestData,
+    pedia: &Pedia,
+) -> Box<section<String>> {
+    let em_link = |em_type: EmTypes| match em_type {
+        EmTypes::Em(0) => None,
+        EmTypes::Em(id) => Some(html!(<li>{gen_monster_link(lang, pedia, id)}</li>)),
+        EmTypes::Ems(id) => Some(html!(<li>{ui_args(lang, "Small monster {}",
+                &[&format!("{:03}_{:02}", id & 0xFF, id >> 8)])}</li>)),
+    };
+
+    html!(<section class="section">
+        <h2 class="title">{ui(lang, "Rampage waves")}</h2>
+        <p>{ui_args(lang, "Level: {} / Apex wave: {}",
+            &[&hyakuryu.quest_lv, &hyakuryu.extra_em_wave_no])}</p>
+        <div>{ui(lang, "Apex monster: ")}<ul>{
+            hyakuryu_apex(quest, hyakuryu).and_then(em_link)
+        }</ul></div>
+        <table>
+            <thead><tr>
+                <th>{ui(lang, "Wave")}</th>
+                <th>{ui(lang, "Major monster")}</th>
+                <th>{ui(lang, "Wave monsters")}</th>
+                <th>{ui(lang, "Order table")}</th>
+                <th>{ui(lang, "Difficulty table (major / wave)")}</th>
+            </tr></thead>
+            <tbody> {
+                hyakuryu.wave_data.iter().enumerate().map(|(i, wave)| {
+                    let apex = (usize::try_from(hyakuryu.extra_em_wave_no).ok() == Some(i + 1))
+                        .then(|| html!(<span class="tag is-danger">{ui(lang, "Apex")}</span>));
+                    html!(<tr>
+                        <td>{text!("{}", i + 1)}{apex}</td>
+                        <td><ul>{ em_link(wave.boss_em) }</ul></td>
+                        <td><ul>{ wave.em_table.iter().filter_map(|&em_type| em_link(em_type)) }</ul></td>
+                        <td>{text!("{}", wave.order_tbl)}</td>
+                        <td>{text!("{} / {}", wave.boss_em_nando_tbl_no, wave.wave_em_nando_tbl_no)}</td>
+                    </tr>)
+                })
+            } </tbody>
+        </table>
+    </section>)
+}
+
+// Rampage quests reward the target materials of each major monster slain, apex included
+fn gen_hyakuryu_rewards(
+    lang: Option<usize>,
+    quest: &Quest,
+    hyakuryu: &HyakuryuQuestData,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+) -> Box<section<String>> {
+    let rank = match quest.param.enemy_level {
+        EnemyLevel::High => QuestRank::High,
+        EnemyLevel::Village | EnemyLevel::Low => QuestRank::Low,
+    };
+    let mut monsters: Vec<EmTypes> = vec![];
+    for em_type in hyakuryu
+        .wave_data
+        .iter()
+        .map(|wave| wave.boss_em)
+        .chain(hyakuryu_apex(quest, hyakuryu))
+    {
+        if em_type != EmTypes::Em(0) && !monsters.contains(&em_type) {
+            monsters.push(em_type);
+        }
+    }
+
+    html!(<section class="section">
+        <h2 class="title">{ui(lang, "Rampage rewards")}</h2>
+        <div class="mh-reward-tables">{
+            monsters.into_iter().filter_map(|em_type| {
+                let id = match em_type {
+                    EmTypes::Em(id) => id,
+                    EmTypes::Ems(_) => return None,
+                };
+                let lot = pedia_ex.monster_lot.get(&(em_type, rank))?;
+                Some(html!(<div class="box">
+                    <table>
+                        <thead><tr>
+                            <th>{gen_monster_link(lang, pedia, id)}</th>
+                            <th>{ui(lang, "Probability")}</th>
+                        </tr></thead>
+                        <tbody> {
+                            gen_reward_table(lang, pedia_ex,
+                                &lot.target_reward_item_id_list,
+                                &lot.target_reward_num_list,
+                                &lot.target_reward_probability_list)
+                        } </tbody>
+                    </table>
+                </div>))
+            })
+        }</div>
+    </section>)
+}
+
+fn gen_quest(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
+    quest: &Quest,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+) -> Result<String> {
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Quest {:06}", quest.param.quest_no)}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text_args(lang, "Quest {}",
+                    &[&format!("{:06}", quest.param.quest_no)]))}</title>
+                { head_common(lang, alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
                 <h1 class="title">
-                <span class="tag">{text!("{:?}-{:?}", quest.param.enemy_level, quest.param.quest_level)}</span>
+                {gen_quest_tag(lang, quest.param.enemy_level, quest.param.quest_level)}
                 {
                     quest.name.as_ref().map_or(
-                        html!(<span>{text!("Quest {:06}", quest.param.quest_no)}</span>),
-                        gen_multi_lang
+                        ui_args(lang, "Quest {}", &[&format!("{:06}", quest.param.quest_no)]),
+                        |msg| gen_multi_lang(lang, msg)
                     )
                 }</h1>
-                <p><span>"Objective: "</span><span> {
+                <p><span>{ui(lang, "Objective: ")}</span><span> {
                     quest.target.as_ref().map_or(
                         html!(<span>"-"</span>),
-                        gen_multi_lang
+                        |msg| gen_multi_lang(lang, msg)
                     )
                 }</span></p>
+                <p><span>{ui(lang, "Map: ")}</span><a href={format!("/map/{}", map_page(quest.param.map_no))}>
+                    {gen_map_label(lang, pedia, quest.param.map_no)}
+                </a></p>
+                { gen_quest_spawns(lang, quest, pedia) }
+                { quest.hyakuryu.as_ref().map(|hyakuryu| gen_hyakuryu_waves(lang, quest, hyakuryu, pedia)) }
+                { quest.hyakuryu.as_ref().map(|hyakuryu|
+                    gen_hyakuryu_rewards(lang, quest, hyakuryu, pedia, pedia_ex)) }
+                <section class="section">
+                <h2 class="title">{ui(lang, "Rewards")}</h2>
+                <p>{ui_args(lang, "Money: {}z / Village points: {} / Rank points: {}",
+                    &[&quest.param.rem_money, &quest.param.rem_village_point, &quest.param.rem_rank_point])}</p>
+                <div class="mh-reward-tables">
+                { quest.supply.as_ref().map(|supply| gen_supply(lang, supply, pedia_ex)) }
+                { quest.reward.as_ref().and_then(|reward| reward.additional_target_reward.as_ref())
+                    .map(|lot| gen_reward_lot(lang, &ui_text(lang, "Main rewards"), lot, pedia_ex)) }
+                { quest.reward.as_ref().and_then(|reward| reward.common_material_reward.as_ref()
+                    .map(|lot| gen_reward_lot(lang, &ui_text_args(lang, "Sub rewards (x{})",
+                        &[&reward.param.common_material_reward_num]), lot, pedia_ex))) }
+                { quest.reward.iter().flat_map(|reward| &reward.additional_quest_reward)
+                    .map(|lot| gen_reward_lot(lang, &ui_text(lang, "Additional rewards"), lot, pedia_ex)) }
+                </div>
+                </section>
                 <section class="section">
-                <h2 class="title">"Monster stats"</h2>
+                <h2 class="title">{ui(lang, "Monster stats")}</h2>
                 <table>
                     <thead><tr>
-                        <th>"Monster"</th>
-                        <th>"Size (?)"</th>
-                        <th>"HP"</th>
-                        <th>"Attack"</th>
-                        <th>"Parts"</th>
-                        <th>"Defense"</th>
-                        <th>"Element A"</th>
-                        <th>"Element B"</th>
-                        <th>"Stun"</th>
-                        <th>"Exhaust"</th>
-                        <th>"Ride"</th>
-                        <th>"Stamina"</th>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "Size (?)")}</th>
+                        <th>{ui(lang, "HP")}</th>
+                        <th>{ui(lang, "Attack")}</th>
+                        <th>{ui(lang, "Parts")}</th>
+                        <th>{ui(lang, "Defense")}</th>
+                        <th>{ui(lang, "Element A")}</th>
+                        <th>{ui(lang, "Element B")}</th>
+                        <th>{ui(lang, "Stun")}</th>
+                        <th>{ui(lang, "Exhaust")}</th>
+                        <th>{ui(lang, "Ride")}</th>
+                        <th>{ui(lang, "Stamina")}</th>
                     </tr></thead>
                     <tbody> {
                         quest.param.boss_em_type.iter().copied().enumerate()
                         .filter(|&(_, em_type)|em_type != EmTypes::Em(0))
                         .map(|(i, em_type)|{
                             html!(<tr>
-                                { gen_monster_tag(quest, pedia, em_type) }
-                                { gen_quest_monster_data(quest.enemy_param.as_ref().map(|p|&p.param),
+                                { gen_monster_tag(lang, quest, pedia, em_type) }
+                                { gen_quest_monster_data(lang, quest.enemy_param.as_ref().map(|p|&p.param),
                                     em_type, i, pedia, pedia_ex) }
                             </tr>)
                         })
@@ -345,31 +575,95 @@ fn gen_quest(quest: &Quest, pedia: &Pedia, pedia_ex: &PediaEx<'_>, path: &Path)
                 </table>
                 </section>
                 <section class="section">
-                <h2 class="title">"Multiplayer Factor (Column header might be wrong)"</h2>
+                <h2 class="title">{ui(lang, "Effective stats for solo hunts")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "HP")}</th>
+                        <th>{ui(lang, "Attack")}</th>
+                        <th>{ui(lang, "Part HP")}</th>
+                        <th>{ui(lang, "Defense")}</th>
+                        <th>{ui(lang, "Element A")}</th>
+                        <th>{ui(lang, "Element B")}</th>
+                        <th>{ui(lang, "Stun")}</th>
+                        <th>{ui(lang, "Exhaust")}</th>
+                        <th>{ui(lang, "Ride")}</th>
+                    </tr></thead>
+                    <tbody> {
+                        quest.monster_stats.iter().map(|stats| html!(<tr>
+                            { gen_monster_tag(lang, quest, pedia, stats.em_type) }
+                            <td>{text!("{:.0}", stats.solo.hp)}</td>
+                            <td>{text!("x{:.2}", stats.solo.attack_rate)}</td>
+                            <td>{text!("{}", stats.solo.parts_vital.iter()
+                                .map(|v| format!("{:.0}", v)).collect::<Vec<_>>().join(" / "))}</td>
+                            <td>{text!("x{:.2}", stats.solo.defense_rate)}</td>
+                            <td>{text!("x{:.2}", stats.solo.element_a_rate)}</td>
+                            <td>{text!("x{:.2}", stats.solo.element_b_rate)}</td>
+                            <td>{text!("x{:.2}", stats.solo.stun_rate)}</td>
+                            <td>{text!("x{:.2}", stats.solo.exhaust_rate)}</td>
+                            <td>{text!("x{:.2}", stats.solo.ride_rate)}</td>
+                        </tr>))
+                    } </tbody>
+                </table>
+                </section>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Status buildup")}</h2>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "Status")}</th>
+                        <th>{ui(lang, "Threshold")}</th>
+                        <th>{ui(lang, "Quest rate")}</th>
+                    </tr></thead>
+                    <tbody> {
+                        quest.monster_stats.iter().flat_map(|stats| {
+                            let count = stats.buildups.len();
+                            stats.buildups.iter().enumerate().map(move |(i, buildup)| {
+                                let monster = (i == 0).then(|| html!(<td rowspan={count}>
+                                    {match stats.em_type {
+                                        EmTypes::Em(id) => gen_monster_link(lang, pedia, id),
+                                        EmTypes::Ems(_) => html!(<a>{ui(lang, "Unexpected small monster")}</a>),
+                                    }}
+                                </td>));
+                                html!(<tr>
+                                    {monster}
+                                    <td>{ui(lang, buildup.status)}</td>
+                                    <td>{text!("{} (+{}) -> {}", buildup.default_limit,
+                                        buildup.add_limit, buildup.max_limit)}</td>
+                                    <td>{text!("{}", buildup.quest_rate
+                                        .map_or_else(|| "-".to_owned(), |rate| format!("x{:.2}", rate)))}</td>
+                                </tr>)
+                            })
+                        })
+                    } </tbody>
+                </table>
+                </section>
+                <section class="section">
+                <h2 class="title">{ui(lang, "Multiplayer Factor (Column header might be wrong)")}</h2>
 
                 <table>
                     <thead><tr>
-                        <th>"Monster"</th>
-                        <th>"HP"</th>
-                        <th>"Attack"</th>
-                        <th>"Parts"</th>
-                        <th>"Other parts"</th>
-                        <th>"Multi parts"</th>
-                        <th>"Defense"</th>
-                        <th>"Element A"</th>
-                        <th>"Element B"</th>
-                        <th>"Stun"</th>
-                        <th>"Exhaust"</th>
-                        <th>"Ride"</th>
-                        <th>"Monster to monster"</th>
+                        <th>{ui(lang, "Monster")}</th>
+                        <th>{ui(lang, "HP")}</th>
+                        <th>{ui(lang, "Attack")}</th>
+                        <th>{ui(lang, "Parts")}</th>
+                        <th>{ui(lang, "Other parts")}</th>
+                        <th>{ui(lang, "Multi parts")}</th>
+                        <th>{ui(lang, "Defense")}</th>
+                        <th>{ui(lang, "Element A")}</th>
+                        <th>{ui(lang, "Element B")}</th>
+                        <th>{ui(lang, "Stun")}</th>
+                        <th>{ui(lang, "Exhaust")}</th>
+                        <th>{ui(lang, "Ride")}</th>
+                        <th>{ui(lang, "Monster to monster")}</th>
                     </tr></thead>
                     <tbody> {
                         quest.param.boss_em_type.iter().copied().enumerate()
                         .filter(|&(_, em_type)|em_type != EmTypes::Em(0))
                         .map(|(i, em_type)|{
                             html!(<tr>
-                                { gen_monster_tag(quest, pedia, em_type) }
-                                { gen_quest_monster_multi_player_data(
+                                { gen_monster_tag(lang, quest, pedia, em_type) }
+                                { gen_quest_monster_multi_player_data(lang,
                                     quest.enemy_param.as_ref().map(|p|&p.param), i, pedia) }
                             </tr>)
                         })
@@ -382,16 +676,24 @@ fn gen_quest(quest: &Quest, pedia: &Pedia, pedia_ex: &PediaEx<'_>, path: &Path)
         </html>
     );
 
-    write(&path, doc.to_string())?;
-    Ok(())
+    Ok(doc.to_string())
 }
 
-pub fn gen_quests(pedia: &Pedia, pedia_ex: &PediaEx<'_>, root: &Path) -> Result<()> {
+pub fn gen_quests(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
     let quest_path = root.join("quest");
-    create_dir(&quest_path)?;
+    create_dir_all(&quest_path)?;
     for quest in &pedia_ex.quests {
         let path = quest_path.join(format!("{:06}.html", quest.param.quest_no));
-        gen_quest(quest, pedia, pedia_ex, &path)?
+        let alternates = manifest.alternate_links(&path)?;
+        manifest.page(&path, quest, || {
+            gen_quest(lang, &alternates, quest, pedia, pedia_ex)
+        })?
     }
     Ok(())
 }
@@ -0,0 +1,152 @@
+use super::gen_item::*;
+use super::gen_pedia::item_icon_file_name;
+use super::gen_skill::*;
+use super::gen_website::*;
+use super::manifest::*;
+use super::pedia::*;
+use crate::msg::*;
+use anyhow::*;
+use serde::*;
+use std::fs::create_dir_all;
+use std::path::*;
+
+#[derive(Serialize)]
+struct SearchEntry<'a> {
+    name: &'a str,
+    kind: &'static str,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<String>,
+}
+
+struct SearchSource<'a> {
+    msg: &'a MsgEntry,
+    kind: &'static str,
+    url: String,
+    icon: Option<String>,
+}
+
+fn gather_sources<'a>(pedia: &'a Pedia, pedia_ex: &'a PediaEx<'a>) -> Vec<SearchSource<'a>> {
+    let mut sources = vec![];
+
+    for monster in &pedia.monsters {
+        let enemy_type = if let Some(boss_init_set_data) = &monster.boss_init_set_data {
+            boss_init_set_data.enemy_type
+        } else {
+            continue;
+        };
+        let url = format!("/monster/{:03}_{:02}.html", monster.id, monster.sub_id);
+        let names = [
+            (&pedia.monster_names, format!("EnemyIndex{:03}", enemy_type)),
+            (
+                &pedia.monster_aliases,
+                format!("Alias_EnemyIndex{:03}", enemy_type),
+            ),
+        ];
+        for (msg, name) in &names {
+            if let Some(msg) = msg.get_entry(name) {
+                sources.push(SearchSource {
+                    msg,
+                    kind: "Monster",
+                    url: url.clone(),
+                    icon: None,
+                });
+            }
+        }
+    }
+
+    for quest in &pedia_ex.quests {
+        if let Some(msg) = &quest.name {
+            sources.push(SearchSource {
+                msg,
+                kind: "Quest",
+                url: format!("/quest/{:06}.html", quest.param.quest_no),
+                icon: None,
+            });
+        }
+    }
+
+    for (&id, skill) in &pedia_ex.skills {
+        sources.push(SearchSource {
+            msg: &skill.name,
+            kind: "Skill",
+            url: format!("/skill/{}", skill_page(id)),
+            icon: None,
+        });
+    }
+
+    for series in &pedia_ex.armors {
+        let url = format!("/armor/{:03}.html", series.series.armor_series.0);
+        if let Some(msg) = &series.name {
+            sources.push(SearchSource {
+                msg,
+                kind: "Armor series",
+                url: url.clone(),
+                icon: None,
+            });
+        }
+        for piece in series.pieces.iter().flatten() {
+            sources.push(SearchSource {
+                msg: &piece.name,
+                kind: "Armor",
+                url: url.clone(),
+                icon: None,
+            });
+        }
+    }
+
+    for (&id, item) in &pedia_ex.items {
+        sources.push(SearchSource {
+            msg: &item.name,
+            kind: "Item",
+            url: format!("/item/{}", item_page(id)),
+            icon: Some(format!(
+                "/resources/item_icon/{}",
+                item_icon_file_name(item.param)
+            )),
+        });
+    }
+
+    sources
+}
+
+pub fn gen_search_index(
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let search_path = root.join("search");
+    create_dir_all(&search_path)?;
+
+    let sources = gather_sources(pedia, pedia_ex);
+
+    for (language, _) in LANGUAGE_MAP
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| name.is_some())
+    {
+        let entries: Vec<SearchEntry> = sources
+            .iter()
+            .filter_map(|source| {
+                let name = source.msg.content.get(language)?;
+                if name.is_empty() {
+                    return None;
+                }
+                Some(SearchEntry {
+                    name,
+                    kind: source.kind,
+                    url: source.url.clone(),
+                    icon: source.icon.clone(),
+                })
+            })
+            .collect();
+
+        manifest.write(
+            &search_path.join(format!("{}.json", language)),
+            serde_json::to_string(&entries)?,
+        )?;
+    }
+
+    Ok(())
+}
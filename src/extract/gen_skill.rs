@@ -1,9 +1,12 @@
+use super::gen_item::*;
+use super::gen_weapon::gen_weapon_label;
 use super::gen_website::*;
+use super::manifest::*;
 use super::pedia::*;
 use crate::rsz::*;
 use anyhow::*;
 use std::collections::BTreeMap;
-use std::fs::{create_dir, write};
+use std::fs::create_dir_all;
 use std::path::*;
 use typed_html::{dom::*, html, text};
 
@@ -14,24 +17,30 @@ pub fn skill_page(id: PlEquipSkillId) -> String {
     }
 }
 
-pub fn gen_skill_list(skills: &BTreeMap<PlEquipSkillId, Skill>, root: &Path) -> Result<()> {
+pub fn gen_skill_list(
+    lang: Option<usize>,
+    skills: &BTreeMap<PlEquipSkillId, Skill>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("skill.html"))?;
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Skills - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Skills"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"Skill"</h1>
+                <h1 class="title">{ui(lang, "Skill")}</h1>
                 <ul class="mh-list-skill">
                 {
                     skills.iter().map(|(&id, skill)|{
                         html!(<li class="mh-list-skill">
                             <a href={format!("/skill/{}", skill_page(id))} class="mh-icon-text">
                             {gen_colored_icon(skill.icon_color, "/resources/skill", &[])}
-                            <span>{gen_multi_lang(&skill.name)}</span>
+                            <span>{gen_multi_lang(lang, &skill.name)}</span>
                             </a>
                         </li>)
                     })
@@ -42,33 +51,37 @@ pub fn gen_skill_list(skills: &BTreeMap<PlEquipSkillId, Skill>, root: &Path) ->
         </html>
     );
     let quests_path = root.join("skill.html");
-    write(&quests_path, doc.to_string())?;
+    manifest.write(&quests_path, doc.to_string())?;
 
     Ok(())
 }
 
-pub fn gen_skill(skill: &Skill, path: &Path) -> Result<()> {
+pub fn gen_skill(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
+    skill: &Skill,
+) -> Result<String> {
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Skill - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Skill"))}</title>
+                { head_common(lang, alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
                 <div class="mh-title-icon">
                     {gen_colored_icon(skill.icon_color, "/resources/skill", &[])}
                 </div>
                 <h1 class="title">
-                    {gen_multi_lang(&skill.name)}
+                    {gen_multi_lang(lang, &skill.name)}
                 </h1>
-                <div>{gen_multi_lang(&skill.explain)}</div>
+                <div>{gen_multi_lang(lang, &skill.explain)}</div>
                 <ul>{
                     skill.levels.iter().enumerate().map(|(level, detail)| {
                         html!(<li>
-                            <span>{text!("Level {}: ", level + 1)}</span>
-                            <span>{gen_multi_lang(detail)}</span>
+                            {ui_args(lang, "Level {}: ", &[&(level + 1)])}
+                            <span>{gen_multi_lang(lang, detail)}</span>
                         </li>)
                     })
                 }</ul>
@@ -77,17 +90,84 @@ pub fn gen_skill(skill: &Skill, path: &Path) -> Result<()> {
         </html>
     );
 
-    write(&path, doc.to_string())?;
-
-    Ok(())
+    Ok(doc.to_string())
 }
 
-pub fn gen_skills(skills: &BTreeMap<PlEquipSkillId, Skill>, root: &Path) -> Result<()> {
+pub fn gen_skills(
+    lang: Option<usize>,
+    skills: &BTreeMap<PlEquipSkillId, Skill>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
     let skill_path = root.join("skill");
-    create_dir(&skill_path)?;
+    create_dir_all(&skill_path)?;
     for (&id, skill) in skills {
         let path = skill_path.join(skill_page(id));
-        gen_skill(skill, &path)?
+        let alternates = manifest.alternate_links(&path)?;
+        manifest.page(&path, skill, || gen_skill(lang, &alternates, skill))?
     }
     Ok(())
 }
+
+pub fn gen_hyakuryu_skill_list(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("hyakuryu_skill.html"))?;
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Rampage skills"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Rampage skills")}</h1>
+                <table>
+                    <thead><tr>
+                        <th>{ui(lang, "Skill")}</th>
+                        <th>{ui(lang, "Description")}</th>
+                        <th>{ui(lang, "Cost")}</th>
+                        <th>{ui(lang, "Materials")}</th>
+                        <th>{ui(lang, "Weapons")}</th>
+                    </tr></thead>
+                    <tbody> {
+                        pedia_ex.hyakuryu_skills.iter().map(|(&id, skill)| html!(<tr>
+                            <td>{skill.name.as_ref().map_or_else(
+                                || ui_args(lang, "Rampage skill {}", &[&id]),
+                                |msg| gen_multi_lang(lang, msg)
+                            )}</td>
+                            <td>{skill.explain.as_ref().map(|msg| gen_multi_lang(lang, msg))}</td>
+                            <td>{text!("{}", skill.recipe.map_or(0, |recipe| recipe.cost))}</td>
+                            <td><ul>{
+                                skill.recipe.into_iter().flat_map(|recipe| recipe.recipe_item_id_list.iter()
+                                    .zip(&recipe.recipe_item_num_list))
+                                .filter(|&(&item, _)| item != ItemId::None)
+                                .map(|(item, &num)| {
+                                    let item = if let Some(item) = pedia_ex.items.get(item) {
+                                        html!(<span>{gen_item_label(lang, item)}</span>)
+                                    } else {
+                                        html!(<span>{text!("{:?}", item)}</span>)
+                                    };
+                                    html!(<li>{text!("{}x ", num)}{item}</li>)
+                                })
+                            }</ul></td>
+                            <td><ul>{
+                                skill.weapons.iter().map(|weapon| html!(<li>
+                                    {gen_weapon_label(lang, weapon)}
+                                </li>))
+                            }</ul></td>
+                        </tr>))
+                    } </tbody>
+                </table>
+                </div></div></main>
+            </body>
+        </html>
+    );
+    manifest.write(&root.join("hyakuryu_skill.html"), doc.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use super::gen_website::*;
+use super::manifest::*;
+use super::pedia::*;
+use anyhow::*;
+use std::path::*;
+use typed_html::{dom::*, elements::*, html, text};
+
+pub fn gen_weapon_label(lang: Option<usize>, weapon: &Weapon) -> Box<span<String>> {
+    match weapon.name {
+        Some(name) => gen_multi_lang(lang, name),
+        None => html!(<span>
+            {ui(lang, weapon.weapon_type)}
+            <span class="is-family-monospace">{text!(" {:08X}", weapon.main.base.id)}</span>
+        </span>),
+    }
+}
+
+fn gen_hyakuryu_skill_label(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx<'_>,
+    id: u32,
+) -> Box<span<String>> {
+    match pedia_ex
+        .hyakuryu_skills
+        .get(&id)
+        .and_then(|skill| skill.name.as_ref())
+    {
+        Some(name) => gen_multi_lang(lang, name),
+        None => ui_args(lang, "Rampage skill {}", &[&id]),
+    }
+}
+
+pub fn gen_weapon_list(
+    lang: Option<usize>,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("weapon.html"))?;
+    let doc: DOMTree<String> = html!(
+        <html lang={html_lang(lang)}>
+            <head>
+                <title>{text!("{} - MHRice", ui_text(lang, "Weapons"))}</title>
+                { head_common(lang, &alternates) }
+            </head>
+            <body>
+                { navbar(lang) }
+                <main> <div class="container"> <div class="content">
+                <h1 class="title">{ui(lang, "Weapons")}</h1>
+                {
+                    pedia_ex.weapons.iter().map(|(weapon_type, weapons)| html!(<section class="section">
+                        <h2 class="title">{ui(lang, weapon_type)}</h2>
+                        <table>
+                            <thead><tr>
+                                <th>{ui(lang, "Weapon")}</th>
+                                <th>{ui(lang, "Rarity")}</th>
+                                <th>{ui(lang, "Attack")}</th>
+                                <th>{ui(lang, "Affinity")}</th>
+                                <th>{ui(lang, "Defense")}</th>
+                                <th>{ui(lang, "Slots")}</th>
+                                <th>{ui(lang, "Rampage skills")}</th>
+                            </tr></thead>
+                            <tbody> {
+                                weapons.iter().map(|weapon| {
+                                    let main = weapon.main;
+                                    let slots = main.slot_num_list.iter()
+                                        .map(|slot| slot.to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(" / ");
+                                    html!(<tr>
+                                        <td>{gen_weapon_label(lang, weapon)}</td>
+                                        <td>{text!("{}", main.base.rare_type.0)}</td>
+                                        <td>{text!("{}", main.atk)}</td>
+                                        <td>{text!("{}%", main.critical_rate)}</td>
+                                        <td>{text!("{}", main.def_bonus)}</td>
+                                        <td>{text!("{}", slots)}</td>
+                                        <td><ul>{
+                                            main.hyakuryu_skill_id_list.iter()
+                                                .filter(|&&id| id != 0)
+                                                .map(|&id| html!(<li>
+                                                    {gen_hyakuryu_skill_label(lang, pedia_ex, id)}
+                                                </li>))
+                                        }</ul></td>
+                                    </tr>)
+                                })
+                            } </tbody>
+                        </table>
+                    </section>))
+                }
+                </div></div></main>
+            </body>
+        </html>
+    );
+    manifest.write(&root.join("weapon.html"), doc.to_string())?;
+
+    Ok(())
+}
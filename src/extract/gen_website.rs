@@ -1,20 +1,34 @@
+use super::diff::PediaDiff;
+use super::gen_api::*;
 use super::gen_armor::*;
+use super::gen_crown::*;
+use super::gen_diff::gen_patch_notes;
+use super::gen_habitat::*;
 use super::gen_item::*;
+use super::gen_map::*;
 use super::gen_monster::*;
+use super::gen_pedia::Resources;
 use super::gen_quest::*;
+use super::gen_search::*;
 use super::gen_skill::*;
+use super::gen_weapon::*;
+use super::manifest::*;
 use super::pedia::*;
+use super::ui_text::translate;
 use crate::msg::*;
 use crate::part_color::*;
+use crate::rsz::{EnemyLevel, QuestLevel};
 use anyhow::*;
 use chrono::prelude::*;
+use serde::*;
 use std::convert::TryInto;
-use std::fs::{create_dir, remove_dir_all, write, File};
-use std::io::Write;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::fs::create_dir_all;
 use std::path::*;
 use typed_html::{dom::*, elements::*, html, text, types::*};
 
-const LANGUAGE_MAP: [Option<&str>; 32] = [
+pub const LANGUAGE_MAP: [Option<&str>; 32] = [
     Some("Japanese"),
     Some("English"),
     Some("French"),
@@ -49,8 +63,154 @@ const LANGUAGE_MAP: [Option<&str>; 32] = [
     None,
 ];
 
-pub fn head_common() -> Vec<Box<dyn MetadataContent<String>>> {
-    vec![
+pub const LANGUAGE_CODES: [Option<&str>; 32] = [
+    Some("ja"),
+    Some("en"),
+    Some("fr"),
+    Some("it"),
+    Some("de"),
+    Some("es"),
+    Some("ru"),
+    Some("pl"),
+    None,
+    None,
+    Some("pt-BR"),
+    Some("ko"),
+    Some("zh-Hant"),
+    Some("zh-Hans"),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some("ar"),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+
+pub fn language_from_code(code: &str) -> Result<usize> {
+    LANGUAGE_CODES
+        .iter()
+        .position(|c| *c == Some(code))
+        .with_context(|| format!("Unknown language code {}", code))
+}
+
+#[derive(Serialize)]
+pub struct SiteOptions {
+    // Generates a single-language site if set. Otherwise all languages are switchable in place
+    pub language: Option<usize>,
+    // (language, base URL) of the sites for the other languages
+    pub alternates: Vec<(usize, String)>,
+}
+
+fn translate_or_english(text: &str, language: usize) -> &str {
+    translate(text, language).unwrap_or(text)
+}
+
+// Fills the {} placeholders of a template in order
+fn fill_template(template: &str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut result = String::new();
+    for (i, piece) in template.split("{}").enumerate() {
+        if i != 0 {
+            if let Some(arg) = args.next() {
+                result += &arg.to_string();
+            }
+        }
+        result += piece;
+    }
+    result
+}
+
+/// Static UI text in the site language, or in every language if the site is multi-language
+pub fn ui(lang: Option<usize>, text: &str) -> Box<span<String>> {
+    ui_args(lang, text, &[])
+}
+
+/// Like `ui`, with the {} placeholders of the translated text filled by `args`
+pub fn ui_args(lang: Option<usize>, template: &str, args: &[&dyn Display]) -> Box<span<String>> {
+    if let Some(language) = lang {
+        return html!(<span>{text!("{}", fill_template(translate_or_english(template, language), args))}</span>);
+    }
+    html!(<span> {
+        (0..32).filter(|&i|LANGUAGE_MAP[i].is_some()).map(|i|{
+            html! (<span class={format!("mh-lang-{}", i).as_str()}>
+                {text!("{}", fill_template(translate_or_english(template, i), args))}
+            </span>)
+        })
+    } </span>)
+}
+
+/// Static UI text for places that can't hold markup, such as titles and attributes
+pub fn ui_text(lang: Option<usize>, text: &str) -> String {
+    ui_text_args(lang, text, &[])
+}
+
+pub fn ui_text_args(lang: Option<usize>, template: &str, args: &[&dyn Display]) -> String {
+    fill_template(translate_or_english(template, lang.unwrap_or(1)), args)
+}
+
+pub fn enemy_level_text(level: EnemyLevel) -> &'static str {
+    match level {
+        EnemyLevel::Village => "Village",
+        EnemyLevel::Low => "Low rank",
+        EnemyLevel::High => "High rank",
+    }
+}
+
+pub fn quest_level_text(level: QuestLevel) -> &'static str {
+    match level {
+        QuestLevel::QL1 => "★1",
+        QuestLevel::QL2 => "★2",
+        QuestLevel::QL3 => "★3",
+        QuestLevel::QL4 => "★4",
+        QuestLevel::QL5 => "★5",
+        QuestLevel::QL6 => "★6",
+        QuestLevel::QL7 => "★7",
+        QuestLevel::QL7Ex => "★7 Ex",
+    }
+}
+
+pub fn gen_quest_tag(
+    lang: Option<usize>,
+    enemy_level: EnemyLevel,
+    quest_level: QuestLevel,
+) -> Box<span<String>> {
+    html!(<span class="tag">
+        {ui(lang, enemy_level_text(enemy_level))}
+        {text!(" {}", quest_level_text(quest_level))}
+    </span>)
+}
+
+// The same page on the site of another language
+pub struct AlternateLink {
+    pub hreflang: LanguageTag,
+    pub href: String,
+}
+
+/// The lang attribute of a page. Multi-language pages are tagged as English like their titles
+pub fn html_lang(lang: Option<usize>) -> LanguageTag {
+    LANGUAGE_CODES[lang.unwrap_or(1)]
+        .unwrap_or("en")
+        .parse()
+        .unwrap()
+}
+
+pub fn head_common(
+    lang: Option<usize>,
+    alternates: &[AlternateLink],
+) -> Vec<Box<dyn MetadataContent<String>>> {
+    let mut head: Vec<Box<dyn MetadataContent<String>>> = vec![
         html!(<meta charset="UTF-8" />),
         html!(<link rel="icon" type="image/png" href="/favicon.png" />),
         html!(<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/bulma@0.9.1/css/bulma.min.css" />),
@@ -59,10 +219,41 @@ pub fn head_common() -> Vec<Box<dyn MetadataContent<String>>> {
         html!(<link rel="stylesheet" href="/resources/item_color.css" />),
         html!(<script src="https://kit.fontawesome.com/ceb13a2ba1.js" crossorigin="anonymous" />),
         html!(<script src="/mhrice.js" crossorigin="anonymous" />),
-    ]
+    ];
+    if let Some(language) = lang {
+        head.push(html!(<meta name="mh-language" content={language.to_string().as_str()} />));
+    }
+    for alternate in alternates {
+        let hreflang = alternate.hreflang.clone();
+        let href = alternate.href.as_str();
+        head.push(html!(<link rel="alternate" hreflang=hreflang href=href />));
+    }
+    head
 }
 
-pub fn navbar() -> Box<div<String>> {
+pub fn navbar(lang: Option<usize>) -> Box<div<String>> {
+    let language_menu = if lang.is_none() {
+        Some(html!(<div class="navbar-item has-dropdown is-hoverable">
+            <a class="navbar-link">
+                {ui(lang, "Data language")}
+            </a>
+            <div class="navbar-dropdown">{
+                (0..32).filter_map(|i| {
+                    let language_name = LANGUAGE_MAP[i]?;
+                    let onclick = format!("selectLanguage({})", i);
+                    let class_string = format!("navbar-item mh-lang-menu-{}", i);
+                    let c: SpacedSet<Class> = class_string.as_str().try_into().unwrap();
+                    Some(html!{ <a class=c onclick=onclick> {
+                        text!("{}", language_name)
+                    }</a>: String})
+                })
+            }
+            </div>
+        </div>: String))
+    } else {
+        None
+    };
+    let search_placeholder = ui_text(lang, "Search");
     html!(<div>
         <nav class="navbar is-primary" role="navigation"> <div class="container">
             <div class="navbar-brand">
@@ -81,39 +272,45 @@ pub fn navbar() -> Box<div<String>> {
             <div id="navbarMenu" class="navbar-menu">
                 <div class="navbar-start">
                     <a class="navbar-item" href="/monster.html">
-                        "Monsters"
+                        {ui(lang, "Monsters")}
                     </a>
                     <a class="navbar-item" href="/quest.html">
-                        "Quests"
+                        {ui(lang, "Quests")}
+                    </a>
+                    <a class="navbar-item" href="/map.html">
+                        {ui(lang, "Maps")}
+                    </a>
+                    <a class="navbar-item" href="/habitat.html">
+                        {ui(lang, "Habitats")}
+                    </a>
+                    <a class="navbar-item" href="/crown.html">
+                        {ui(lang, "Crowns")}
                     </a>
                     <a class="navbar-item" href="/skill.html">
-                        "Skills"
+                        {ui(lang, "Skills")}
+                    </a>
+                    <a class="navbar-item" href="/hyakuryu_skill.html">
+                        {ui(lang, "Rampage skills")}
                     </a>
                     <a class="navbar-item" href="/armor.html">
-                        "Armors"
+                        {ui(lang, "Armors")}
+                    </a>
+                    <a class="navbar-item" href="/weapon.html">
+                        {ui(lang, "Weapons")}
                     </a>
                     <a class="navbar-item" href="/item.html">
-                        "Items"
+                        {ui(lang, "Items")}
                     </a>
                     <a class="navbar-item" href="/about.html">
-                        "About"
+                        {ui(lang, "About")}
                     </a>
-                    <div class="navbar-item has-dropdown is-hoverable">
-                        <a class="navbar-link">
-                            "Data language"
-                        </a>
-                        <div class="navbar-dropdown">{
-                            (0..32).filter_map(|i| {
-                                let language_name = LANGUAGE_MAP[i]?;
-                                let onclick = format!("selectLanguage({})", i);
-                                let class_string = format!("navbar-item mh-lang-menu-{}", i);
-                                let c: SpacedSet<Class> = class_string.as_str().try_into().unwrap();
-                                Some(html!{ <a class=c onclick=onclick> {
-                                    text!("{}", language_name)
-                                }</a>: String})
-                            })
-                        }
-                        </div>
+                    { language_menu }
+                </div>
+                <div class="navbar-end">
+                    <div class="navbar-item mh-search">
+                        <input id="mh-search-input" class="input" type="text"
+                            placeholder=search_placeholder oninput="onSearch()" />
+                        <div id="mh-search-result" class="mh-search-result" />
                     </div>
                 </div>
             </div>
@@ -121,7 +318,10 @@ pub fn navbar() -> Box<div<String>> {
     </div>: String)
 }
 
-pub fn gen_multi_lang(msg: &MsgEntry) -> Box<span<String>> {
+pub fn gen_multi_lang(lang: Option<usize>, msg: &MsgEntry) -> Box<span<String>> {
+    if let Some(language) = lang {
+        return html!(<span>{text!("{}", msg.content[language])}</span>);
+    }
     html!(<span> {
         (0..32).filter(|&i|LANGUAGE_MAP[i].is_some()).map(|i|{
             html! (<span class={format!("mh-lang-{}", i).as_str()}>
@@ -146,21 +346,28 @@ pub fn gen_colored_icon(color: i32, icon: &str, addons: &[&str]) -> Box<div<Stri
     </div>)
 }
 
-pub fn gen_monsters(pedia: &Pedia, pedia_ex: &PediaEx<'_>, root: &Path) -> Result<()> {
+pub fn gen_monsters(
+    lang: Option<usize>,
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
     let monsters_path = root.join("monster.html");
+    let alternates = manifest.alternate_links(&monsters_path)?;
 
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Monsters - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "Monsters"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"Monsters"</h1>
+                <h1 class="title">{ui(lang, "Monsters")}</h1>
                 <section class="section">
-                <h2 class="title">"Large monsters"</h2>
+                <h2 class="title">{ui(lang, "Large monsters")}</h2>
                 <ul class="mh-list-monster">{
                     pedia.monsters.iter().filter_map(|monster| {
                         let icon_path = format!("/resources/em{0:03}_{1:02}_icon.png", monster.id, monster.sub_id);
@@ -170,14 +377,14 @@ pub fn gen_monsters(pedia: &Pedia, pedia_ex: &PediaEx<'_>, root: &Path) -> Resul
                         Some(html!{<li class="mh-list-monster">
                             <a href={format!("/monster/{:03}_{:02}.html", monster.id, monster.sub_id)}>
                                 <img class="mh-list-monster-icon" src=icon_path />
-                                <div>{gen_multi_lang(name_entry)}</div>
+                                <div>{gen_multi_lang(lang, name_entry)}</div>
                             </a>
                         </li>})
                     }).collect::<Vec<_>>()
                 }</ul>
                 </section>
                 <section class="section">
-                <h2 class="title">"Small monsters"</h2>
+                <h2 class="title">{ui(lang, "Small monsters")}</h2>
                 <ul class="mh-list-monster">{
                     pedia.small_monsters.iter().filter(|monster|monster.sub_id == 0) // sub small monsters are b0rked
                     .map(|monster| {
@@ -186,7 +393,8 @@ pub fn gen_monsters(pedia: &Pedia, pedia_ex: &PediaEx<'_>, root: &Path) -> Resul
                             <a href={format!("/small-monster/{:03}_{:02}.html", monster.id, monster.sub_id)}>
                                 <img class="mh-list-monster-icon" src=icon_path />
                                 <div>{
-                                    text!("Small monster {:03}_{:02}", monster.id, monster.sub_id)
+                                    ui_args(lang, "Small monster {}",
+                                        &[&format!("{:03}_{:02}", monster.id, monster.sub_id)])
                                 }</div>
                             </a>
                         </li>}
@@ -198,51 +406,69 @@ pub fn gen_monsters(pedia: &Pedia, pedia_ex: &PediaEx<'_>, root: &Path) -> Resul
         </html>
     );
 
-    write(&monsters_path, doc.to_string())?;
-
-    let monster_path = root.join("monster");
-    create_dir(&monster_path)?;
-    for monster in &pedia.monsters {
-        gen_monster(true, monster, pedia, pedia_ex, &monster_path)?;
-    }
+    manifest.write(&monsters_path, doc.to_string())?;
 
-    let monster_path = root.join("small-monster");
-    create_dir(&monster_path)?;
-    for monster in &pedia.small_monsters {
-        gen_monster(false, monster, pedia, pedia_ex, &monster_path)?;
+    for &(is_large, folder, monsters) in &[
+        (true, "monster", &pedia.monsters),
+        (false, "small-monster", &pedia.small_monsters),
+    ] {
+        let monster_path = root.join(folder);
+        create_dir_all(&monster_path)?;
+        for monster in monsters {
+            let path = monster_path.join(format!("{:03}_{:02}.html", monster.id, monster.sub_id));
+            let alternates = manifest.alternate_links(&path)?;
+            manifest.page(&path, &(is_large, monster), || {
+                gen_monster(lang, &alternates, is_large, monster, pedia, pedia_ex)
+            })?;
+        }
     }
     Ok(())
 }
 
-pub fn gen_about(root: &Path) -> Result<()> {
+pub fn gen_about(
+    lang: Option<usize>,
+    has_patch_notes: bool,
+    root: &Path,
+    manifest: &mut Manifest,
+) -> Result<()> {
+    let alternates = manifest.alternate_links(&root.join("about.html"))?;
+    let api_index = api_index_url();
     let doc: DOMTree<String> = html!(
-        <html>
+        <html lang={html_lang(lang)}>
             <head>
-                <title>{text!("Monsters - MHRice")}</title>
-                { head_common() }
+                <title>{text!("{} - MHRice", ui_text(lang, "About"))}</title>
+                { head_common(lang, &alternates) }
             </head>
             <body>
-                { navbar() }
+                { navbar(lang) }
                 <main> <div class="container"> <div class="content">
-                <h1 class="title">"About MHRice"</h1>
+                <h1 class="title">{ui(lang, "About MHRice")}</h1>
+                <p>
+                {ui(lang, "MHRice is an information site for Monster Hunter Rise, displaying data extracted from the game.")}
+                </p>
                 <p>
-                "MHRice is an information site for Monster Hunter Rise, displaying data extracted from the game."
+                {ui(lang, "MHRice website is generated from the open source MHRice project.")}
                 </p>
                 <p>
-                "MHRice website is generated from the open source MHRice project."
+                {ui(lang, "The data is also available as JSON files, starting from ")}
+                <a href={api_index.as_str()}>{text!("{}", api_index)}</a>
+                "."
                 </p>
                 <p>
                 <a class="button" href="https://github.com/wwylele/mhrice" target="_blank" rel=["noopener", "noreferrer"]>
                     <span class="icon">
                         <i class="fab fa-github"></i>
                     </span>
-                    <span>"Visit MHRice on Github"</span>
+                    {ui(lang, "Visit MHRice on Github")}
                 </a>
                 </p>
+                { has_patch_notes.then(|| html!(<p>
+                    <a href="/patch_notes.html">{ui(lang, "Patch notes")}</a>
+                </p>)) }
                 <section class="section">
-                <h2 class="title">"Build information"</h2>
+                <h2 class="title">{ui(lang, "Build information")}</h2>
                 <ul>
-                    <li>"Git hash: " <span class="is-family-monospace">{
+                    <li>{ui(lang, "Git hash: ")} <span class="is-family-monospace">{
                         text!("{}{}",
                             crate::built_info::GIT_COMMIT_HASH.unwrap_or("unknown"),
                             if crate::built_info::GIT_DIRTY == Some(true) {
@@ -252,7 +478,7 @@ pub fn gen_about(root: &Path) -> Result<()> {
                             }
                         )
                     }</span></li>
-                    <li>{text!("Update time: {}", Utc::now())}</li>
+                    <li>{ui_args(lang, "Update time: {}", &[&Utc::now()])}</li>
                 </ul>
                 </section>
                 </div> </div> </main>
@@ -261,55 +487,77 @@ pub fn gen_about(root: &Path) -> Result<()> {
     );
 
     let about_path = root.join("about.html");
-    write(&about_path, doc.to_string())?;
+    manifest.write(&about_path, doc.to_string())?;
 
     Ok(())
 }
 
-pub fn gen_static(root: &Path) -> Result<()> {
-    write(
-        root.to_path_buf().join("mhrice.css"),
-        include_bytes!("static/mhrice.css"),
+pub fn gen_static(root: &Path, manifest: &mut Manifest) -> Result<()> {
+    manifest.write(
+        &root.join("mhrice.css"),
+        &include_bytes!("static/mhrice.css")[..],
     )?;
-    write(
-        root.to_path_buf().join("mhrice.js"),
-        include_bytes!("static/mhrice.js"),
+    manifest.write(
+        &root.join("mhrice.js"),
+        &include_bytes!("static/mhrice.js")[..],
     )?;
-    write(
-        root.to_path_buf().join("favicon.png"),
-        include_bytes!("static/favicon.png"),
+    manifest.write(
+        &root.join("favicon.png"),
+        &include_bytes!("static/favicon.png")[..],
     )?;
     Ok(())
 }
 
-pub fn gen_part_color_css(root: &Path) -> Result<()> {
-    let mut file = File::create(root.to_path_buf().join("part_color.css"))?;
+pub fn gen_part_color_css(root: &Path, manifest: &mut Manifest) -> Result<()> {
+    let mut css = String::new();
 
     for (i, color) in PART_COLORS.iter().enumerate() {
-        writeln!(file, ".mh-part-{} {{color: {}}}", i, color)?;
+        writeln!(css, ".mh-part-{} {{color: {}}}", i, color)?;
     }
 
-    Ok(())
+    manifest.write(&root.join("part_color.css"), css)
 }
 
-pub fn gen_website(pedia: &Pedia, pedia_ex: &PediaEx<'_>, output: &str) -> Result<()> {
+pub fn gen_website(
+    pedia: &Pedia,
+    pedia_ex: &PediaEx<'_>,
+    resources: &Resources,
+    output: &str,
+    options: &SiteOptions,
+    patch_notes: Option<&PediaDiff>,
+) -> Result<()> {
     let root = PathBuf::from(output);
-    if root.exists() {
-        remove_dir_all(&root)?;
+    create_dir_all(&root)?;
+    let mut manifest = Manifest::load(&root, options)?;
+    let lang = options.language;
+
+    gen_quests(lang, pedia, pedia_ex, &root, &mut manifest)?;
+    gen_skills(lang, &pedia_ex.skills, &root, &mut manifest)?;
+    gen_skill_list(lang, &pedia_ex.skills, &root, &mut manifest)?;
+    gen_hyakuryu_skill_list(lang, pedia_ex, &root, &mut manifest)?;
+    gen_armors(
+        lang,
+        pedia_ex,
+        &resources.armor_previews,
+        &root,
+        &mut manifest,
+    )?;
+    gen_armor_list(lang, &pedia_ex.armors, &root, &mut manifest)?;
+    gen_weapon_list(lang, pedia_ex, &root, &mut manifest)?;
+    gen_monsters(lang, pedia, pedia_ex, &root, &mut manifest)?;
+    gen_quest_list(lang, &pedia_ex.quests, &root, &mut manifest)?;
+    gen_items(lang, pedia_ex, &root, &mut manifest)?;
+    gen_item_list(lang, pedia_ex, &root, &mut manifest)?;
+    gen_crowns(lang, pedia, pedia_ex, &root, &mut manifest)?;
+    gen_maps(lang, pedia, pedia_ex, &root, &mut manifest)?;
+    gen_habitats(lang, pedia, pedia_ex, &root, &mut manifest)?;
+    gen_search_index(pedia, pedia_ex, &root, &mut manifest)?;
+    gen_api(pedia, pedia_ex, &root, &mut manifest)?;
+    if let Some(diff) = patch_notes {
+        gen_patch_notes(lang, diff, &root.join("patch_notes.html"), &mut manifest)?;
     }
-    create_dir(&root)?;
-
-    gen_quests(pedia, pedia_ex, &root)?;
-    gen_skills(&pedia_ex.skills, &root)?;
-    gen_skill_list(&pedia_ex.skills, &root)?;
-    gen_armors(pedia_ex, &root)?;
-    gen_armor_list(&pedia_ex.armors, &root)?;
-    gen_monsters(pedia, pedia_ex, &root)?;
-    gen_quest_list(&pedia_ex.quests, &root)?;
-    gen_items(pedia_ex, &root)?;
-    gen_item_list(pedia_ex, &root)?;
-    gen_about(&root)?;
-    gen_static(&root)?;
-    gen_part_color_css(&root)?;
-    Ok(())
+    gen_about(lang, patch_notes.is_some(), &root, &mut manifest)?;
+    gen_static(&root, &mut manifest)?;
+    gen_part_color_css(&root, &mut manifest)?;
+    manifest.finish()
 }
@@ -0,0 +1,161 @@
+use super::gen_website::{AlternateLink, SiteOptions, LANGUAGE_CODES};
+use anyhow::*;
+use serde::*;
+use std::collections::BTreeMap;
+use std::fs::{read, remove_file, write};
+use std::path::*;
+
+// Bump this whenever page templates change so that all pages are regenerated
+const TEMPLATE_VERSION: u32 = 10;
+
+const MANIFEST_NAME: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Default)]
+struct ManifestData {
+    #[serde(default)]
+    template_version: u32,
+    // Language settings of the site. Files are regenerated if this changes
+    #[serde(default)]
+    site: String,
+    // Hash of the source data of every page, or of the content for other files
+    pages: BTreeMap<String, String>,
+}
+
+pub struct Manifest {
+    root: PathBuf,
+    old: ManifestData,
+    site: String,
+    alternates: Vec<(usize, String)>,
+    pages: BTreeMap<String, String>,
+    written: usize,
+    unchanged: usize,
+}
+
+fn hash_source<T: Serialize + ?Sized>(source: &T) -> Result<String> {
+    let data = serde_json::to_vec(source)?;
+    hash_contents(&data)
+}
+
+fn hash_contents(contents: &[u8]) -> Result<String> {
+    let hash = murmur3::murmur3_x64_128(&mut &contents[..], TEMPLATE_VERSION)?;
+    Ok(format!("{:032x}", hash))
+}
+
+impl Manifest {
+    pub fn load(root: &Path, options: &SiteOptions) -> Result<Manifest> {
+        let path = root.join(MANIFEST_NAME);
+        let old = if path.exists() {
+            serde_json::from_slice(&read(&path)?).context("Failed to read website manifest")?
+        } else {
+            ManifestData::default()
+        };
+        Ok(Manifest {
+            root: root.to_owned(),
+            old,
+            site: serde_json::to_string(options)?,
+            alternates: options.alternates.clone(),
+            pages: BTreeMap::new(),
+            written: 0,
+            unchanged: 0,
+        })
+    }
+
+    fn key(&self, path: &Path) -> Result<String> {
+        Ok(path
+            .strip_prefix(&self.root)
+            .context("Page outside of the website root")?
+            .to_str()
+            .context("Non UTF-8 page path")?
+            .replace('\\', "/"))
+    }
+
+    /// Links to the same page on the sites of the other languages
+    pub fn alternate_links(&self, path: &Path) -> Result<Vec<AlternateLink>> {
+        let key = self.key(path)?;
+        self.alternates
+            .iter()
+            .map(|(language, url)| {
+                let code = LANGUAGE_CODES[*language].context("Unsupported alternate language")?;
+                Ok(AlternateLink {
+                    hreflang: code
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid language code {}", code))?,
+                    href: format!("{}/{}", url.trim_end_matches('/'), key),
+                })
+            })
+            .collect()
+    }
+
+    fn record(
+        &mut self,
+        path: &Path,
+        hash: String,
+        gen: impl FnOnce() -> Result<Vec<u8>>,
+    ) -> Result<()> {
+        let key = self.key(path)?;
+
+        if self.old.template_version == TEMPLATE_VERSION
+            && self.old.site == self.site
+            && self.old.pages.get(&key) == Some(&hash)
+            && path.exists()
+        {
+            self.unchanged += 1;
+        } else {
+            write(path, gen()?)?;
+            self.written += 1;
+        }
+
+        if self.pages.insert(key, hash).is_some() {
+            bail!("Page {} is generated more than once", path.display())
+        }
+        Ok(())
+    }
+
+    /// Generates a page only if its source data, the templates or the site settings
+    /// changed since the last run.
+    pub fn page<T: Serialize + ?Sized>(
+        &mut self,
+        path: &Path,
+        source: &T,
+        gen: impl FnOnce() -> Result<String>,
+    ) -> Result<()> {
+        let hash = hash_source(source)?;
+        self.record(path, hash, || Ok(gen()?.into_bytes()))
+    }
+
+    /// Writes a file that has no single source struct, such as lists and static files,
+    /// skipping it if the content is the same as in the last run.
+    /// Every file of the website goes through here or page() so that stale ones can be removed.
+    pub fn write(&mut self, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+        let contents = contents.as_ref();
+        let hash = hash_contents(contents)?;
+        self.record(path, hash, || Ok(contents.to_vec()))
+    }
+
+    pub fn finish(self) -> Result<()> {
+        let mut removed = 0;
+        for key in self.old.pages.keys() {
+            if self.pages.contains_key(key) {
+                continue;
+            }
+            let path = self.root.join(key);
+            if path.exists() {
+                remove_file(&path)?;
+                removed += 1;
+            }
+        }
+
+        println!(
+            "Files: {} written, {} unchanged, {} removed",
+            self.written, self.unchanged, removed
+        );
+
+        let data = ManifestData {
+            template_version: TEMPLATE_VERSION,
+            site: self.site,
+            pages: self.pages,
+        };
+        write(self.root.join(MANIFEST_NAME), serde_json::to_string(&data)?)?;
+        Ok(())
+    }
+}
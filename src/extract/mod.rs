@@ -1,13 +1,30 @@
+mod behavior;
+mod crown;
+mod diff;
+mod gen_api;
 mod gen_armor;
+mod gen_crown;
+mod gen_diff;
+mod gen_habitat;
 mod gen_item;
+mod gen_map;
 mod gen_monster;
 mod gen_pedia;
 mod gen_quest;
+mod gen_search;
 mod gen_skill;
+mod gen_weapon;
 mod gen_website;
+mod manifest;
+mod part_break;
 mod pedia;
+mod ui_text;
 
-pub use gen_pedia::gen_resources;
+pub use behavior::monster_behavior;
+pub use crown::{crown_rankings, sort_crown_ranking, CrownKind};
+pub use diff::{gen_diff, PediaDiff};
 pub use gen_pedia::{gen_pedia, gen_pedia_ex};
-pub use gen_website::gen_website;
+pub use gen_pedia::{gen_resources, Resources};
+pub use gen_website::{gen_website, language_from_code, SiteOptions};
+pub use part_break::break_plan;
 pub use pedia::*;
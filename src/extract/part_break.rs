@@ -0,0 +1,265 @@
+use super::pedia::*;
+use crate::msg::*;
+use crate::rsz::*;
+use anyhow::*;
+use serde::*;
+use std::convert::TryFrom;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum DamageRequirement {
+    Any,
+    Slash,
+    Impact,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BreakStage {
+    pub break_level: i32,
+    pub hp: f32,
+    /// Break HP as a percentage of the monster HP
+    pub hp_share: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SeverInfo {
+    pub hp: f32,
+    pub hp_share: f32,
+    pub damage: DamageRequirement,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartRewardItem {
+    pub rank: QuestRank,
+    pub item: ItemId,
+    pub count: u32,
+    pub probability: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartRewardCondition {
+    pub broken_parts_type: BrokenPartsTypes,
+    pub name: Option<MsgEntry>,
+    pub break_level: u16,
+    /// Whether all listed parts need to be broken, rather than any of them
+    pub requires_all: bool,
+    pub items: Vec<PartRewardItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartBreakPlan<'a> {
+    pub index: usize,
+    pub colliders: Vec<String>,
+    /// Names of the reward part types whose conditions refer to this part
+    pub names: Vec<MsgEntry>,
+    pub stagger_hp: f32,
+    pub breaks: Vec<BreakStage>,
+    pub sever: Option<SeverInfo>,
+    /// Conditions under which the part breaks regardless of its HP
+    pub break_conditions: Vec<&'a ConditionPartsBreakData>,
+    /// Conditions under which the part is severed regardless of its HP
+    pub sever_conditions: Vec<&'a ConditionPartsLossData>,
+    pub rewards: Vec<PartRewardCondition>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MultiPartGroup {
+    pub parts: Vec<String>,
+    pub hp: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonsterBreakPlan<'a> {
+    pub id: u32,
+    pub sub_id: u32,
+    pub total_hp: f32,
+    pub parts_vital_rate: f32,
+    pub parts: Vec<PartBreakPlan<'a>>,
+    pub multi_part_groups: Vec<MultiPartGroup>,
+}
+
+fn reward_items(
+    pedia_ex: &PediaEx<'_>,
+    em_type: EmTypes,
+    ranks: &[QuestRank],
+    part: BrokenPartsTypes,
+) -> Vec<PartRewardItem> {
+    let mut items = vec![];
+    for &rank in ranks {
+        let lot = if let Some(lot) = pedia_ex.monster_lot.get(&(em_type, rank)) {
+            lot
+        } else {
+            continue;
+        };
+        let rewards = lot
+            .parts_break_list
+            .iter()
+            .zip(lot.parts_break_reward_item_id_list.chunks(10))
+            .zip(lot.parts_break_reward_num_list.chunks(10))
+            .zip(lot.parts_break_reward_probability_list.chunks(10))
+            .filter(|&(((&p, _), _), _)| p == part)
+            .flat_map(|(((_, item), num), probability)| item.iter().zip(num).zip(probability))
+            .filter(|&((&item, _), _)| item != ItemId::None)
+            .map(|((&item, &count), &probability)| PartRewardItem {
+                rank,
+                item,
+                count,
+                probability,
+            });
+        items.extend(rewards);
+    }
+    items
+}
+
+/// Lists breakable and severable parts with their HP scaled by the quest rates.
+/// Pass 1.0 for both rates to get the base values. Rewards are listed for the given
+/// rank, or for both ranks if none is given.
+pub fn break_plan<'a>(
+    monster: &'a Monster,
+    pedia_ex: &PediaEx<'_>,
+    vital_rate: f32,
+    parts_vital_rate: f32,
+    rank: Option<QuestRank>,
+) -> Result<MonsterBreakPlan<'a>> {
+    let em_type = EmTypes::Em(monster.id | monster.sub_id << 8);
+    let ranks = match rank {
+        Some(rank) => vec![rank],
+        None => vec![QuestRank::Low, QuestRank::High],
+    };
+    let tune = &monster.data_tune;
+    let total_hp = tune.base_hp_vital as f32 * vital_rate;
+    let share = |hp: f32| {
+        if total_hp > 0.0 {
+            hp * 100.0 / total_hp
+        } else {
+            0.0
+        }
+    };
+
+    let mut parts = vec![];
+    for (index, part) in tune.enemy_parts_data.iter().enumerate() {
+        let group = u16::try_from(index)?;
+
+        let breaks: Vec<BreakStage> = tune
+            .enemy_parts_break_data_list
+            .iter()
+            .filter(|p| p.parts_group == group)
+            .flat_map(|p| &p.parts_break_data_list)
+            .map(|p| {
+                let hp = p.vital as f32 * parts_vital_rate;
+                BreakStage {
+                    break_level: p.break_level,
+                    hp,
+                    hp_share: share(hp),
+                }
+            })
+            .collect();
+
+        let sever = tune
+            .enemy_parts_loss_data_list
+            .iter()
+            .find(|p| p.parts_group == group)
+            .map(|p| {
+                let hp = p.parts_loss_data.vital as f32 * parts_vital_rate;
+                let damage = match p.parts_loss_data.permit_damage_attr {
+                    PermitDamageAttrEnum::Slash => DamageRequirement::Slash,
+                    PermitDamageAttrEnum::Strike => DamageRequirement::Impact,
+                    PermitDamageAttrEnum::All => DamageRequirement::Any,
+                };
+                SeverInfo {
+                    hp,
+                    hp_share: share(hp),
+                    damage,
+                }
+            });
+
+        if breaks.is_empty() && sever.is_none() {
+            continue;
+        }
+
+        let break_conditions = monster
+            .parts_break_data
+            .parts_break_group_data_list
+            .iter()
+            .filter(|p| p.parts_group == group)
+            .flat_map(|p| &p.condition_parts_break_data_list)
+            .collect();
+
+        let sever_conditions = monster
+            .parts_break_data
+            .parts_loss_group_data_list
+            .iter()
+            .filter(|p| p.parts_group == group)
+            .flat_map(|p| &p.condition_parts_loss_data_list)
+            .collect();
+
+        let rewards: Vec<PartRewardCondition> = monster
+            .parts_break_reward
+            .iter()
+            .flat_map(|data| &data.enemy_parts_break_reward_infos)
+            .flat_map(|info| {
+                info.parts_break_condition_list
+                    .iter()
+                    .filter(|cond| cond.parts_group == group)
+                    .map(move |cond| (info, cond))
+            })
+            .map(|(info, cond)| PartRewardCondition {
+                broken_parts_type: info.broken_parts_type,
+                name: pedia_ex
+                    .parts_dictionary
+                    .get(&(em_type, info.broken_parts_type))
+                    .cloned(),
+                break_level: cond.parts_break_level,
+                requires_all: matches!(
+                    info.condition_type,
+                    EnemyPartsBreakRewardDataConditionType::All
+                ),
+                items: reward_items(pedia_ex, em_type, &ranks, info.broken_parts_type),
+            })
+            .collect();
+
+        let mut names: Vec<MsgEntry> = vec![];
+        for name in rewards.iter().filter_map(|reward| reward.name.as_ref()) {
+            if !names.iter().any(|n| n.guid == name.guid) {
+                names.push(name.clone());
+            }
+        }
+
+        parts.push(PartBreakPlan {
+            index,
+            colliders: monster
+                .collider_mapping
+                .part_map
+                .get(&index)
+                .map_or_else(Vec::new, |names| names.iter().cloned().collect()),
+            names,
+            stagger_hp: part.vital as f32 * parts_vital_rate,
+            breaks,
+            sever,
+            break_conditions,
+            sever_conditions,
+            rewards,
+        });
+    }
+
+    let multi_part_groups = tune
+        .enemy_multi_parts_vital_data_list
+        .iter()
+        .map(|data| MultiPartGroup {
+            parts: data.enable_parts_names.clone(),
+            hp: data
+                .multi_parts_vital_data
+                .iter()
+                .map(|v| v.vital as f32 * parts_vital_rate)
+                .collect(),
+        })
+        .collect();
+
+    Ok(MonsterBreakPlan {
+        id: monster.id,
+        sub_id: monster.sub_id,
+        total_hp,
+        parts_vital_rate,
+        parts,
+        multi_part_groups,
+    })
+}
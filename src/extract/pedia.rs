@@ -46,6 +46,10 @@ pub struct Pedia {
     pub random_scale: EnemyBossRandomScaleData,
     pub size_list: EnemySizeListData,
     pub discover_em_set_data: DiscoverEmSetData,
+    pub supply_data: Option<SupplyData>,
+    pub reward_id_lot_table: Option<RewardIdLotTableUserData>,
+    pub quest_data_for_reward: Option<QuestDataForRewardUserData>,
+    pub hyakuryu_quest_data: HyakuryuQuestDataTbl,
     pub quest_hall_msg: Msg,
     pub quest_village_msg: Msg,
     pub quest_tutorial_msg: Msg,
@@ -70,6 +74,8 @@ pub struct Pedia {
 
     pub hyakuryu_skill: PlHyakuryuSkillBaseUserData,
     pub hyakuryu_skill_recipe: PlHyakuryuSkillRecipeUserData,
+    pub hyakuryu_skill_name_msg: Msg,
+    pub hyakuryu_skill_explain_msg: Msg,
 
     pub alchemy_pattern: AlchemyPatturnUserData,
     pub alchemy_pl_skill: AlchemyPlSkillTableUserData,
@@ -83,16 +89,112 @@ pub struct Pedia {
     pub items: ItemUserData,
     pub items_name_msg: Msg,
     pub material_category_msg: Msg,
+
+    pub stage_name_msg: Option<Msg>,
+    pub stages: BTreeMap<i32, Stage>,
+
+    pub great_sword: GreatSwordBaseUserData,
+    pub great_sword_name_msg: Msg,
+    pub short_sword: ShortSwordBaseUserData,
+    pub short_sword_name_msg: Msg,
+    pub dual_blades: DualBladesBaseUserData,
+    pub dual_blades_name_msg: Msg,
+    pub long_sword: LongSwordBaseUserData,
+    pub long_sword_name_msg: Msg,
+    pub hammer: HammerBaseUserData,
+    pub hammer_name_msg: Msg,
+    pub horn: HornBaseUserData,
+    pub horn_name_msg: Msg,
+    pub lance: LanceBaseUserData,
+    pub lance_name_msg: Msg,
+    pub gun_lance: GunLanceBaseUserData,
+    pub gun_lance_name_msg: Msg,
+    pub slash_axe: SlashAxeBaseUserData,
+    pub slash_axe_name_msg: Msg,
+    pub charge_axe: ChargeAxeBaseUserData,
+    pub charge_axe_name_msg: Msg,
+    pub insect_glaive: InsectGlaiveBaseUserData,
+    pub insect_glaive_name_msg: Msg,
+    pub bow: BowBaseUserData,
+    pub bow_name_msg: Msg,
+    pub light_bowgun: LightBowgunBaseUserData,
+    pub light_bowgun_name_msg: Msg,
+    pub heavy_bowgun: HeavyBowgunBaseUserData,
+    pub heavy_bowgun_name_msg: Msg,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct SpawnPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stage {
+    pub map_no: i32,
+    /// World positions of the boss init set markers, keyed by set name
+    pub spawn_points: BTreeMap<String, SpawnPoint>,
+}
+
+/// Stats for a solo hunt. Multiplayer factors are left out, as the column order of
+/// MultiRateTableData is not known
+#[derive(Debug, Serialize)]
+pub struct SoloStats {
+    pub hp: f32,
+    pub attack_rate: f32,
+    pub parts_vital: Vec<f32>,
+    pub defense_rate: f32,
+    pub element_a_rate: f32,
+    pub element_b_rate: f32,
+    pub stun_rate: f32,
+    pub exhaust_rate: f32,
+    pub ride_rate: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusBuildup {
+    pub status: &'static str,
+    pub default_limit: f32,
+    pub add_limit: f32,
+    pub max_limit: f32,
+    /// The quest's other rate table entry, for the statuses it covers
+    pub quest_rate: Option<f32>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct QuestMonsterStats {
+    pub index: usize,
+    pub em_type: EmTypes,
+    pub base_hp: i32,
+    pub vital_rate: f32,
+    pub parts_vital_rate: f32,
+    pub buildups: Vec<StatusBuildup>,
+    pub solo: SoloStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestReward {
+    pub param: QuestDataForRewardUserDataParam,
+    pub additional_target_reward: Option<RewardIdLotTableUserDataParam>,
+    pub common_material_reward: Option<RewardIdLotTableUserDataParam>,
+    pub additional_quest_reward: Vec<RewardIdLotTableUserDataParam>,
+}
+
+#[derive(Serialize)]
 pub struct Quest {
     pub param: NormalQuestDataParam,
     pub enemy_param: Option<NormalQuestDataForEnemyParam>,
     pub name: Option<MsgEntry>,
     pub target: Option<MsgEntry>,
     pub condition: Option<MsgEntry>,
+    pub monster_stats: Vec<QuestMonsterStats>,
+    pub supply: Option<SupplyDataParam>,
+    pub reward: Option<QuestReward>,
+    pub hyakuryu: Option<HyakuryuQuestData>,
 }
 
+#[derive(Serialize)]
 pub struct Skill {
     pub name: MsgEntry,
     pub explain: MsgEntry,
@@ -100,6 +202,25 @@ pub struct Skill {
     pub icon_color: i32,
 }
 
+#[derive(Serialize)]
+pub struct HyakuryuSkill<'a> {
+    pub name: Option<MsgEntry>,
+    pub explain: Option<MsgEntry>,
+    pub data: &'a PlHyakuryuSkillBaseUserDataParam,
+    pub recipe: Option<&'a PlHyakuryuSkillRecipeUserDataParam>,
+    pub weapons: Vec<Weapon<'a>>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct Weapon<'a> {
+    pub weapon_type: &'static str,
+    pub name: Option<&'a MsgEntry>,
+    pub main: &'a MainWeaponBaseData,
+    pub close_range: Option<&'a CloseRangeWeaponBaseData>,
+    pub elements: Vec<(i32, i32)>,
+}
+
+#[derive(Serialize)]
 pub struct Armor<'a> {
     pub name: MsgEntry,
     pub data: &'a ArmorBaseUserDataParam,
@@ -108,6 +229,7 @@ pub struct Armor<'a> {
     pub overwear_product: Option<&'a PlOverwearProductUserDataParam>,
 }
 
+#[derive(Serialize)]
 pub struct ArmorSeries<'a> {
     pub name: Option<MsgEntry>,
     pub series: &'a ArmorSeriesUserDataParam,
@@ -121,6 +243,7 @@ pub struct MeatKey {
     pub phase: usize,
 }
 
+#[derive(Serialize)]
 pub struct Item<'a> {
     pub name: MsgEntry,
     pub param: &'a ItemUserDataParam,
@@ -132,11 +255,15 @@ pub struct PediaEx<'a> {
     pub size_dists: HashMap<i32, &'a [ScaleAndRateData]>,
     pub quests: Vec<Quest>,
     pub discoveries: HashMap<EmTypes, &'a DiscoverEmSetDataParam>,
+    pub tour_maps: HashMap<EmTypes, Vec<i32>>,
+    pub habitat_maps: BTreeMap<usize, i32>,
     pub skills: BTreeMap<PlEquipSkillId, Skill>,
+    pub hyakuryu_skills: BTreeMap<u32, HyakuryuSkill<'a>>,
     pub armors: Vec<ArmorSeries<'a>>,
     pub meat_names: HashMap<MeatKey, MsgEntry>,
     pub items: BTreeMap<ItemId, Item<'a>>,
     pub material_categories: HashMap<MaterialCategory, MsgEntry>,
     pub monster_lot: HashMap<(EmTypes, QuestRank), &'a MonsterLotTableUserDataParam>,
     pub parts_dictionary: HashMap<(EmTypes, BrokenPartsTypes), MsgEntry>,
+    pub weapons: Vec<(&'static str, Vec<Weapon<'a>>)>,
 }
@@ -5,6 +5,10 @@ var navbar_menu_active = false;
 var classes_to_hide = new Set();
 
 window.onload = function () {
+    var site_language = document.querySelector("meta[name='mh-language']");
+    if (site_language !== null) {
+        language_index = parseInt(site_language.content);
+    }
     switchLanguage();
     hide_class("mh-ride-cond");
     hide_class("mh-invalid-meat");
@@ -43,6 +47,7 @@ function show_class(c) {
 function selectLanguage(language) {
     language_index = language;
     switchLanguage();
+    onSearch();
 }
 
 function switchLanguage() {
@@ -89,3 +94,54 @@ function onToggleNavbarMenu() {
         document.getElementById("navbarMenu").classList.remove("is-active");
     }
 }
+
+var search_indexes = {};
+
+function loadSearchIndex(language) {
+    if (!(language in search_indexes)) {
+        search_indexes[language] = fetch("/search/" + language + ".json")
+            .then(response => response.json());
+    }
+    return search_indexes[language];
+}
+
+function onSearch() {
+    var query = document.getElementById("mh-search-input").value.trim().toLowerCase();
+    var result = document.getElementById("mh-search-result");
+    if (query === "") {
+        result.replaceChildren();
+        return;
+    }
+
+    var language = language_index;
+    loadSearchIndex(language).then(entries => {
+        if (document.getElementById("mh-search-input").value.trim().toLowerCase() !== query
+            || language !== language_index) {
+            return;
+        }
+
+        var items = [];
+        for (let entry of entries) {
+            if (entry.name.toLowerCase().includes(query)) {
+                var item = document.createElement("a");
+                item.href = entry.url;
+                var kind = document.createElement("span");
+                kind.classList.add("mh-search-kind");
+                kind.textContent = entry.kind;
+                item.appendChild(kind);
+                if (entry.icon) {
+                    var icon = document.createElement("img");
+                    icon.src = entry.icon;
+                    icon.classList.add("mh-search-icon");
+                    item.appendChild(icon);
+                }
+                item.appendChild(document.createTextNode(entry.name));
+                items.push(item);
+                if (items.length >= 20) {
+                    break;
+                }
+            }
+        }
+        result.replaceChildren(...items);
+    });
+}
@@ -0,0 +1,472 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+// Static UI text, keyed by the English text.
+// Columns are English, Japanese, Traditional Chinese and Simplified Chinese.
+// Other languages fall back to English.
+const CATALOG: &[[&str; 4]] = &[
+    // Navigation
+    ["Monsters", "モンスター", "魔物", "怪物"],
+    ["Quests", "クエスト", "任務", "任务"],
+    ["Maps", "マップ", "地圖", "地图"],
+    ["Habitats", "生息地", "棲息地", "栖息地"],
+    ["Crowns", "王冠", "王冠", "王冠"],
+    ["Skills", "スキル", "技能", "技能"],
+    ["Rampage skills", "百竜スキル", "百龍技能", "百龙技能"],
+    ["Armors", "防具", "防具", "防具"],
+    ["Weapons", "武器", "武器", "武器"],
+    ["Items", "アイテム", "道具", "道具"],
+    ["About", "このサイトについて", "關於", "关于"],
+    ["Data language", "データの言語", "資料語言", "数据语言"],
+    ["Search", "検索", "搜尋", "搜索"],
+    // Common
+    ["Large monsters", "大型モンスター", "大型魔物", "大型怪物"],
+    ["Small monsters", "小型モンスター", "小型魔物", "小型怪物"],
+    ["Name", "名前", "名稱", "名称"],
+    ["Monster", "モンスター", "魔物", "怪物"],
+    ["Quest", "クエスト", "任務", "任务"],
+    ["Item", "アイテム", "道具", "道具"],
+    ["Skill", "スキル", "技能", "技能"],
+    ["Armor", "防具", "防具", "防具"],
+    ["Type", "種類", "種類", "种类"],
+    ["Level", "レベル", "等級", "等级"],
+    ["Probability", "確率", "機率", "概率"],
+    ["Description", "説明", "說明", "说明"],
+    ["Basic data", "基本データ", "基本資料", "基本数据"],
+    ["Rewards", "報酬", "報酬", "报酬"],
+    ["Target", "ターゲット", "目標", "目标"],
+    ["Key", "キー", "關鍵", "关键"],
+    ["None", "なし", "無", "无"],
+    ["Village", "里", "村莊", "村庄"],
+    ["Low rank", "下位", "下位", "下位"],
+    ["High rank", "上位", "上位", "上位"],
+    ["Monster {}", "モンスター {}", "魔物 {}", "怪物 {}"],
+    ["Small monster {}", "小型モンスター {}", "小型魔物 {}", "小型怪物 {}"],
+    ["Quest {}", "クエスト {}", "任務 {}", "任务 {}"],
+    ["Map {}", "マップ {}", "地圖 {}", "地图 {}"],
+    ["[NO DATA]", "[データなし]", "[無資料]", "[无数据]"],
+    // Elements and statuses
+    ["Fire", "火", "火", "火"],
+    ["Water", "水", "水", "水"],
+    ["Thunder", "雷", "雷", "雷"],
+    ["Ice", "氷", "冰", "冰"],
+    ["Dragon", "龍", "龍", "龙"],
+    ["Poison", "毒", "毒", "毒"],
+    ["Sleep", "睡眠", "睡眠", "睡眠"],
+    ["Paralyze", "麻痺", "麻痺", "麻痹"],
+    ["Blast", "爆破", "爆破", "爆破"],
+    ["Stun", "気絶", "暈眩", "晕眩"],
+    ["Exhaust", "減気", "減氣", "减气"],
+    ["Ride", "操竜", "操龍", "操龙"],
+    ["Abnormal status", "状態異常", "異常狀態", "异常状态"],
+    ["Flash", "閃光", "閃光", "闪光"],
+    ["Dung", "こやし", "糞", "粪"],
+    ["Shock trap", "シビレ罠", "麻痺陷阱", "麻痹陷阱"],
+    ["Fall trap", "落とし穴", "陷阱", "陷阱"],
+    [
+        "Buddy shock trap",
+        "オトモのシビレ罠",
+        "隨從的麻痺陷阱",
+        "随从的麻痹陷阱",
+    ],
+    [
+        "Buddy fall trap",
+        "オトモの落とし穴",
+        "隨從的陷阱",
+        "随从的陷阱",
+    ],
+    ["Quick sand", "流砂", "流沙", "流沙"],
+    ["Steel fang", "鉄蟲糸技", "鐵蟲絲技", "铁虫丝技"],
+    // Monster pages
+    ["Hitzone data", "肉質データ", "肉質資料", "肉质数据"],
+    ["Part map", "部位マップ", "部位地圖", "部位地图"],
+    ["Behavior", "行動", "行為", "行为"],
+    ["Part", "部位", "部位", "部位"],
+    ["Parts", "部位", "部位", "部位"],
+    ["Phase", "段階", "階段", "阶段"],
+    ["Slash", "切断", "斬擊", "斩击"],
+    ["Impact", "打撃", "打擊", "打击"],
+    ["Shot", "弾", "射擊", "射击"],
+    ["Extract", "エキス", "精華", "精华"],
+    ["Red", "赤", "紅", "红"],
+    ["White", "白", "白", "白"],
+    ["Orange", "橙", "橙", "橙"],
+    ["HP", "体力", "體力", "体力"],
+    ["Part HP", "部位耐久値", "部位耐久值", "部位耐久值"],
+    ["Stagger", "怯み", "硬直", "硬直"],
+    ["Break", "部位破壊", "部位破壞", "部位破坏"],
+    ["Sever", "切断", "切斷", "切断"],
+    ["Limping", "足引きずり", "跛行", "跛行"],
+    ["Capturing", "捕獲", "捕獲", "捕获"],
+    ["Capture", "捕獲", "捕獲", "捕获"],
+    ["Carves", "剥ぎ取り", "剝取", "剥取"],
+    ["Capture rewards", "捕獲報酬", "捕獲報酬", "捕获报酬"],
+    ["Target rewards", "討伐報酬", "討伐報酬", "讨伐报酬"],
+    [
+        "Broken part rewards",
+        "部位破壊報酬",
+        "部位破壞報酬",
+        "部位破坏报酬",
+    ],
+    ["Dropped materials", "落とし物", "掉落物", "掉落物"],
+    ["From buddy", "オトモから", "來自隨從", "来自随从"],
+    ["Max stock", "最大蓄積", "最大累積", "最大累积"],
+    ["Threshold", "閾値", "閾值", "阈值"],
+    ["Damage", "ダメージ", "傷害", "伤害"],
+    ["Decay", "減少", "衰減", "衰减"],
+    ["Active time", "効果時間", "持續時間", "持续时间"],
+    [
+        "Add tired time",
+        "疲労時間増加",
+        "疲勞時間增加",
+        "疲劳时间增加",
+    ],
+    ["Dizzy", "めまい", "暈眩", "晕眩"],
+    ["Attack", "攻撃力", "攻擊力", "攻击力"],
+    ["Defense", "防御力", "防禦力", "防御力"],
+    ["Element A", "属性A", "屬性A", "属性A"],
+    ["Element B", "属性B", "屬性B", "属性B"],
+    ["Stamina", "スタミナ", "耐力", "耐力"],
+    ["Size (?)", "サイズ (?)", "尺寸 (?)", "尺寸 (?)"],
+    ["Additional information", "追加情報", "額外資訊", "额外信息"],
+    ["Enrage", "怒り", "憤怒", "愤怒"],
+    ["HP thresholds", "体力の閾値", "體力閾值", "体力阈值"],
+    [
+        "Doesn't get exhausted",
+        "疲労しない",
+        "不會疲勞",
+        "不会疲劳",
+    ],
+    ["Apex", "ヌシ", "主", "主"],
+    ["Village tour", "里の探索ツアー", "村莊探索", "村庄探索"],
+    ["Low rank tour", "下位の探索ツアー", "下位探索", "下位探索"],
+    ["High rank tour", "上位の探索ツアー", "上位探索", "上位探索"],
+    [
+        "Display data for riding",
+        "操竜時のデータを表示",
+        "顯示操龍時的資料",
+        "显示操龙时的数据",
+    ],
+    [
+        "Display invalid parts",
+        "無効な部位を表示",
+        "顯示無效部位",
+        "显示无效部位",
+    ],
+    [
+        "Don't override with preset data",
+        "プリセットデータで上書きしない",
+        "不使用預設資料覆蓋",
+        "不使用预设数据覆盖",
+    ],
+    ["Part {}", "部位 {}", "部位 {}", "部位 {}"],
+    ["[{}] Stagger {}", "[{}] 怯み {}", "[{}] 硬直 {}", "[{}] 硬直 {}"],
+    [" / Break {}", " / 部位破壊 {}", " / 部位破壞 {}", " / 部位破坏 {}"],
+    [" / Sever {}", " / 切断 {}", " / 切斷 {}", " / 切断 {}"],
+    ["Base HP: {}", "基本体力: {}", "基礎體力: {}", "基础体力: {}"],
+    ["Crown hunting", "王冠サイズ狙い", "王冠狩獵", "王冠狩猎"],
+    ["Thresholds: {}", "閾値: {}", "閾值: {}", "阈值: {}"],
+    ["Duration: {} (+{} when extended)", "持続時間: {} (延長時 +{})", "持續時間: {} (延長時 +{})", "持续时间: {} (延长时 +{})"],
+    ["Attack x{} / Defense x{} / Speed x{}", "攻撃力 x{} / 防御力 x{} / 速度 x{}", "攻擊力 x{} / 防禦力 x{} / 速度 x{}", "攻击力 x{} / 防御力 x{} / 速度 x{}"],
+    ["Sleep recovering: {} seconds / recover {}% HP", "睡眠回復: {}秒 / 体力{}%回復", "睡眠恢復: {}秒 / 恢復{}%體力", "睡眠恢复: {}秒 / 恢复{}%体力"],
+    [" / can sleep in combat", " / 戦闘中も眠る", " / 戰鬥中也會睡眠", " / 战斗中也会睡眠"],
+    ["Threshold: {} (+{} each time, up to {})", "閾値: {} (毎回 +{}、最大 {})", "閾值: {} (每次 +{}，最多 {})", "阈值: {} (每次 +{}，最多 {})"],
+    ["Decay: {} every {} seconds", "減少: {} ({}秒ごと)", "衰減: {} (每{}秒)", "衰减: {} (每{}秒)"],
+    ["Exhausted for {} seconds, losing {} stamina", "疲労 {}秒、スタミナ {} 減少", "疲勞 {}秒，減少 {} 耐力", "疲劳 {}秒，减少 {} 耐力"],
+    ["{} / {} sec", "{} / {}秒", "{} / {}秒", "{} / {}秒"],
+    ["{} sec (-{} sec) → {} sec", "{}秒 (-{}秒) → {}秒", "{}秒 (-{}秒) → {}秒", "{}秒 (-{}秒) → {}秒"],
+    ["+{} sec", "+{}秒", "+{}秒", "+{}秒"],
+    ["Preset = {}", "プリセット = {}", "預設 = {}", "预设 = {}"],
+    ["Stamina reduction = {}, Preset = {}", "スタミナ減少 = {}、プリセット = {}", "耐力減少 = {}，預設 = {}", "耐力减少 = {}，预设 = {}"],
+    ["Activate count = {}, Active time = {}", "発動回数 = {}、効果時間 = {}", "發動次數 = {}，持續時間 = {}", "发动次数 = {}，持续时间 = {}"],
+    ["Ignore refresh stance = {}", "リフレッシュを無視する姿勢 = {}", "忽略刷新的姿勢 = {}", "忽略刷新的姿势 = {}"],
+    ["Stand", "立ち", "站立", "站立"],
+    ["Fly", "飛行", "飛行", "飞行"],
+    ["Diving", "潜行", "潛行", "潜行"],
+    ["Wall", "壁", "牆壁", "墙壁"],
+    ["Ceiling", "天井", "天花板", "天花板"],
+    ["Distance = {} ~ {}, Angle = {}", "距離 = {} ~ {}、角度 = {}", "距離 = {} ~ {}，角度 = {}", "距离 = {} ~ {}，角度 = {}"],
+    ["Blast damage = {}, Preset = {}", "爆破ダメージ = {}、プリセット = {}", "爆破傷害 = {}，預設 = {}", "爆破伤害 = {}，预设 = {}"],
+    ["Common data", "共通データ", "共通資料", "共通数据"],
+    ["Unique data", "固有データ", "專屬資料", "专属数据"],
+    ["{}, Nora first limit = {}", "{}、野良初回上限 = {}", "{}，野生首次上限 = {}", "{}，野生首次上限 = {}"],
+    ["Melee hzv adjust: hard = {}, soft = {}, judge = {}", "近接肉質補正: 硬 = {}、軟 = {}、判定 = {}", "近戰肉質修正: 硬 = {}，軟 = {}，判定 = {}", "近战肉质修正: 硬 = {}，软 = {}，判定 = {}"],
+    ["Shot hzv adjust: hard = {}, soft = {}, judge = {}", "弾肉質補正: 硬 = {}、軟 = {}、判定 = {}", "射擊肉質修正: 硬 = {}，軟 = {}，判定 = {}", "射击肉质修正: 硬 = {}，软 = {}，判定 = {}"],
+    ["Hit-damage rate = {}, Preset = {}", "被ダメージ倍率 = {}、プリセット = {}", "受傷倍率 = {}，預設 = {}", "受伤倍率 = {}，预设 = {}"],
+    ["Motion speed rate = {}, Preset = {}", "モーション速度倍率 = {}、プリセット = {}", "動作速度倍率 = {}，預設 = {}", "动作速度倍率 = {}，预设 = {}"],
+    ["Stun hzv adjust: rate = {}, min = {}, max = {}, default = {}", "気絶肉質補正: 倍率 = {}、最小 = {}、最大 = {}、既定 = {}", "暈眩肉質修正: 倍率 = {}，最小 = {}，最大 = {}，預設 = {}", "晕眩肉质修正: 倍率 = {}，最小 = {}，最大 = {}，默认 = {}"],
+    ["Normal hzv adjust: rate = {}, min = {}, max = {}, default = {}", "通常肉質補正: 倍率 = {}、最小 = {}、最大 = {}、既定 = {}", "一般肉質修正: 倍率 = {}，最小 = {}，最大 = {}，預設 = {}", "一般肉质修正: 倍率 = {}，最小 = {}，最大 = {}，默认 = {}"],
+    ["Stun active limit = {}, Preset = {}", "気絶発動上限 = {}、プリセット = {}", "暈眩發動上限 = {}，預設 = {}", "晕眩发动上限 = {}，预设 = {}"],
+    ["Poison stacking = {}, Preset = {}", "毒の重ね掛け = {}、プリセット = {}", "毒累積 = {}，預設 = {}", "毒累积 = {}，预设 = {}"],
+    ["Active limit = {}, Preset = {}, Unique target param = {}", "発動上限 = {}、プリセット = {}、固有ターゲット設定 = {}", "發動上限 = {}，預設 = {}，專屬目標參數 = {}", "发动上限 = {}，预设 = {}，专属目标参数 = {}"],
+    ["Low rank reward", "下位報酬", "下位報酬", "下位报酬"],
+    ["High rank reward", "上位報酬", "上位報酬", "上位报酬"],
+    ["Main body", "本体", "本體", "本体"],
+    ["Severed part A", "切断部位A", "切斷部位A", "切断部位A"],
+    ["Severed part B", "切断部位B", "切斷部位B", "切断部位B"],
+    ["Special", "特殊", "特殊", "特殊"],
+    ["Riding", "操竜", "操龍", "操龙"],
+    ["Drop {} - {}%", "落とし物 {} - {}%", "掉落物 {} - {}%", "掉落物 {} - {}%"],
+    ["All of:", "すべて:", "全部:", "全部:"],
+    ["Any of:", "いずれか:", "任一:", "任一:"],
+    // Habitat pages
+    ["Ecology", "生態", "生態", "生态"],
+    ["Family", "系統", "種族", "种族"],
+    ["Families", "系統", "種族", "种族"],
+    ["Family {}", "系統 {}", "種族 {}", "种族 {}"],
+    ["Habitat {}", "生息地 {}", "棲息地 {}", "栖息地 {}"],
+    ["Habitats: ", "生息地: ", "棲息地: ", "栖息地: "],
+    ["Tour unlocks", "探索ツアーの解放", "探索解鎖", "探索解锁"],
+    ["Tour unlocks:", "探索ツアーの解放:", "探索解鎖:", "探索解锁:"],
+    ["Village tour: {}", "里の探索ツアー: {}", "村莊探索: {}", "村庄探索: {}"],
+    ["Low rank hub tour: {}", "集会所下位の探索ツアー: {}", "集會所下位探索: {}", "集会所下位探索: {}"],
+    ["High rank hub tour: {}", "集会所上位の探索ツアー: {}", "集會所上位探索: {}", "集会所上位探索: {}"],
+    ["Tour maps: {}", "探索ツアーのマップ: {}", "探索地圖: {}", "探索地图: {}"],
+    ["Starting areas on this map", "このマップの開始エリア", "此地圖的起始區域", "此地图的起始区域"],
+    [" ({} monsters)", " ({}体)", " ({}隻)", " ({}只)"],
+    // Crown pages
+    ["Crown hunting: ", "王冠サイズ狙い: ", "王冠狩獵: ", "王冠狩猎: "],
+    ["Back to monster", "モンスターに戻る", "返回魔物", "返回怪物"],
+    ["Mini crown", "最小金冠", "最小金冠", "最小金冠"],
+    ["Silver crown", "銀冠", "銀冠", "银冠"],
+    ["Gold crown", "最大金冠", "最大金冠", "最大金冠"],
+    ["Silver {}%", "銀冠 {}%", "銀冠 {}%", "银冠 {}%"],
+    ["Best quests for gold crown", "最大金冠に最適なクエスト", "最大金冠的最佳任務", "最大金冠的最佳任务"],
+    ["Best quests for silver crown", "銀冠に最適なクエスト", "銀冠的最佳任務", "银冠的最佳任务"],
+    ["Best quests for mini crown", "最小金冠に最適なクエスト", "最小金冠的最佳任務", "最小金冠的最佳任务"],
+    // Map pages
+    ["Area {}: {}%", "エリア {}: {}%", "區域 {}: {}%", "区域 {}: {}%"],
+    ["Monster starting areas", "モンスターの開始エリア", "魔物起始區域", "怪物起始区域"],
+    ["Spawn sets", "出現セット", "出現組合", "出现组合"],
+    ["Spawn positions", "出現位置", "出現位置", "出现位置"],
+    ["Areas: {}", "エリア: {}", "區域: {}", "区域: {}"],
+    // Quest pages
+    ["Objective: ", "目的: ", "目標: ", "目标: "],
+    ["Map: ", "マップ: ", "地圖: ", "地图: "],
+    ["Monster stats", "モンスターの能力", "魔物能力", "怪物能力"],
+    [
+        "Effective stats for solo hunts",
+        "ソロでの実効値",
+        "單人狩獵的實際數值",
+        "单人狩猎的实际数值",
+    ],
+    [
+        "Multiplayer Factor (Column header might be wrong)",
+        "マルチプレイ補正 (列名は誤りの可能性あり)",
+        "多人遊玩倍率 (欄位名稱可能有誤)",
+        "多人游玩倍率 (列名可能有误)",
+    ],
+    ["Status buildup", "状態異常の蓄積", "異常狀態累積", "异常状态累积"],
+    ["Status", "状態異常", "異常狀態", "异常状态"],
+    ["Quest rate", "クエスト補正", "任務倍率", "任务倍率"],
+    ["Starting areas", "開始エリア", "起始區域", "起始区域"],
+    ["Areas", "エリア", "區域", "区域"],
+    ["Spawn set", "出現セット", "出現組合", "出现组合"],
+    [
+        "Rampage waves",
+        "百竜夜行のウェーブ",
+        "百龍夜行的波次",
+        "百龙夜行的波次",
+    ],
+    ["Wave", "ウェーブ", "波次", "波次"],
+    [
+        "Wave monsters",
+        "ウェーブのモンスター",
+        "波次魔物",
+        "波次怪物",
+    ],
+    ["Major monster", "主なモンスター", "主要魔物", "主要怪物"],
+    [
+        "Monster to monster",
+        "モンスター同士",
+        "魔物之間",
+        "怪物之间",
+    ],
+    ["Other parts", "その他の部位", "其他部位", "其他部位"],
+    ["Multi parts", "複数部位", "多部位", "多部位"],
+    ["Order table", "受注テーブル", "接受表", "接受表"],
+    [
+        "Difficulty table (major / wave)",
+        "難易度テーブル (メイン / ウェーブ)",
+        "難度表 (主要 / 波次)",
+        "难度表 (主要 / 波次)",
+    ],
+    ["Supply items", "支給品", "支給品", "支给品"],
+    [
+        "Unexpected small monster",
+        "想定外の小型モンスター",
+        "意外的小型魔物",
+        "意外的小型怪物",
+    ],
+    ["Level: {} / Apex wave: {}", "レベル: {} / ヌシのウェーブ: {}", "等級: {} / 主的波次: {}", "等级: {} / 主的波次: {}"],
+    ["Apex monster: ", "ヌシ: ", "主: ", "主: "],
+    ["Rampage rewards", "百竜夜行の報酬", "百龍夜行報酬", "百龙夜行报酬"],
+    ["Money: {}z / Village points: {} / Rank points: {}", "報酬金: {}z / 里ポイント: {} / HRポイント: {}", "報酬金: {}z / 村莊點數: {} / HR點數: {}", "报酬金: {}z / 村庄点数: {} / HR点数: {}"],
+    ["Main rewards", "メイン報酬", "主要報酬", "主要报酬"],
+    ["Sub rewards (x{})", "サブ報酬 (x{})", "次要報酬 (x{})", "次要报酬 (x{})"],
+    ["Additional rewards", "追加報酬", "追加報酬", "追加报酬"],
+    ["Random", "ランダム", "隨機", "随机"],
+    ["Random out 1", "ランダム (1枠除外)", "隨機 (排除1格)", "随机 (排除1格)"],
+    ["Random out 2", "ランダム (2枠除外)", "隨機 (排除2格)", "随机 (排除2格)"],
+    ["Random out 3", "ランダム (3枠除外)", "隨機 (排除3格)", "随机 (排除3格)"],
+    ["First fixed", "1枠目固定", "第一格固定", "第一格固定"],
+    // Armor pages
+    [
+        "Armor names are probably incorrect.",
+        "防具名は誤っている可能性があります。",
+        "防具名稱可能有誤。",
+        "防具名称可能有误。",
+    ],
+    ["Stat", "ステータス", "能力", "能力"],
+    [
+        "Value (Sell / Buy)",
+        "価格 (売値 / 買値)",
+        "價格 (賣價 / 買價)",
+        "价格 (卖价 / 买价)",
+    ],
+    ["Slots", "スロット", "鑲嵌槽", "镶嵌槽"],
+    ["Preview", "プレビュー", "預覽", "预览"],
+    ["Layered", "重ね着", "外觀裝備", "外观装备"],
+    ["Crafting", "生産", "生產", "生产"],
+    [
+        "Layered crafting",
+        "重ね着の生産",
+        "外觀裝備生產",
+        "外观装备生产",
+    ],
+    [
+        "Categorized Material",
+        "カテゴリ素材",
+        "類別素材",
+        "类别素材",
+    ],
+    ["Material", "素材", "素材", "素材"],
+    ["Materials", "素材", "素材", "素材"],
+    ["Output", "入手", "產出", "产出"],
+    ["Armor {}", "防具 {}", "防具 {}", "防具 {}"],
+    ["Female", "女性", "女性", "女性"],
+    ["Male", "男性", "男性", "男性"],
+    // Item pages
+    ["Carriable filter", "持ち込み制限", "攜帶限制", "携带限制"],
+    ["Rarity", "レア度", "稀有度", "稀有度"],
+    ["Maximum carry", "所持上限", "持有上限", "持有上限"],
+    [
+        "Maximum carry by buddy",
+        "オトモの所持上限",
+        "隨從持有上限",
+        "随从持有上限",
+    ],
+    [
+        "In item bar",
+        "アイテムバーに表示",
+        "顯示於道具欄",
+        "显示于道具栏",
+    ],
+    [
+        "In action bar",
+        "アクションバーに表示",
+        "顯示於動作欄",
+        "显示于动作栏",
+    ],
+    ["Infinite", "無限", "無限", "无限"],
+    ["Fixed item", "固定アイテム", "固定道具", "固定道具"],
+    ["SE type", "SE種類", "音效種類", "音效种类"],
+    ["Sell price", "売値", "賣價", "卖价"],
+    ["Buy price", "買値", "買價", "买价"],
+    ["Rank type", "ランク", "等級", "等级"],
+    ["Item group", "アイテムグループ", "道具分組", "道具分组"],
+    ["Material category", "素材カテゴリ", "素材類別", "素材类别"],
+    ["Evaluation value", "評価値", "評價值", "评价值"],
+    ["All", "すべて", "全部", "全部"],
+    ["Hyakuryu", "百竜夜行", "百龍夜行", "百龙夜行"],
+    ["Lobby", "集会所", "集會所", "集会所"],
+    ["Consume", "消費", "消耗品", "消耗品"],
+    ["Tool", "道具", "工具", "工具"],
+    ["Offcuts material", "端材", "邊角料", "边角料"],
+    ["Bullet", "弾", "彈藥", "弹药"],
+    ["Bottle", "ビン", "瓶", "瓶"],
+    ["Present", "プレゼント", "禮物", "礼物"],
+    ["Pay off", "換金", "換金", "换金"],
+    ["Carry pay off", "運搬換金", "搬運換金", "搬运换金"],
+    ["Carry", "運搬", "搬運", "搬运"],
+    ["Judge", "鑑定", "鑑定", "鉴定"],
+    ["Antique", "骨董品", "古董", "古董"],
+    ["Drink", "飲み物", "飲料", "饮料"],
+    ["Food", "食べ物", "食物", "食物"],
+    ["Others", "その他", "其他", "其他"],
+    ["{} pt", "{} pt", "{} pt", "{} pt"],
+    // Skill pages
+    ["Cost", "コスト", "費用", "费用"],
+    ["Level {}: ", "レベル {}: ", "等級 {}: ", "等级 {}: "],
+    ["Rampage skill {}", "百竜スキル {}", "百龍技能 {}", "百龙技能 {}"],
+    // Weapon pages
+    ["Weapon", "武器", "武器", "武器"],
+    ["Great sword", "大剣", "大劍", "大剑"],
+    ["Long sword", "太刀", "太刀", "太刀"],
+    ["Sword & shield", "片手剣", "單手劍", "单手剑"],
+    ["Hammer", "ハンマー", "大錘", "大锤"],
+    ["Lance", "ランス", "長槍", "长枪"],
+    ["Dual blades", "双剣", "雙劍", "双剑"],
+    ["Hunting horn", "狩猟笛", "狩獵笛", "狩猎笛"],
+    ["Gunlance", "ガンランス", "銃槍", "铳枪"],
+    ["Switch axe", "スラッシュアックス", "斬擊斧", "斩击斧"],
+    ["Charge blade", "チャージアックス", "充能斧", "充能斧"],
+    ["Insect glaive", "操虫棍", "操蟲棍", "操虫棍"],
+    ["Bow", "弓", "弓", "弓"],
+    ["Light bowgun", "ライトボウガン", "輕弩", "轻弩"],
+    ["Heavy bowgun", "ヘビィボウガン", "重弩", "重弩"],
+    ["Affinity", "会心率", "會心率", "会心率"],
+    // About page
+    ["About MHRice", "MHRiceについて", "關於MHRice", "关于MHRice"],
+    ["MHRice is an information site for Monster Hunter Rise, displaying data extracted from the game.", "MHRiceはモンスターハンターライズの情報サイトで、ゲームから抽出したデータを表示しています。", "MHRice 是魔物獵人 崛起的資訊網站，顯示從遊戲中提取的資料。", "MHRice 是怪物猎人 崛起的信息网站，显示从游戏中提取的数据。"],
+    ["MHRice website is generated from the open source MHRice project.", "MHRiceのウェブサイトはオープンソースのMHRiceプロジェクトから生成されています。", "MHRice 網站由開源的 MHRice 專案產生。", "MHRice 网站由开源的 MHRice 项目生成。"],
+    ["The data is also available as JSON files, starting from ", "データはJSONファイルとしても公開しています。入口: ", "資料也以 JSON 檔案提供，入口為 ", "数据也以 JSON 文件提供，入口为 "],
+    ["Visit MHRice on Github", "GithubでMHRiceを見る", "在 Github 上查看 MHRice", "在 Github 上查看 MHRice"],
+    ["Build information", "ビルド情報", "建置資訊", "构建信息"],
+    ["Git hash: ", "Gitハッシュ: ", "Git 雜湊: ", "Git 哈希: "],
+    ["Update time: {}", "更新日時: {}", "更新時間: {}", "更新时间: {}"],
+    // Patch notes
+    ["Patch notes", "更新内容", "更新內容", "更新内容"],
+    ["Added: ", "追加: ", "新增: ", "新增: "],
+    ["Removed: Monster {}", "削除: モンスター {}", "移除: 魔物 {}", "移除: 怪物 {}"],
+    ["Base HP: {} → {}", "基本体力: {} → {}", "基礎體力: {} → {}", "基础体力: {} → {}"],
+    ["Hitzones", "肉質", "肉質", "肉质"],
+    ["New quests", "新しいクエスト", "新任務", "新任务"],
+    ["Removed quests", "削除されたクエスト", "移除的任務", "移除的任务"],
+    ["New armors", "新しい防具", "新防具", "新防具"],
+    ["Removed armors", "削除された防具", "移除的防具", "移除的防具"],
+    ["Item prices", "アイテムの価格", "道具價格", "道具价格"],
+    ["Text", "テキスト", "文字", "文本"],
+    ["Field", "項目", "欄位", "字段"],
+    ["Old", "旧", "舊", "旧"],
+    ["New", "新", "新", "新"],
+    ["File", "ファイル", "檔案", "文件"],
+    ["Entry", "エントリ", "條目", "条目"],
+    ["Language", "言語", "語言", "语言"],
+];
+
+static CATALOG_MAP: Lazy<HashMap<&'static str, &'static [&'static str; 4]>> = Lazy::new(|| {
+    let mut map = HashMap::new();
+    for entry in CATALOG {
+        if map.insert(entry[0], entry).is_some() {
+            panic!("Duplicate UI text {}", entry[0]);
+        }
+    }
+    map
+});
+
+fn catalog_column(language: usize) -> Option<usize> {
+    match language {
+        0 => Some(1),
+        12 => Some(2),
+        13 => Some(3),
+        _ => None,
+    }
+}
+
+/// Looks up the translation of the English UI text for a data language index.
+/// Returns `None` if the text is not in the catalog.
+pub fn translate(text: &str, language: usize) -> Option<&'static str> {
+    let entry = CATALOG_MAP.get(text)?;
+    Some(entry[catalog_column(language).unwrap_or(0)])
+}
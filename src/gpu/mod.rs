@@ -11,9 +11,11 @@ use std::thread::*;
 
 // mod astc;
 mod ffi;
+mod model_preview;
 mod monster_hitzone;
 
 pub use ffi::*;
+pub use model_preview::*;
 pub use monster_hitzone::*;
 
 struct Job {
@@ -134,6 +136,88 @@ impl RgbaImage {
         })
     }
 
+    pub fn empty(width: u32, height: u32) -> anyhow::Result<RgbaImage> {
+        Ok(RgbaImage {
+            data: vec![0; usize::try_from(width * height * 4)?],
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Makes a copy with the color of every pixel replaced, keeping the alpha
+    pub fn fill(&self, color: [u8; 3]) -> RgbaImage {
+        let mut data = self.data.clone();
+        for pixel in data.chunks_exact_mut(4) {
+            pixel[0..3].copy_from_slice(&color);
+        }
+        RgbaImage {
+            data,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Scales the image with nearest sampling
+    pub fn resize(&self, width: u32, height: u32) -> anyhow::Result<RgbaImage> {
+        let mut data = vec![0; usize::try_from(width * height * 4)?];
+        for x in 0..width {
+            for y in 0..height {
+                let src_x = x * self.width / width;
+                let src_y = y * self.height / height;
+                let src = usize::try_from(src_x + src_y * self.width)? * 4;
+                let dst = usize::try_from(x + y * width)? * 4;
+                data[dst..dst + 4].copy_from_slice(&self.data[src..src + 4]);
+            }
+        }
+        Ok(RgbaImage {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Alpha-blends `top` over this image with its top-left corner at (x, y).
+    /// Parts outside of this image are clipped.
+    pub fn blend(&mut self, top: &RgbaImage, x: i32, y: i32) -> anyhow::Result<()> {
+        for top_y in 0..top.height {
+            for top_x in 0..top.width {
+                let dst_x = x + top_x as i32;
+                let dst_y = y + top_y as i32;
+                if dst_x < 0
+                    || dst_y < 0
+                    || dst_x >= self.width as i32
+                    || dst_y >= self.height as i32
+                {
+                    continue;
+                }
+                let src = usize::try_from(top_x + top_y * top.width)? * 4;
+                let dst = usize::try_from(dst_x as u32 + dst_y as u32 * self.width)? * 4;
+                let src_a = f32::from(top.data[src + 3]) / 255.0;
+                let dst_a = f32::from(self.data[dst + 3]) / 255.0;
+                let out_a = src_a + dst_a * (1.0 - src_a);
+                if out_a <= 0.0 {
+                    continue;
+                }
+                for c in 0..3 {
+                    let value = (f32::from(top.data[src + c]) * src_a
+                        + f32::from(self.data[dst + c]) * dst_a * (1.0 - src_a))
+                        / out_a;
+                    self.data[dst + c] = value.round() as u8;
+                }
+                self.data[dst + 3] = (out_a * 255.0).round() as u8;
+            }
+        }
+        Ok(())
+    }
+
     pub fn gen_double_mask(mut self) -> (RgbaImage, RgbaImage) {
         let width = self.width;
         let height = self.height;
@@ -157,3 +241,39 @@ impl RgbaImage {
         )
     }
 }
+
+fn crop_image(image: texture::RawImage2d<u8>) -> anyhow::Result<RgbaImage> {
+    let mut min_x = image.width;
+    let mut max_x = 0;
+    let mut min_y = image.height;
+    let mut max_y = 0;
+    for x in 0..image.width {
+        for y in 0..image.height {
+            let index = usize::try_from(x + y * image.width)?;
+            if image.data[index * 4 + 3] != 0 {
+                min_x = std::cmp::min(min_x, x);
+                min_y = std::cmp::min(min_y, y);
+                max_x = std::cmp::max(max_x, x);
+                max_y = std::cmp::max(max_y, y);
+            }
+        }
+    }
+
+    let new_width = max_x - min_x + 1;
+    let new_height = max_y - min_y + 1;
+    let mut new_data = vec![0; usize::try_from(new_width * new_height * 4)?];
+
+    for x in 0..new_width {
+        for y in 0..new_height {
+            let new_index = usize::try_from(x + y * new_width)?;
+            let index = usize::try_from(x + min_x + (y + min_y) * image.width)?;
+            new_data[new_index * 4..][..4].copy_from_slice(&image.data[index * 4..][..4]);
+        }
+    }
+
+    Ok(RgbaImage {
+        data: new_data,
+        width: new_width,
+        height: new_height,
+    })
+}
@@ -0,0 +1,158 @@
+use super::*;
+use anyhow::Context;
+use ordered_float::*;
+
+pub struct PreviewPart {
+    pub indexs: Vec<u32>,
+    pub albedo: Option<RgbaImage>,
+}
+
+pub struct PreviewMesh {
+    pub positions: Vec<Vec3>,
+    pub uvs: Vec<Vec2>,
+    pub parts: Vec<PreviewPart>,
+}
+
+/// Renders a front view of the mesh, lit from the camera and cropped to its silhouette
+pub fn gen_model_preview(mesh: PreviewMesh, size: u32) -> anyhow::Result<RgbaImage> {
+    CONTEXT.run(move |gl| {
+        let coord = |f: fn(&Vec3) -> f32| -> anyhow::Result<(f32, f32)> {
+            let values = || mesh.positions.iter().filter_map(|p| NotNan::new(f(p)).ok());
+            Ok((
+                values().min().context("null mesh")?.into_inner(),
+                values().max().context("null mesh")?.into_inner(),
+            ))
+        };
+        let (x_min, x_max) = coord(|p| p.x)?;
+        let (y_min, y_max) = coord(|p| p.y)?;
+        let (z_min, z_max) = coord(|p| p.z)?;
+
+        let center = vec3(
+            (x_min + x_max) * 0.5,
+            (y_min + y_max) * 0.5,
+            (z_min + z_max) * 0.5,
+        );
+        let half_xy = ((x_max - x_min).max(y_max - y_min) * 0.5).max(f32::EPSILON);
+        let half_z = ((z_max - z_min) * 0.5).max(f32::EPSILON);
+
+        let move_to_center = translate(&identity(), &-center);
+        // Models face +Z. Also flip Y because the image is read back bottom row first
+        let face_camera = rotate_x(&identity(), std::f32::consts::PI);
+        let scale_to_fit = scale(
+            &identity(),
+            &vec3(1.0 / half_xy, 1.0 / half_xy, 1.0 / half_z),
+        );
+        let transform = scale_to_fit * face_camera * move_to_center;
+
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 3],
+            uv: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position, uv);
+
+        let vertex_buffer_raw: Vec<Vertex> = mesh
+            .positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let uv = mesh.uvs.get(i).map_or([0.0, 0.0], |uv| [uv.x, uv.y]);
+                Vertex {
+                    position: [p.x, p.y, p.z],
+                    uv,
+                }
+            })
+            .collect();
+        let vertex_buffer = VertexBuffer::new(&gl.display, &vertex_buffer_raw)?;
+
+        let program = Program::from_source(
+            &gl.display,
+            "#version 330 core
+
+            uniform mat4 matrix;
+
+            in vec3 position;
+            in vec2 uv;
+
+            out vec2 frag_uv;
+            out vec3 view_position;
+
+            void main() {
+                gl_Position = matrix * vec4(position, 1.0);
+                view_position = gl_Position.xyz;
+                frag_uv = uv;
+            }
+        ",
+            "#version 330 core
+
+            uniform sampler2D albedo;
+            uniform bool has_albedo;
+
+            in vec2 frag_uv;
+            in vec3 view_position;
+
+            layout(location = 0) out vec4 out_color;
+
+            void main() {
+                vec3 normal = normalize(cross(dFdx(view_position), dFdy(view_position)));
+                float light = 0.35 + 0.65 * abs(normal.z);
+                vec3 color = has_albedo ? texture(albedo, frag_uv).rgb : vec3(0.7);
+                out_color = vec4(color * light, 1.0);
+            }
+        ",
+            None,
+        )?;
+
+        let color = texture::Texture2d::empty_with_format(
+            &gl.display,
+            texture::UncompressedFloatFormat::U8U8U8U8,
+            texture::MipmapsOption::NoMipmap,
+            size,
+            size,
+        )?;
+        let depth = texture::DepthTexture2d::empty(&gl.display, size, size)?;
+        let mut framebuffer =
+            framebuffer::SimpleFrameBuffer::with_depth_buffer(&gl.display, &color, &depth)?;
+        framebuffer.clear_all((0.0, 0.0, 0.0, 0.0), 1.0, 0);
+
+        let param = glium::DrawParameters {
+            depth: glium::Depth {
+                test: glium::DepthTest::IfLess,
+                write: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let blank = texture::Texture2d::empty(&gl.display, 1, 1)?;
+        for part in mesh.parts {
+            let index_buffer = IndexBuffer::new(
+                &gl.display,
+                index::PrimitiveType::TrianglesList,
+                &part.indexs,
+            )?;
+            let albedo = part
+                .albedo
+                .map(|image| {
+                    let raw =
+                        texture::RawImage2d::from_raw_rgba(image.data, (image.width, image.height));
+                    texture::Texture2d::new(&gl.display, raw)
+                })
+                .transpose()?;
+            let sampler = uniforms::Sampler::new(albedo.as_ref().unwrap_or(&blank))
+                .wrap_function(uniforms::SamplerWrapFunction::Repeat)
+                .minify_filter(uniforms::MinifySamplerFilter::Linear)
+                .magnify_filter(uniforms::MagnifySamplerFilter::Linear);
+            let uniforms = uniform! {
+                matrix: *transform.as_ref(),
+                albedo: sampler,
+                has_albedo: albedo.is_some(),
+            };
+            framebuffer.draw(&vertex_buffer, &index_buffer, &program, &uniforms, &param)?;
+        }
+
+        let image: texture::RawImage2d<u8> = color.read();
+        crop_image(image)
+    })
+}
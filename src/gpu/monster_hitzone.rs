@@ -3,7 +3,6 @@ use crate::part_color::PART_COLORS;
 use anyhow::Context;
 use ordered_float::*;
 use std::collections::HashSet;
-use std::convert::TryFrom;
 
 pub struct HitzoneDiagram {
     pub meat: RgbaImage,
@@ -16,42 +15,6 @@ pub struct ColoredVertex {
     pub parts_group: HashSet<usize>,
 }
 
-fn crop_image(image: texture::RawImage2d<u8>) -> anyhow::Result<RgbaImage> {
-    let mut min_x = image.width;
-    let mut max_x = 0;
-    let mut min_y = image.height;
-    let mut max_y = 0;
-    for x in 0..image.width {
-        for y in 0..image.height {
-            let index = usize::try_from(x + y * image.width)?;
-            if image.data[index * 4 + 3] != 0 {
-                min_x = std::cmp::min(min_x, x);
-                min_y = std::cmp::min(min_y, y);
-                max_x = std::cmp::max(max_x, x);
-                max_y = std::cmp::max(max_y, y);
-            }
-        }
-    }
-
-    let new_width = max_x - min_x + 1;
-    let new_height = max_y - min_y + 1;
-    let mut new_data = vec![0; usize::try_from(new_width * new_height * 4)?];
-
-    for x in 0..new_width {
-        for y in 0..new_height {
-            let new_index = usize::try_from(x + y * new_width)?;
-            let index = usize::try_from(x + min_x + (y + min_y) * image.width)?;
-            new_data[new_index * 4..][..4].copy_from_slice(&image.data[index * 4..][..4]);
-        }
-    }
-
-    Ok(RgbaImage {
-        data: new_data,
-        width: new_width,
-        height: new_height,
-    })
-}
-
 pub fn gen_hitzone_diagram(
     vertexs: Vec<ColoredVertex>,
     indexs: Vec<u32>,
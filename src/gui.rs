@@ -1,6 +1,7 @@
 #![allow(unused_variables)]
 
 use crate::file_ext::*;
+use crate::rsz::Guid;
 use anyhow::*;
 use serde::*;
 use std::io::{Read, Seek, SeekFrom};
@@ -17,12 +18,39 @@ where
 #[derive(Debug, Serialize)]
 pub enum FieldValue {
     Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
     F64(f64),
+    U8String(String),
     String(String),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    Color([u8; 4]),
+    Enum(i32),
+    Guid(Guid),
+    Resource(String),
+    Message(Guid),
     Texture(String),
     Unknown(u32, u64),
 }
 
+impl FieldValue {
+    pub fn unknown_type(&self) -> Option<u32> {
+        if let FieldValue::Unknown(t, _) = *self {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct Field {
     pub b: u32,
@@ -155,17 +183,67 @@ impl Gui {
 
             let old = file.tell()?;
 
+            // Scalar values are stored inline, while everything wider than 8 bytes
+            // or variable-sized lives at an offset
             let value = match value_type {
                 1 => FieldValue::Bool(match value {
                     0 => false,
                     1 => true,
                     _ => bail!("Invalid bool value"),
                 }),
+                2 => FieldValue::I8(value as i8),
+                3 => FieldValue::U8(value as u8),
+                4 => FieldValue::I16(value as i16),
+                5 => FieldValue::U16(value as u16),
+                6 => FieldValue::I32(value as i32),
+                7 => FieldValue::U32(value as u32),
+                8 => FieldValue::I64(value as i64),
+                9 => FieldValue::U64(value),
                 10 => FieldValue::F64(f64::from_bits(value)),
+                12 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    FieldValue::U8String(file.read_u8str()?)
+                }
                 13 => {
                     file.seek(SeekFrom::Start(value))?;
                     FieldValue::String(file.read_u16str()?)
                 }
+                20 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    FieldValue::Vec2([file.read_f32()?, file.read_f32()?])
+                }
+                21 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    FieldValue::Vec3([file.read_f32()?, file.read_f32()?, file.read_f32()?])
+                }
+                22 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    FieldValue::Vec4([
+                        file.read_f32()?,
+                        file.read_f32()?,
+                        file.read_f32()?,
+                        file.read_f32()?,
+                    ])
+                }
+                // Keep colors that don't fit in RGBA8 as unknown for scan_gui to report
+                23 if value >> 32 == 0 => FieldValue::Color((value as u32).to_le_bytes()),
+                24 => FieldValue::Enum(value as i32),
+                25 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    let mut bytes = [0; 16];
+                    file.read_exact(&mut bytes)?;
+                    FieldValue::Guid(Guid { bytes })
+                }
+                26 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    FieldValue::Resource(file.read_u16str()?)
+                }
+                27 => {
+                    file.seek(SeekFrom::Start(value))?;
+                    let mut bytes = [0; 16];
+                    file.read_exact(&mut bytes)?;
+                    FieldValue::Message(Guid { bytes })
+                }
                 32 => {
                     file.seek(SeekFrom::Start(value))?;
                     FieldValue::Texture(file.read_u16str()?)
@@ -2,44 +2,52 @@
 
 use anyhow::*;
 use rayon::prelude::*;
-use rusoto_core::{ByteStream, Region};
-use rusoto_s3::*;
-use std::convert::TryFrom;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Cursor};
 use std::path::*;
-use std::sync::Mutex;
 use structopt::*;
-use walkdir::WalkDir;
 
 mod align;
 mod bitfield;
+mod depgraph;
 mod extract;
 mod file_ext;
 mod gpu;
 mod gui;
 mod hash;
+mod mdf2;
 mod mesh;
 mod msg;
 mod pak;
 mod part_color;
+mod pathdict;
 mod pfb;
+mod publish;
 mod rcol;
 mod rsz;
+mod scene;
 mod scn;
+mod sniff;
 mod suffix;
 mod tdb;
 mod tex;
 mod user;
 mod uvs;
 
+use depgraph::*;
 use gui::*;
+use mdf2::*;
 use mesh::*;
 use msg::*;
 use pak::*;
+use pathdict::*;
 use pfb::*;
+use publish::*;
 use rcol::*;
+use scene::*;
 use scn::*;
+use sniff::*;
 use tdb::*;
 use tex::*;
 use user::*;
@@ -50,6 +58,22 @@ pub mod built_info {
     include!(concat!(env!("OUT_DIR"), "/built.rs"));
 }
 
+#[derive(StructOpt)]
+struct PublishOptions {
+    #[structopt(long)]
+    s3: Option<String>,
+    #[structopt(long)]
+    s3_region: Option<String>,
+    #[structopt(long)]
+    s3_endpoint: Option<String>,
+    #[structopt(long)]
+    mirror: Option<String>,
+    #[structopt(long)]
+    bundle: Option<String>,
+    #[structopt(long)]
+    dry_run: bool,
+}
+
 #[derive(StructOpt)]
 enum Mhrice {
     Dump {
@@ -77,18 +101,88 @@ enum Mhrice {
         pak: Vec<String>,
     },
 
+    Stat {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+    },
+
     GenJson {
         #[structopt(short, long)]
         pak: Vec<String>,
     },
 
+    GenQuestJson {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+    },
+
+    GenBehaviorJson {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+    },
+
+    CrownRanking {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+
+        #[structopt(short, long)]
+        monster: u32,
+
+        #[structopt(short, long, default_value = "0")]
+        sub_id: u32,
+
+        /// mini, silver or gold
+        #[structopt(short, long, default_value = "gold")]
+        crown: extract::CrownKind,
+    },
+
+    PartBreakPlan {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+
+        #[structopt(short, long)]
+        monster: u32,
+
+        #[structopt(short, long, default_value = "0")]
+        sub_id: u32,
+
+        /// Scale HP by the rates of the monster in this quest
+        #[structopt(short, long)]
+        quest: Option<i32>,
+    },
+
     GenWebsite {
         #[structopt(short, long)]
         pak: Vec<String>,
         #[structopt(short, long)]
         output: String,
+        /// Generate a single-language site, such as ja, en or zh-Hans
+        #[structopt(short, long)]
+        language: Option<String>,
+        /// Link to the site of another language, as code=url
+        #[structopt(long)]
+        alternate: Vec<String>,
+        /// Render patch notes from the JSON output of the diff command
+        #[structopt(long)]
+        patch_notes: Option<String>,
+        #[structopt(flatten)]
+        publish: PublishOptions,
+    },
+
+    Publish {
+        #[structopt(short, long)]
+        input: String,
+        #[structopt(flatten)]
+        publish: PublishOptions,
+    },
+
+    Diff {
+        #[structopt(long)]
+        old_pak: Vec<String>,
         #[structopt(long)]
-        s3: Option<String>,
+        new_pak: Vec<String>,
+        #[structopt(short, long)]
+        output: String,
     },
 
     ReadTdb {
@@ -125,6 +219,25 @@ enum Mhrice {
     SearchPath {
         #[structopt(short, long)]
         pak: Vec<String>,
+        /// Path dictionary to add the found paths to
+        #[structopt(short, long)]
+        dict: Option<String>,
+    },
+
+    AddPaths {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+        #[structopt(short, long)]
+        dict: String,
+        #[structopt(short, long)]
+        list: String,
+    },
+
+    Coverage {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+        #[structopt(short, long)]
+        dict: String,
     },
 
     DumpTree {
@@ -136,6 +249,38 @@ enum Mhrice {
         output: String,
     },
 
+    GenDepGraph {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+        #[structopt(short, long)]
+        list: String,
+        #[structopt(short, long)]
+        output: String,
+    },
+
+    QueryDepGraph {
+        #[structopt(short, long)]
+        graph: String,
+        #[structopt(short, long)]
+        path: String,
+        /// List files referencing the path instead of files it references
+        #[structopt(short, long)]
+        reverse: bool,
+        /// Include indirect references
+        #[structopt(short = "R", long)]
+        recursive: bool,
+    },
+
+    ExportDepGraph {
+        #[structopt(short, long)]
+        graph: String,
+        /// dot or json
+        #[structopt(short, long, default_value = "dot")]
+        format: DepGraphFormat,
+        #[structopt(short, long)]
+        output: String,
+    },
+
     ScanMesh {
         #[structopt(short, long)]
         pak: Vec<String>,
@@ -173,6 +318,31 @@ enum Mhrice {
         rcol: String,
     },
 
+    ScanMdf {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+    },
+
+    DumpMdf {
+        #[structopt(short, long)]
+        mdf: String,
+    },
+
+    /// Export a mesh from the pak as OBJ, with its materials and textures
+    ExportModel {
+        #[structopt(short, long)]
+        pak: Vec<String>,
+        /// Mesh path, e.g. enemy/em001/00/mod/em001_00.mesh
+        #[structopt(short, long)]
+        mesh: String,
+        /// Material path. Defaults to the mesh path with the .mdf2 extension
+        #[structopt(long)]
+        mdf: Option<String>,
+        /// Output directory
+        #[structopt(short, long)]
+        output: String,
+    },
+
     DumpMeat {
         #[structopt(short, long)]
         mesh: String,
@@ -194,6 +364,13 @@ enum Mhrice {
         gui: String,
     },
 
+    DumpScene {
+        #[structopt(short, long)]
+        scene: String,
+        #[structopt(short, long)]
+        json: bool,
+    },
+
     GenMeat {
         #[structopt(short, long)]
         pak: Vec<String>,
@@ -263,26 +440,48 @@ struct TreeNode {
 }*/
 
 fn scan(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-
-    for index in pak.all_file_indexs() {
-        let content = pak
-            .read_file(index)
-            .context(format!("Failed to open file at {:?}", index))?;
-        if content.len() < 3 {
-            continue;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+
+    pak.par_files().try_for_each(|(index, content)| {
+        let content = content.context(format!("Failed to open file at {:?}", index))?;
+        match sniff(&content) {
+            FileType::User => {
+                let _ = User::new(Cursor::new(&content))
+                    .context(format!("Failed to open USER at {:?}", index))?;
+            }
+            FileType::Pfb => {
+                let _ = Pfb::new(Cursor::new(&content))
+                    .context(format!("Failed to open PFB at {:?}", index))?;
+            }
+            FileType::Scn => {
+                let _ = Scn::new(Cursor::new(&content))
+                    .context(format!("Failed to open SCN at {:?}", index))?;
+            }
+            _ => (),
         }
+        Ok(())
+    })
+}
 
-        if &content[0..3] == b"USR" {
-            let _ = User::new(Cursor::new(&content))
-                .context(format!("Failed to open USER at {:?}", index))?;
-        } else if &content[0..3] == b"PFB" {
-            let _ = Pfb::new(Cursor::new(&content))
-                .context(format!("Failed to open PFB at {:?}", index))?;
-        } else if &content[0..3] == b"SCN" {
-            let _ = Scn::new(Cursor::new(&content))
-                .context(format!("Failed to open SCN at {:?}", index))?;
-        }
+fn stat(pak: Vec<String>) -> Result<()> {
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    let files = pak
+        .par_files()
+        .map(|(_, file)| {
+            let file = file?;
+            Ok((sniff(&file), file.len()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // file type -> (count, total size)
+    let mut stats: BTreeMap<FileType, (usize, usize)> = BTreeMap::new();
+    for (file_type, len) in files {
+        let entry = stats.entry(file_type).or_default();
+        entry.0 += 1;
+        entry.1 += len;
+    }
+    println!("{:8} {:>8} {:>14}", "type", "count", "size");
+    for (file_type, (count, size)) in stats {
+        println!("{:8} {:>8} {:>14}", file_type.name(), count, size);
     }
     Ok(())
 }
@@ -295,157 +494,173 @@ fn gen_json(pak: Vec<String>) -> Result<()> {
     Ok(())
 }
 
-async fn upload_s3(
-    path: PathBuf,
-    len: u64,
-    mime: &str,
-    bucket: String,
-    key: String,
-    client: &S3Client,
-) -> Result<()> {
-    use futures::StreamExt;
-    use tokio_util::codec;
-    let file = tokio::fs::File::open(path).await?;
-    let stream =
-        codec::FramedRead::new(file, codec::BytesCodec::new()).map(|r| r.map(|r| r.freeze()));
-    let request = PutObjectRequest {
-        bucket,
-        key,
-        body: Some(ByteStream::new(stream)),
-        content_length: Some(i64::try_from(len)?),
-        content_type: Some(mime.to_owned()),
-        ..PutObjectRequest::default()
-    };
-    client.put_object(request).await?;
-    Ok(())
-}
-
-async fn cleanup_s3_old_files(path: &Path, bucket: String, client: &S3Client) -> Result<()> {
-    let mut objects = vec![];
-    let mut continuation_token = None;
-    loop {
-        println!("Continue listing files..");
-        let request = ListObjectsV2Request {
-            bucket: bucket.clone(),
-            continuation_token: continuation_token.take(),
-            delimiter: None,
-            encoding_type: None,
-            expected_bucket_owner: None,
-            fetch_owner: None,
-            max_keys: None,
-            prefix: None,
-            request_payer: None,
-            start_after: None,
-        };
-        let result = client.list_objects_v2(request).await?;
-
-        for object in result.contents.into_iter().flatten() {
-            let key = if let Some(key) = object.key {
-                key
-            } else {
-                continue;
-            };
-            if !path.join(&key).is_file() {
-                println!("Deleting {}...", key);
-                objects.push(ObjectIdentifier {
-                    key,
-                    version_id: None,
-                });
-            }
-        }
+fn diff(old_pak: Vec<String>, new_pak: Vec<String>, output: String) -> Result<()> {
+    let mut old_pak = PakReader::new(open_pak_files(old_pak)?)?;
+    let old_pedia = extract::gen_pedia(&mut old_pak)?;
+    let old_pedia_ex = extract::gen_pedia_ex(&old_pedia)?;
+    let mut new_pak = PakReader::new(open_pak_files(new_pak)?)?;
+    let new_pedia = extract::gen_pedia(&mut new_pak)?;
+    let new_pedia_ex = extract::gen_pedia_ex(&new_pedia)?;
+    let diff = extract::gen_diff(&old_pedia, &old_pedia_ex, &new_pedia, &new_pedia_ex)?;
+    std::fs::write(output, serde_json::to_string_pretty(&diff)?)?;
+    Ok(())
+}
 
-        if result.is_truncated.unwrap_or(false) {
-            continuation_token = result.next_continuation_token;
+fn publish_website(input: &Path, options: PublishOptions) -> Result<()> {
+    let mut publishers: Vec<Box<dyn Publisher>> = vec![];
+    if let Some(bucket) = options.s3 {
+        publishers.push(Box::new(S3Publisher::new(
+            bucket,
+            options.s3_region,
+            options.s3_endpoint,
+        )?));
+    }
+    if let Some(mirror) = options.mirror {
+        publishers.push(Box::new(LocalPublisher::new(mirror)));
+    }
+    if let Some(bundle) = options.bundle {
+        if bundle.ends_with(".zip") {
+            publishers.push(Box::new(ZipPublisher::new(bundle)));
         } else {
-            break;
+            publishers.push(Box::new(TarPublisher::new(bundle)));
         }
     }
-
-    if !objects.is_empty() {
-        let request = DeleteObjectsRequest {
-            bucket,
-            bypass_governance_retention: None,
-            delete: Delete {
-                objects,
-                quiet: Some(true),
-            },
-            expected_bucket_owner: None,
-            mfa: None,
-            request_payer: None,
-        };
-
-        client.delete_objects(request).await?;
+    for mut publisher in publishers {
+        publish(input, publisher.as_mut(), options.dry_run)?;
     }
-
     Ok(())
 }
 
-fn upload_s3_folder(path: &Path, bucket: String, client: &S3Client) -> Result<()> {
-    use futures::future::try_join_all;
-    use tokio::runtime::Runtime;
-    let mut futures = vec![];
-    for entry in WalkDir::new(path) {
-        let entry = entry?;
-        if !entry.file_type().is_file() {
-            continue;
-        }
-        let len = entry.metadata()?.len();
-        let mime = match entry
-            .path()
-            .extension()
-            .context("Missing extension")?
-            .to_str()
-            .context("Extension is not UTF-8")?
-        {
-            "html" => "text/html",
-            "css" => "text/css",
-            "js" => "text/javascript",
-            "png" => "image/png",
-            _ => bail!("Unknown extension"),
-        };
-
-        let key = entry
-            .path()
-            .strip_prefix(path)?
-            .to_str()
-            .context("Path contain non UTF-8 character")?
-            .to_owned();
-
-        println!("Uploading {}...", key);
-
-        futures.push(upload_s3(
-            entry.into_path(),
-            len,
-            mime,
-            bucket.clone(),
-            key,
-            client,
-        ));
-    }
+fn gen_quest_json(pak: Vec<String>) -> Result<()> {
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pedia = extract::gen_pedia(&mut pak)?;
+    let pedia_ex = extract::gen_pedia_ex(&pedia)?;
+    let json = serde_json::to_string_pretty(&pedia_ex.quests)?;
+    println!("{}", json);
+    Ok(())
+}
 
-    let rt = Runtime::new()?;
-    rt.block_on(try_join_all(futures))?;
-    println!("Finished uploading. Cleaning deleted files...");
-    rt.block_on(cleanup_s3_old_files(path, bucket, client))?;
+fn gen_behavior_json(pak: Vec<String>) -> Result<()> {
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pedia = extract::gen_pedia(&mut pak)?;
+    let behaviors: Vec<_> = pedia
+        .monsters
+        .iter()
+        .map(extract::monster_behavior)
+        .collect();
+    let json = serde_json::to_string_pretty(&behaviors)?;
+    println!("{}", json);
+    Ok(())
+}
 
+fn part_break_plan(pak: Vec<String>, monster: u32, sub_id: u32, quest: Option<i32>) -> Result<()> {
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pedia = extract::gen_pedia(&mut pak)?;
+    let pedia_ex = extract::gen_pedia_ex(&pedia)?;
+    let monster_data = pedia
+        .monsters
+        .iter()
+        .find(|m| m.id == monster && m.sub_id == sub_id)
+        .context("Monster not found")?;
+    let (vital_rate, parts_vital_rate, rank) = if let Some(quest_no) = quest {
+        let quest = pedia_ex
+            .quests
+            .iter()
+            .find(|q| q.param.quest_no == quest_no)
+            .context("Quest not found")?;
+        let stats = quest
+            .monster_stats
+            .iter()
+            .find(|s| s.em_type == rsz::EmTypes::Em(monster | sub_id << 8))
+            .context("The monster doesn't appear in the quest")?;
+        let rank = match quest.param.enemy_level {
+            rsz::EnemyLevel::High => rsz::QuestRank::High,
+            rsz::EnemyLevel::Village | rsz::EnemyLevel::Low => rsz::QuestRank::Low,
+        };
+        (stats.vital_rate, stats.parts_vital_rate, Some(rank))
+    } else {
+        (1.0, 1.0, None)
+    };
+    let plan = extract::break_plan(monster_data, &pedia_ex, vital_rate, parts_vital_rate, rank)?;
+    println!("{}", serde_json::to_string_pretty(&plan)?);
     Ok(())
 }
 
-fn gen_website(pak: Vec<String>, output: String, s3: Option<String>) -> Result<()> {
+fn crown_ranking(
+    pak: Vec<String>,
+    monster: u32,
+    sub_id: u32,
+    crown: extract::CrownKind,
+) -> Result<()> {
     let mut pak = PakReader::new(open_pak_files(pak)?)?;
     let pedia = extract::gen_pedia(&mut pak)?;
     let pedia_ex = extract::gen_pedia_ex(&pedia)?;
-    extract::gen_website(&pedia, &pedia_ex, &output)?;
-    extract::gen_resources(&mut pak, &Path::new(&output).to_owned().join("resources"))?;
-    if let Some(bucket) = s3 {
-        println!("Uploading to S3...");
-        let s3client = S3Client::new(Region::UsEast1);
-        upload_s3_folder(Path::new(&output), bucket, &s3client)?;
+    let rankings = extract::crown_rankings(&pedia_ex);
+    let ranking = rankings
+        .get(&(monster | sub_id << 8))
+        .context("The monster doesn't appear in any quest")?;
+    println!("Quest  | Mini   | Silver | Gold   | Name");
+    for rank in extract::sort_crown_ranking(ranking, crown) {
+        let name = rank
+            .quest
+            .name
+            .as_ref()
+            .and_then(|name| name.content.get(1))
+            .map_or("", |name| name.as_str());
+        println!(
+            "{:06} | {:5.1}% | {:5.1}% | {:5.1}% | {}",
+            rank.quest.param.quest_no, rank.odds.mini, rank.odds.silver, rank.odds.gold, name
+        );
     }
-
     Ok(())
 }
 
+fn gen_website(
+    pak: Vec<String>,
+    output: String,
+    language: Option<String>,
+    alternate: Vec<String>,
+    patch_notes: Option<String>,
+    publish: PublishOptions,
+) -> Result<()> {
+    let language = language
+        .map(|code| extract::language_from_code(&code))
+        .transpose()?;
+    let alternates = alternate
+        .iter()
+        .map(|alternate| {
+            let mut split = alternate.splitn(2, '=');
+            let code = split.next().context("Missing language code")?;
+            let url = split.next().context("Alternate site should be code=url")?;
+            Ok((extract::language_from_code(code)?, url.to_owned()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let options = extract::SiteOptions {
+        language,
+        alternates,
+    };
+    let patch_notes: Option<extract::PediaDiff> = patch_notes
+        .map(|path| -> Result<_> {
+            serde_json::from_slice(&std::fs::read(&path)?)
+                .with_context(|| format!("Failed to read patch notes from {}", path))
+        })
+        .transpose()?;
+
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pedia = extract::gen_pedia(&mut pak)?;
+    let pedia_ex = extract::gen_pedia_ex(&pedia)?;
+    let resources = extract::gen_resources(&pak, &Path::new(&output).to_owned().join("resources"))?;
+    extract::gen_website(
+        &pedia,
+        &pedia_ex,
+        &resources,
+        &output,
+        &options,
+        patch_notes.as_ref(),
+    )?;
+    publish_website(Path::new(&output), publish)
+}
+
 fn read_tdb(tdb: String) -> Result<()> {
     let _ = Tdb::new(File::open(tdb)?)?;
     Ok(())
@@ -458,129 +673,178 @@ fn read_msg(msg: String) -> Result<()> {
 }
 
 fn scan_msg(pak: Vec<String>, output: String) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
     std::fs::create_dir_all(&output)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 8 || file[4..8] != b"GMSG"[..] {
-            continue;
+    pak.par_files().try_for_each(|(i, file)| {
+        let file = file?;
+        if sniff(&file) != FileType::Msg {
+            return Ok(());
         }
         let msg = Msg::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
         std::fs::write(
             PathBuf::from(&output).join(format!("{}.txt", i.short_string())),
             serde_json::to_string_pretty(&msg)?,
         )?;
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
 fn grep_msg(pak: Vec<String>, pattern: String) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
     use regex::*;
     let regex = RegexBuilder::new(&pattern).build()?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 8 || file[4..8] != b"GMSG"[..] {
-            continue;
-        }
-        let msg = Msg::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
-        for entry in &msg.entries {
-            for text in &entry.content {
-                if regex.is_match(text) {
-                    println!("Found @ {:?}", i);
-                }
+    let found = pak
+        .par_files()
+        .map(|(i, file)| {
+            let file = file?;
+            if sniff(&file) != FileType::Msg {
+                return Ok((i, 0));
             }
+            let msg = Msg::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
+            let count = msg
+                .entries
+                .iter()
+                .flat_map(|entry| &entry.content)
+                .filter(|text| regex.is_match(text))
+                .count();
+            Ok((i, count))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for (i, count) in found {
+        for _ in 0..count {
+            println!("Found @ {:?}", i);
         }
     }
     Ok(())
 }
 
 fn scan_mesh(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 4 || file[0..4] != b"MESH"[..] {
-            continue;
-        }
-        let _ = Mesh::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    let errors = pak
+        .par_files()
+        .map(|(i, file)| {
+            let file = file?;
+            if sniff(&file) != FileType::Mesh {
+                return Ok(None);
+            }
+            let result = Mesh::new(Cursor::new(&file)).and_then(|mesh| mesh.validate());
+            Ok(result.err().map(|e| (i, e)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for (i, e) in errors.into_iter().flatten() {
+        println!("{:?}: {:#}", i, e);
     }
     Ok(())
 }
 
 fn scan_rcol(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 4 || file[0..4] != b"RCOL"[..] {
-            continue;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    pak.par_files().try_for_each(|(i, file)| {
+        let file = file?;
+        if sniff(&file) == FileType::Rcol {
+            let _ = Rcol::new(Cursor::new(&file), false).context(format!("at {:?}", i))?;
         }
-        let _ = Rcol::new(Cursor::new(&file), false).context(format!("at {:?}", i))?;
-    }
-
-    Ok(())
+        Ok(())
+    })
 }
 
 fn scan_tex(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 4 || file[0..4] != b"TEX\0"[..] {
-            continue;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    pak.par_files().try_for_each(|(i, file)| {
+        let file = file?;
+        if sniff(&file) == FileType::Tex {
+            let _ = Tex::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
         }
-        let _ = Tex::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
-    }
-
-    Ok(())
+        Ok(())
+    })
 }
 
 fn scan_gui(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 8 || file[4..8] != b"GUIR"[..] {
-            continue;
-        }
-        let _ = Gui::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    let files = pak
+        .par_files()
+        .map(|(i, file)| {
+            let file = file?;
+            if sniff(&file) != FileType::Gui {
+                return Ok(vec![]);
+            }
+            let gui = Gui::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
+            let play_objects = std::iter::once(&gui.root).chain(
+                gui.controls
+                    .iter()
+                    .flat_map(|control| control.play_objects.iter()),
+            );
+            Ok(play_objects
+                .flat_map(|play_object| {
+                    play_object
+                        .properties
+                        .iter()
+                        .chain(play_object.variables.iter())
+                })
+                .filter_map(|field| field.value.unknown_type())
+                .collect())
+        })
+        .collect::<Result<Vec<Vec<u32>>>>()?;
+
+    let mut unknown_types: BTreeMap<u32, usize> = BTreeMap::new();
+    for t in files.into_iter().flatten() {
+        *unknown_types.entry(t).or_default() += 1;
+    }
+
+    if unknown_types.is_empty() {
+        println!("All field value types are known");
+    }
+    for (t, count) in unknown_types {
+        println!("Unknown field value type {}: {} fields", t, count);
     }
 
     Ok(())
 }
 
 fn scan_uvs(pak: Vec<String>) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if file.len() < 4 || file[0..4] != b".SVU"[..] {
-            continue;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    pak.par_files().try_for_each(|(i, file)| {
+        let file = file?;
+        if sniff(&file) == FileType::Uvs {
+            let _ = Uvs::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
         }
-        let _ = Uvs::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
-    }
+        Ok(())
+    })
+}
 
-    Ok(())
+fn scan_mdf(pak: Vec<String>) -> Result<()> {
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    pak.par_files().try_for_each(|(i, file)| {
+        let file = file?;
+        if sniff(&file) == FileType::Mdf {
+            let _ = Mdf2::new(Cursor::new(&file)).context(format!("at {:?}", i))?;
+        }
+        Ok(())
+    })
 }
 
 fn grep(pak: Vec<String>, pattern: String) -> Result<()> {
     use regex::bytes::*;
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
     println!("Searching for patterns \"{}\"", &pattern);
     let re = RegexBuilder::new(&pattern).unicode(false).build()?;
-    for i in pak.all_file_indexs() {
-        let file = pak.read_file(i)?;
-        if re.is_match(&file) {
-            println!("Matched @ {:?}", i);
-        }
+    let matched = pak
+        .par_files()
+        .map(|(i, file)| Ok(re.is_match(&file?).then(|| i)))
+        .collect::<Result<Vec<_>>>()?;
+    for i in matched.into_iter().flatten() {
+        println!("Matched @ {:?}", i);
     }
     Ok(())
 }
 
-fn search_path(pak: Vec<String>) -> Result<()> {
-    let pak = Mutex::new(PakReader::new(open_pak_files(pak)?)?);
-    let indexs = pak.lock().unwrap().all_file_indexs();
+fn search_path(pak: Vec<String>, dict: Option<String>) -> Result<()> {
+    let pak = PakReader::new(open_pak_files(pak)?)?;
     let counter = std::sync::atomic::AtomicU32::new(0);
-    let paths: std::collections::BTreeMap<String, Vec<I18nPakFileIndex>> = indexs
-        .into_par_iter()
-        .map(|index| {
-            let file = pak.lock().unwrap().read_file(index)?;
+    let paths: std::collections::BTreeMap<String, Vec<I18nPakFileIndex>> = pak
+        .par_files()
+        .map(|(_, file)| {
+            let file = file?;
             let mut paths = vec![];
             for &suffix in suffix::SUFFIX_MAP.keys() {
                 let mut full_suffix = vec![0; (suffix.len() + 2) * 2];
@@ -612,7 +876,7 @@ fn search_path(pak: Vec<String>) -> Result<()> {
                     for pos in (begin..end).step_by(2) {
                         path.push(char::from(file[pos]));
                     }
-                    let index = pak.lock().unwrap().find_file_i18n(&path)?;
+                    let index = pak.find_file_i18n(&path)?;
                     paths.push((path, index));
                 }
             }
@@ -627,10 +891,80 @@ fn search_path(pak: Vec<String>) -> Result<()> {
         .flat_map_iter(|paths: Result<_>| paths.unwrap())
         .collect();
 
-    for (path, index) in paths {
+    for (path, index) in &paths {
         println!("{} $ {:?}", path, index);
     }
 
+    if let Some(dict_path) = dict {
+        let mut dict = PathDict::load(&dict_path)?;
+        let added: usize = paths.keys().map(|path| dict.add_path(&pak, path)).sum();
+        dict.save(&dict_path)?;
+        eprintln!("Added {} paths to the dictionary", added);
+    }
+
+    Ok(())
+}
+
+fn add_paths(pak: Vec<String>, dict_path: String, list: String) -> Result<()> {
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    let mut dict = PathDict::load(&dict_path)?;
+    let mut added = 0;
+    for line in BufReader::new(File::open(list)?).lines() {
+        let line = line?;
+        if let Some(path) = line.split(' ').next().filter(|path| !path.is_empty()) {
+            added += dict.add_path(&pak, path);
+        }
+    }
+    dict.save(&dict_path)?;
+    println!("Added {} paths, {} in total", added, dict.paths.len());
+    Ok(())
+}
+
+fn coverage(pak: Vec<String>, dict: String) -> Result<()> {
+    let pak = PakReader::new(open_pak_files(pak)?)?;
+    let dict = PathDict::load(dict)?;
+    let named = dict.resolve(&pak);
+
+    let files = pak
+        .par_files()
+        .map(|(index, file)| Ok((index, sniff(&file?))))
+        .collect::<Result<Vec<_>>>()?;
+    // version -> file type -> (named, unnamed)
+    let mut report: BTreeMap<usize, BTreeMap<FileType, (usize, usize)>> = BTreeMap::new();
+    for (index, file_type) in files {
+        let entry = report
+            .entry(index.version())
+            .or_default()
+            .entry(file_type)
+            .or_default();
+        if named.contains_key(&index) {
+            entry.0 += 1;
+        } else {
+            entry.1 += 1;
+        }
+    }
+
+    let percent = |named: usize, unnamed: usize| named as f64 * 100.0 / (named + unnamed) as f64;
+    for (version, types) in report {
+        let named: usize = types.values().map(|(named, _)| named).sum();
+        let unnamed: usize = types.values().map(|(_, unnamed)| unnamed).sum();
+        println!(
+            "pak {:02}: {}/{} named ({:.1}%)",
+            version,
+            named,
+            named + unnamed,
+            percent(named, unnamed)
+        );
+        for (file_type, (named, unnamed)) in types {
+            println!(
+                "    {:8} {}/{} named ({:.1}%)",
+                file_type.name(),
+                named,
+                named + unnamed,
+                percent(named, unnamed)
+            );
+        }
+    }
     Ok(())
 }
 
@@ -658,19 +992,142 @@ fn dump_tree(pak: Vec<String>, list: String, output: String) -> Result<()> {
     }
 
     for index in unvisited {
-        let path = PathBuf::from(&output)
-            .join("_unknown")
-            .join(index.short_string());
+        let file = pak.read_file(index)?;
+        let file_name = match sniff(&file) {
+            FileType::Unknown => index.short_string(),
+            file_type => format!("{}.{}", index.short_string(), file_type.name()),
+        };
+        let path = PathBuf::from(&output).join("_unknown").join(file_name);
         std::fs::create_dir_all(path.parent().context("no parent")?)?;
-        std::fs::write(path, &pak.read_file(index)?)?;
+        std::fs::write(path, &file)?;
     }
 
     Ok(())
 }
 
+fn gen_dep_graph(pak: Vec<String>, list: String, output: String) -> Result<()> {
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let list = File::open(list)?;
+    let roots = BufReader::new(list)
+        .lines()
+        .map(|line| Ok(line?.split(' ').next().context("Empty line")?.to_owned()))
+        .collect::<Result<Vec<_>>>()?;
+    let graph = DepGraph::build(&mut pak, roots)?;
+    eprintln!(
+        "{} files, {} missing, {} failed to parse",
+        graph.edges.len(),
+        graph.missing.len(),
+        graph.broken.len()
+    );
+    std::fs::write(output, serde_json::to_string_pretty(&graph)?)?;
+    Ok(())
+}
+
+fn load_dep_graph(graph: String) -> Result<DepGraph> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(graph)?))?)
+}
+
+fn query_dep_graph(graph: String, path: String, reverse: bool, recursive: bool) -> Result<()> {
+    let graph = load_dep_graph(graph)?;
+    let result = if reverse {
+        graph.dependents(&path, recursive)
+    } else {
+        graph.dependencies(&path, recursive)
+    };
+    for path in result {
+        let mark = if graph.missing.contains(path) {
+            " (missing)"
+        } else {
+            ""
+        };
+        println!("{}{}", path, mark);
+    }
+    Ok(())
+}
+
+fn export_dep_graph(graph: String, format: DepGraphFormat, output: String) -> Result<()> {
+    let graph = load_dep_graph(graph)?;
+    let content = match format {
+        DepGraphFormat::Dot => graph.to_dot(),
+        DepGraphFormat::Json => {
+            let edges: Vec<(&str, &str)> = graph
+                .edges
+                .iter()
+                .flat_map(|(from, to)| to.iter().map(move |to| (from.as_str(), to.as_str())))
+                .collect();
+            serde_json::to_string_pretty(&edges)?
+        }
+    };
+    std::fs::write(output, content)?;
+    Ok(())
+}
+
 fn dump_mesh(mesh: String, output: String) -> Result<()> {
     let mesh = Mesh::new(File::open(mesh)?)?;
-    mesh.dump(output)?;
+    mesh.dump(&output, None)?;
+    Ok(())
+}
+
+fn dump_mdf(mdf: String) -> Result<()> {
+    let mdf = Mdf2::new(File::open(mdf)?)?;
+    println!("{}", serde_json::to_string_pretty(&mdf)?);
+    Ok(())
+}
+
+fn export_model(pak: Vec<String>, mesh: String, mdf: Option<String>, output: String) -> Result<()> {
+    use std::io::Write;
+    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let output = PathBuf::from(output);
+    std::fs::create_dir_all(&output)?;
+
+    let mdf_path = mdf.unwrap_or_else(|| {
+        let stem = mesh.strip_suffix(".mesh").unwrap_or(&mesh);
+        format!("{}.mdf2", stem)
+    });
+    let mesh_index = pak.find_file(&mesh)?;
+    let mesh = Mesh::new(Cursor::new(pak.read_file(mesh_index)?))?;
+    let mdf_index = pak.find_file(&mdf_path)?;
+    let mdf = Mdf2::new(Cursor::new(pak.read_file(mdf_index)?))?;
+
+    let mut mtl = File::create(output.join("model.mtl"))?;
+    for name in &mesh.material_names {
+        writeln!(mtl, "newmtl {}", name)?;
+        let material = if let Some(material) = mdf.material(name) {
+            material
+        } else {
+            eprintln!("Material {} not found in {}", name, mdf_path);
+            continue;
+        };
+        let textures = [
+            ("map_Kd", material.albedo()),
+            ("norm", material.normal()),
+            ("# mask", material.mask()),
+        ];
+        for &(key, texture) in &textures {
+            let texture = if let Some(texture) = texture {
+                texture
+            } else {
+                continue;
+            };
+            let file_name = format!(
+                "{}.png",
+                texture
+                    .trim_end_matches(".tex")
+                    .replace(&['/', '\\'][..], "_")
+            );
+            let result = (|| {
+                let tex = Tex::new(Cursor::new(pak.read_file(pak.find_file(texture)?)?))?;
+                tex.save_png(0, 0, &output.join(&file_name))
+            })();
+            match result {
+                Ok(()) => writeln!(mtl, "{} {}", key, file_name)?,
+                Err(e) => eprintln!("Failed to export texture {}: {:#}", texture, e),
+            }
+        }
+        writeln!(mtl)?;
+    }
+
+    mesh.dump(output.join("model.obj"), Some("model.mtl"))?;
     Ok(())
 }
 
@@ -696,6 +1153,21 @@ fn dump_gui(gui: String) -> Result<()> {
     Ok(())
 }
 
+fn dump_scene(scene: String, json: bool) -> Result<()> {
+    let content = std::fs::read(&scene)?;
+    let scene = if let Ok(scn) = Scn::new(Cursor::new(&content)) {
+        Scene::from_scn(&scn)?
+    } else {
+        Scene::from_pfb(&Pfb::new(Cursor::new(&content))?)?
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&scene)?);
+    } else {
+        scene.dump_tree();
+    }
+    Ok(())
+}
+
 fn dump_meat(_mesh: String, _rcol: String, _output: String) -> Result<()> {
     /*use std::io::*;
     let mesh = Mesh::new(File::open(mesh)?)?;
@@ -778,9 +1250,9 @@ fn gen_meat(pak: Vec<String>, index: u32, output: String) -> Result<()> {
 }
 
 fn gen_resources(pak: Vec<String>, output: String) -> Result<()> {
-    let mut pak = PakReader::new(open_pak_files(pak)?)?;
+    let pak = PakReader::new(open_pak_files(pak)?)?;
 
-    extract::gen_resources(&mut pak, Path::new(&output))?;
+    extract::gen_resources(&pak, Path::new(&output))?;
 
     Ok(())
 }
@@ -803,25 +1275,76 @@ fn main() -> Result<()> {
             output,
         } => dump_index(pak, version, index, output),
         Mhrice::Scan { pak } => scan(pak),
+        Mhrice::Stat { pak } => stat(pak),
         Mhrice::GenJson { pak } => gen_json(pak),
-        Mhrice::GenWebsite { pak, output, s3 } => gen_website(pak, output, s3),
+        Mhrice::GenQuestJson { pak } => gen_quest_json(pak),
+        Mhrice::GenBehaviorJson { pak } => gen_behavior_json(pak),
+        Mhrice::PartBreakPlan {
+            pak,
+            monster,
+            sub_id,
+            quest,
+        } => part_break_plan(pak, monster, sub_id, quest),
+        Mhrice::CrownRanking {
+            pak,
+            monster,
+            sub_id,
+            crown,
+        } => crown_ranking(pak, monster, sub_id, crown),
+        Mhrice::GenWebsite {
+            pak,
+            output,
+            language,
+            alternate,
+            patch_notes,
+            publish,
+        } => gen_website(pak, output, language, alternate, patch_notes, publish),
+        Mhrice::Publish { input, publish } => publish_website(Path::new(&input), publish),
+        Mhrice::Diff {
+            old_pak,
+            new_pak,
+            output,
+        } => diff(old_pak, new_pak, output),
         Mhrice::ReadTdb { tdb } => read_tdb(tdb),
         Mhrice::ReadMsg { msg } => read_msg(msg),
         Mhrice::ScanMsg { pak, output } => scan_msg(pak, output),
         Mhrice::GrepMsg { pak, pattern } => grep_msg(pak, pattern),
         Mhrice::Grep { pak, pattern } => grep(pak, pattern),
-        Mhrice::SearchPath { pak } => search_path(pak),
+        Mhrice::SearchPath { pak, dict } => search_path(pak, dict),
+        Mhrice::AddPaths { pak, dict, list } => add_paths(pak, dict, list),
+        Mhrice::Coverage { pak, dict } => coverage(pak, dict),
         Mhrice::DumpTree { pak, list, output } => dump_tree(pak, list, output),
+        Mhrice::GenDepGraph { pak, list, output } => gen_dep_graph(pak, list, output),
+        Mhrice::QueryDepGraph {
+            graph,
+            path,
+            reverse,
+            recursive,
+        } => query_dep_graph(graph, path, reverse, recursive),
+        Mhrice::ExportDepGraph {
+            graph,
+            format,
+            output,
+        } => export_dep_graph(graph, format, output),
         Mhrice::ScanMesh { pak } => scan_mesh(pak),
         Mhrice::ScanRcol { pak } => scan_rcol(pak),
         Mhrice::ScanTex { pak } => scan_tex(pak),
         Mhrice::ScanGui { pak } => scan_gui(pak),
         Mhrice::ScanUvs { pak } => scan_uvs(pak),
         Mhrice::DumpMesh { mesh, output } => dump_mesh(mesh, output),
+        Mhrice::ScanMdf { pak } => scan_mdf(pak),
+        Mhrice::DumpMdf { mdf } => dump_mdf(mdf),
+        Mhrice::ExportModel {
+            pak,
+            mesh,
+            mdf,
+            output,
+        } => export_model(pak, mesh, mdf, output),
         Mhrice::DumpRcol { rcol } => dump_rcol(rcol),
         Mhrice::DumpMeat { mesh, rcol, output } => dump_meat(mesh, rcol, output),
         Mhrice::DumpTex { tex, output } => dump_tex(tex, output),
         Mhrice::DumpGui { gui } => dump_gui(gui),
+        Mhrice::DumpScene { scene, json } => dump_scene(scene, json),
         Mhrice::GenMeat { pak, index, output } => gen_meat(pak, index, output),
         Mhrice::GenResources { pak, output } => gen_resources(pak, output),
         Mhrice::Hash { input, utf16 } => {
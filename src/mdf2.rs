@@ -0,0 +1,191 @@
+use crate::file_ext::*;
+use anyhow::*;
+use serde::*;
+use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom};
+
+#[derive(Debug, Serialize)]
+pub struct MaterialParam {
+    pub name: String,
+    pub value: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextureBinding {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Material {
+    pub name: String,
+    pub shader_type: u32,
+    pub flags: u32,
+    pub shader: String,
+    pub params: Vec<MaterialParam>,
+    pub textures: Vec<TextureBinding>,
+}
+
+impl Material {
+    fn texture_where(&self, f: impl Fn(&str) -> bool) -> Option<&str> {
+        self.textures
+            .iter()
+            .find(|texture| f(&texture.name))
+            .map(|texture| texture.path.as_str())
+    }
+
+    pub fn albedo(&self) -> Option<&str> {
+        self.texture_where(|name| {
+            name.starts_with("BaseDielectricMap")
+                || name.starts_with("BaseMetalMap")
+                || name.starts_with("AlbedoMap")
+        })
+    }
+
+    pub fn normal(&self) -> Option<&str> {
+        self.texture_where(|name| name.starts_with("NormalRoughness") || name == "NormalMap")
+    }
+
+    pub fn mask(&self) -> Option<&str> {
+        self.texture_where(|name| {
+            name.starts_with("AlphaTranslucentOcclusionCavityMap") || name.contains("Mask")
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Mdf2 {
+    pub materials: Vec<Material>,
+}
+
+fn read_string_at<F: Read + Seek>(file: &mut F, offset: u64) -> Result<String> {
+    let back = file.tell()?;
+    file.seek(SeekFrom::Start(offset))?;
+    let s = file.read_u16str()?;
+    file.seek(SeekFrom::Start(back))?;
+    Ok(s)
+}
+
+impl Mdf2 {
+    pub fn new<F: Read + Seek>(mut file: F) -> Result<Mdf2> {
+        if &file.read_magic()? != b"MDF\0" {
+            bail!("Wrong magic for MDF2");
+        }
+        let version = file.read_u16()?;
+        if version != 1 {
+            bail!("Unexpected MDF2 version {}", version);
+        }
+        let material_count = file.read_u16()?;
+        let x = file.read_u64()?;
+        if x != 0 {
+            bail!("Expected 0");
+        }
+
+        struct MaterialHeader {
+            name_offset: u64,
+            params_size: u32,
+            param_count: u32,
+            texture_count: u32,
+            shader_type: u32,
+            flags: u32,
+            param_header_offset: u64,
+            texture_header_offset: u64,
+            params_offset: u64,
+            shader_offset: u64,
+        }
+
+        let headers = (0..material_count)
+            .map(|_| {
+                let name_offset = file.read_u64()?;
+                let _name_hash = file.read_u32()?;
+                let params_size = file.read_u32()?;
+                let param_count = file.read_u32()?;
+                let texture_count = file.read_u32()?;
+                let _gpbf_name_count = file.read_u32()?;
+                let _gpbf_data_count = file.read_u32()?;
+                let shader_type = file.read_u32()?;
+                let flags = file.read_u32()?;
+                let param_header_offset = file.read_u64()?;
+                let texture_header_offset = file.read_u64()?;
+                let _gpbf_offset = file.read_u64()?;
+                let params_offset = file.read_u64()?;
+                let shader_offset = file.read_u64()?;
+                Ok(MaterialHeader {
+                    name_offset,
+                    params_size,
+                    param_count,
+                    texture_count,
+                    shader_type,
+                    flags,
+                    param_header_offset,
+                    texture_header_offset,
+                    params_offset,
+                    shader_offset,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let materials = headers
+            .into_iter()
+            .map(|header| {
+                file.seek(SeekFrom::Start(header.texture_header_offset))?;
+                let textures = (0..header.texture_count)
+                    .map(|_| {
+                        let name_offset = file.read_u64()?;
+                        let _hash = file.read_u32()?;
+                        let _ascii_hash = file.read_u32()?;
+                        let path_offset = file.read_u64()?;
+                        let _ = file.read_u64()?;
+                        Ok(TextureBinding {
+                            name: read_string_at(&mut file, name_offset)?,
+                            path: read_string_at(&mut file, path_offset)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                file.seek(SeekFrom::Start(header.params_offset))?;
+                let mut param_buffer = vec![0; usize::try_from(header.params_size)?];
+                file.read_exact(&mut param_buffer)?;
+
+                file.seek(SeekFrom::Start(header.param_header_offset))?;
+                let params = (0..header.param_count)
+                    .map(|_| {
+                        let name_offset = file.read_u64()?;
+                        let _hash = file.read_u32()?;
+                        let _ascii_hash = file.read_u32()?;
+                        let value_offset = usize::try_from(file.read_u32()?)?;
+                        let component_count = file.read_u32()?;
+                        if component_count != 1 && component_count != 4 {
+                            bail!("Unexpected component count {}", component_count);
+                        }
+                        let mut value_buffer = param_buffer
+                            .get(value_offset..)
+                            .context("Param value out of bound")?;
+                        let value = (0..component_count)
+                            .map(|_| value_buffer.read_f32())
+                            .collect::<Result<Vec<_>>>()?;
+                        Ok(MaterialParam {
+                            name: read_string_at(&mut file, name_offset)?,
+                            value,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Material {
+                    name: read_string_at(&mut file, header.name_offset)?,
+                    shader_type: header.shader_type,
+                    flags: header.flags,
+                    shader: read_string_at(&mut file, header.shader_offset)?,
+                    params,
+                    textures,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Mdf2 { materials })
+    }
+
+    pub fn material(&self, name: &str) -> Option<&Material> {
+        self.materials.iter().find(|material| material.name == name)
+    }
+}
@@ -4,10 +4,12 @@ use nalgebra_glm::*;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
 
 #[derive(Clone)]
 pub struct Model {
-    pub vertex_count: u32,
+    pub material_index: u16,
+    pub vertex_count: u32, // this is actually the index count
     pub index_buffer_start: u32,
     pub vertex_buffer_start: u32,
 }
@@ -28,6 +30,85 @@ pub struct VertexLayout {
     pub offset: u32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexUsage {
+    Position,
+    NormalTangent,
+    Uv,
+    Uv2,
+    Weight,
+    Color,
+    Unknown(u16),
+}
+
+impl VertexLayout {
+    pub fn usage(&self) -> VertexUsage {
+        match self.usage {
+            0 => VertexUsage::Position,
+            1 => VertexUsage::NormalTangent,
+            2 => VertexUsage::Uv,
+            3 => VertexUsage::Uv2,
+            4 => VertexUsage::Weight,
+            5 => VertexUsage::Color,
+            x => VertexUsage::Unknown(x),
+        }
+    }
+
+    /// Whether the width is one we know how to decode for the usage
+    fn is_known(&self) -> bool {
+        match self.usage() {
+            VertexUsage::Position => self.width == 12,
+            VertexUsage::NormalTangent => self.width == 4 || self.width == 8,
+            VertexUsage::Uv | VertexUsage::Uv2 => self.width == 4,
+            VertexUsage::Weight => self.width == 8 || self.width == 16,
+            VertexUsage::Color => self.width == 4,
+            VertexUsage::Unknown(_) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFormat {
+    U16,
+    U32,
+}
+
+impl IndexFormat {
+    pub fn width(self) -> usize {
+        match self {
+            IndexFormat::U16 => 2,
+            IndexFormat::U32 => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VertexWeight {
+    pub bones: Vec<u8>, // indexes into bone_remap
+    pub weights: Vec<f32>,
+}
+
+/// An attribute with an unknown usage or width, kept as raw bytes
+#[derive(Debug, Clone)]
+pub struct RawAttribute {
+    pub usage: VertexUsage,
+    pub width: u16,
+    pub data: Vec<u8>,
+}
+
+/// Decoded vertex attributes. Attributes missing from the mesh are left empty.
+#[derive(Debug, Clone, Default)]
+pub struct Vertices {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub tangents: Vec<Vec4>,
+    pub uvs: Vec<Vec2>,
+    pub uvs2: Vec<Vec2>,
+    pub weights: Vec<VertexWeight>,
+    pub colors: Vec<[u8; 4]>,
+    pub raw: Vec<RawAttribute>,
+}
+
 pub struct Point4 {
     pub x: f32,
     pub y: f32,
@@ -55,6 +136,10 @@ pub struct Mesh {
     pub vertex_layouts: Vec<VertexLayout>,
     pub vertex_buffer: Vec<u8>,
     pub index_buffer: Vec<u8>,
+    pub index_format: IndexFormat,
+    /// Set when the index width could not be told from the data and U16 was assumed
+    pub index_format_doubt: Option<&'static str>,
+    pub material_names: Vec<String>,
     pub gs: Vec<G>,
     pub bones: Vec<Bone>,
     pub bone_names: HashMap<String, usize>,
@@ -108,7 +193,8 @@ impl Mesh {
 
             let models = (0..model_count)
                 .map(|_| {
-                    let _j = file.read_u32()?;
+                    let material_index = file.read_u16()?;
+                    let _ = file.read_u16()?;
                     let face_count = file.read_u32()?;
                     let index_buffer_start = file.read_u32()?;
                     let vertex_buffer_start = file.read_u32()?;
@@ -117,6 +203,7 @@ impl Mesh {
                     let _ = file.read_u32()?;
 
                     Ok(Model {
+                        material_index,
                         vertex_count: face_count,
                         index_buffer_start,
                         vertex_buffer_start,
@@ -459,12 +546,14 @@ impl Mesh {
             file.read_exact(&mut i_buffer)?;
         }
 
-        if model_names_offset != 0 {
+        let model_names = if model_names_offset != 0 {
             file.seek_assert_align_up(model_names_offset, 16)?;
             (0..model_name_count)
                 .map(|_| file.read_u16())
-                .collect::<Result<Vec<_>>>()?;
-        }
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            vec![]
+        };
 
         let bone_names = if bone_names_offset != 0 {
             file.seek_assert_align_up(bone_names_offset, 16)?;
@@ -526,6 +615,16 @@ impl Mesh {
             bail!("Bone name collision")
         }
 
+        let material_names = model_names
+            .into_iter()
+            .map(|name_index| {
+                Ok(strings
+                    .get(usize::from(name_index))
+                    .context("Name out of bound")?
+                    .clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let gs = if g_offset != 0 {
             file.seek_assert_align_up(g_offset, 16)?;
 
@@ -591,104 +690,282 @@ impl Mesh {
 
         file.seek_assert_align_up(total_len, 16)?;
 
-        Ok(Mesh {
+        // There is no known flag for the index width, so it is inferred from the data
+        let index_end = main_model_lods
+            .iter()
+            .chain(&aux_model_lods)
+            .flat_map(|lod| &lod.model_groups)
+            .flat_map(|group| &group.models)
+            .map(|model| u64::from(model.index_buffer_start) + u64::from(model.vertex_count))
+            .max()
+            .unwrap_or(0);
+        let index_end = usize::try_from(index_end)?;
+
+        let mut mesh = Mesh {
             aux_model_lods,
             main_model_lods,
             vertex_layouts,
             vertex_buffer,
             index_buffer,
+            index_format: IndexFormat::U16,
+            index_format_doubt: None,
+            material_names,
             gs,
             bones,
             bone_names,
             bone_remap,
-        })
+        };
+        if index_end * 2 > index_buffer_len {
+            mesh.index_format_doubt = Some("Index buffer too short");
+        } else if index_end * 4 <= index_buffer_len {
+            match mesh.detect_index_format()? {
+                Ok(format) => mesh.index_format = format,
+                Err(doubt) => mesh.index_format_doubt = Some(doubt),
+            }
+        }
+        Ok(mesh)
     }
 
-    pub fn dump(&self, output: String) -> Result<()> {
-        let mut output = std::fs::File::create(output)?;
-        let position = self
-            .vertex_layouts
+    /// Picks the index width under which every index stays within the vertices.
+    /// Returns the doubt if both or neither width fit, so that validate reports it.
+    fn detect_index_format(&self) -> Result<std::result::Result<IndexFormat, &'static str>> {
+        let vertex_count = self.vertex_count()?;
+        let fits = |format| -> Result<bool> {
+            for model in self.models() {
+                let max = self.read_indices(model, format)?.into_iter().max();
+                if max.map_or(false, |max| max as usize >= vertex_count) {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        match (fits(IndexFormat::U16)?, fits(IndexFormat::U32)?) {
+            (true, false) => Ok(Ok(IndexFormat::U16)),
+            (false, true) => Ok(Ok(IndexFormat::U32)),
+            (true, true) => Ok(Err("Ambiguous index width")),
+            (false, false) => Ok(Err("No index width fits the vertex count")),
+        }
+    }
+
+    fn models(&self) -> impl Iterator<Item = &Model> {
+        self.main_model_lods
             .iter()
-            .find(|layout| layout.usage == 0)
-            .context("No position data")?;
+            .chain(&self.aux_model_lods)
+            .flat_map(|lod| &lod.model_groups)
+            .flat_map(|group| &group.models)
+    }
 
-        let normal = self
-            .vertex_layouts
+    fn layout(&self, usage: VertexUsage) -> Option<&VertexLayout> {
+        self.vertex_layouts
             .iter()
-            .find(|layout| layout.usage == 1)
-            .context("No position data")?;
+            .find(|layout| layout.usage() == usage)
+    }
 
-        let texcoord = self
+    /// The number of vertices, derived from where the position data ends
+    pub fn vertex_count(&self) -> Result<usize> {
+        let position = self
+            .layout(VertexUsage::Position)
+            .context("No position data")?;
+        let end = self
             .vertex_layouts
             .iter()
-            .find(|layout| layout.usage == 2)
-            .context("No position data")?;
+            .map(|layout| layout.offset)
+            .filter(|&offset| offset > position.offset)
+            .min()
+            .map_or(Ok(self.vertex_buffer.len()), usize::try_from)?;
+        Ok((end - usize::try_from(position.offset)?) / usize::from(position.width))
+    }
 
-        let vertex_count = (self.vertex_layouts[1].offset - self.vertex_layouts[0].offset)
-            / self.vertex_layouts[0].width as u32;
+    fn attribute(&self, layout: &VertexLayout, vertex_count: usize) -> Result<&[u8]> {
+        let begin = usize::try_from(layout.offset)?;
+        let end = begin + usize::from(layout.width) * vertex_count;
+        self.vertex_buffer
+            .get(begin..end)
+            .with_context(|| format!("Vertex data for {:?} out of bound", layout.usage()))
+    }
 
-        if position.width != 12 {
-            bail!("Unexpected width");
+    /// Decodes all vertex attributes according to the layouts.
+    /// Attributes we don't understand are kept in `raw` instead.
+    pub fn vertices(&self) -> Result<Vertices> {
+        let vertex_count = self.vertex_count()?;
+        let mut vertices = Vertices::default();
+        for layout in &self.vertex_layouts {
+            let buffer = self.attribute(layout, vertex_count)?;
+            if !layout.is_known() {
+                vertices.raw.push(RawAttribute {
+                    usage: layout.usage(),
+                    width: layout.width,
+                    data: buffer.to_vec(),
+                });
+                continue;
+            }
+            let width = usize::from(layout.width);
+            let chunks = buffer.chunks_exact(width);
+            match layout.usage() {
+                VertexUsage::Position => {
+                    vertices.positions = chunks
+                        .map(|mut v| v.read_f32vec3())
+                        .collect::<Result<_>>()?
+                }
+                VertexUsage::NormalTangent => {
+                    fn snorm(v: &[u8]) -> Vec<f32> {
+                        v.iter().map(|&x| f32::from(x as i8) / 127.0).collect()
+                    }
+                    for v in chunks {
+                        let normal = snorm(&v[0..4]);
+                        vertices.normals.push(vec3(normal[0], normal[1], normal[2]));
+                        if width == 8 {
+                            let tangent = snorm(&v[4..8]);
+                            vertices
+                                .tangents
+                                .push(vec4(tangent[0], tangent[1], tangent[2], tangent[3]));
+                        }
+                    }
+                }
+                VertexUsage::Uv | VertexUsage::Uv2 => {
+                    let uvs = chunks
+                        .map(|mut uv| {
+                            let u = half::f16::from_bits(uv.read_u16()?).to_f32();
+                            let v = half::f16::from_bits(uv.read_u16()?).to_f32();
+                            Ok(vec2(u, v))
+                        })
+                        .collect::<Result<_>>()?;
+                    if layout.usage() == VertexUsage::Uv {
+                        vertices.uvs = uvs
+                    } else {
+                        vertices.uvs2 = uvs
+                    }
+                }
+                VertexUsage::Weight => {
+                    vertices.weights = chunks
+                        .map(|v| {
+                            let (bones, weights) = v.split_at(width / 2);
+                            VertexWeight {
+                                bones: bones.to_vec(),
+                                weights: weights.iter().map(|&w| f32::from(w) / 255.0).collect(),
+                            }
+                        })
+                        .collect()
+                }
+                VertexUsage::Color => {
+                    vertices.colors = chunks.map(|v| [v[0], v[1], v[2], v[3]]).collect()
+                }
+                VertexUsage::Unknown(usage) => {
+                    bail!("Unexpected decoder for vertex usage {}", usage)
+                }
+            }
         }
+        Ok(vertices)
+    }
 
-        let mut buffer = &self.vertex_buffer[position.offset as usize..];
-        for _ in 0..vertex_count {
-            let x = buffer.read_f32()?;
-            let y = buffer.read_f32()?;
-            let z = buffer.read_f32()?;
-            writeln!(output, "v {} {} {}", x, y, z)?;
+    /// Reads the index list of a model, already offset by its base vertex
+    pub fn indices(&self, model: &Model) -> Result<Vec<u32>> {
+        self.read_indices(model, self.index_format)
+    }
+
+    fn read_indices(&self, model: &Model, format: IndexFormat) -> Result<Vec<u32>> {
+        let width = format.width();
+        let begin = usize::try_from(model.index_buffer_start)? * width;
+        let end = begin + usize::try_from(model.vertex_count)? * width;
+        let mut buffer = self
+            .index_buffer
+            .get(begin..end)
+            .context("Index out of bound")?;
+        (0..model.vertex_count)
+            .map(|_| {
+                let index = match format {
+                    IndexFormat::U16 => u32::from(buffer.read_u16()?),
+                    IndexFormat::U32 => buffer.read_u32()?,
+                };
+                Ok(index + model.vertex_buffer_start)
+            })
+            .collect()
+    }
+
+    pub fn material_name(&self, model: &Model) -> Option<&str> {
+        self.material_names
+            .get(usize::from(model.material_index))
+            .map(|s| s.as_str())
+    }
+
+    /// Checks every assumption the typed API relies on
+    pub fn validate(&self) -> Result<()> {
+        if let Some(doubt) = self.index_format_doubt {
+            bail!("{}, assumed 16-bit indices", doubt)
+        }
+        let vertices = self.vertices()?;
+        let vertex_count = vertices.positions.len();
+        for model in self.models() {
+            if model.vertex_count % 3 != 0 {
+                bail!("Index count {} is not a triangle list", model.vertex_count)
+            }
+            if self.material_name(model).is_none() {
+                bail!("Material index {} out of bound", model.material_index)
+            }
+            if let Some(index) = self
+                .indices(model)?
+                .into_iter()
+                .find(|&index| index as usize >= vertex_count)
+            {
+                bail!("Vertex index {} out of bound", index)
+            }
+        }
+        if let Some(raw) = vertices.raw.first() {
+            bail!(
+                "Unknown vertex attribute {:?} of width {}",
+                raw.usage,
+                raw.width
+            )
+        }
+        if let Some(weight) = vertices.weights.iter().find(|weight| {
+            weight
+                .bones
+                .iter()
+                .any(|&bone| usize::from(bone) >= self.bone_remap.len())
+        }) {
+            bail!("Bone index out of bound in {:?}", weight.bones)
         }
+        Ok(())
+    }
+
+    /// Writes the first LOD as OBJ, optionally referring to a material library
+    pub fn dump(&self, output: impl AsRef<Path>, mtllib: Option<&str>) -> Result<()> {
+        let mut output = std::fs::File::create(output)?;
+        let vertices = self.vertices()?;
 
-        let mut buffer = &self.vertex_buffer[normal.offset as usize..];
-        for _ in 0..vertex_count {
-            let x = buffer.read_u8()? as f32 / 255.0;
-            let y = buffer.read_u8()? as f32 / 255.0;
-            let z = buffer.read_u8()? as f32 / 255.0;
-            let _ = buffer.read_u8()?;
-            writeln!(output, "vn {} {} {}", x, y, z)?;
+        if let Some(mtllib) = mtllib {
+            writeln!(output, "mtllib {}", mtllib)?;
         }
 
-        let mut buffer = &self.vertex_buffer[texcoord.offset as usize..];
-        for _ in 0..vertex_count {
-            let u = half::f16::from_bits(buffer.read_u16()?);
-            let v = half::f16::from_bits(buffer.read_u16()?);
-            writeln!(output, "vt {} {}", u, 1.0 - v.to_f32())?;
+        for p in &vertices.positions {
+            writeln!(output, "v {} {} {}", p.x, p.y, p.z)?;
+        }
+        for n in &vertices.normals {
+            writeln!(output, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+        for t in &vertices.uvs {
+            writeln!(output, "vt {} {}", t.x, 1.0 - t.y)?;
         }
 
-        let lod = &self.main_model_lods[0];
+        let lod = self.main_model_lods.first().context("No LOD")?;
 
         for group in &lod.model_groups {
             for model in &group.models {
-                let mut index_buffer = &self.index_buffer[model.index_buffer_start as usize * 2..];
-                for _ in 0..model.vertex_count / 3 {
-                    let a = index_buffer.read_u16()? as u32 + model.vertex_buffer_start;
-                    let b = index_buffer.read_u16()? as u32 + model.vertex_buffer_start;
-                    let c = index_buffer.read_u16()? as u32 + model.vertex_buffer_start;
+                if let Some(material) = self.material_name(model) {
+                    writeln!(output, "usemtl {}", material)?;
+                }
+                for face in self.indices(model)?.chunks_exact(3) {
                     writeln!(
                         output,
                         "f {0}/{0}/{0} {1}/{1}/{1} {2}/{2}/{2}",
-                        a + 1,
-                        b + 1,
-                        c + 1
+                        face[0] + 1,
+                        face[1] + 1,
+                        face[2] + 1
                     )?;
                 }
             }
         }
 
-        /*
-        for bone in &self.bones {
-            let parent = &self.bones[bone.parent.unwrap_or(0)];
-            let a = bone.absolute_transform * vec4(0.0, 0.0, 0.0, 1.0);
-            let b = parent.absolute_transform * vec4(0.0, 0.0, 0.0, 1.0);
-
-            writeln!(output, "v {} {} {}", a.x, a.y, a.z)?;
-            writeln!(output, "v {} {} {}", b.x, b.y, b.z)?;
-        }
-
-        for i in 0..self.bones.len() {
-            writeln!(output, "l {} {}", i * 2 + 1, i * 2 + 2)?;
-        }*/
-
         Ok(())
     }
 }
@@ -3,11 +3,13 @@ use crate::hash::hash_as_utf16;
 use crate::suffix::SUFFIX_MAP;
 use anyhow::*;
 use compress::flate;
+use rayon::prelude::*;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
-use std::io::{Read, Seek, SeekFrom};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom};
 
-const LANGUAGE_LIST: &[&str] = &[
+pub const LANGUAGE_LIST: &[&str] = &[
     "", "Ja", "En", "Fr", "It", "De", "Es", "Ru", "Pl", "Nl", "Pt", "PtBR", "Ko", "ZhTW", "ZhCN",
     "Fi", "Sv", "Da", "No", "Cs", "Hu", "Sk", "Ar", "Tr", "Bu", "Gr", "Ro", "Th", "Uk", "Vi", "Id",
     "Fc", "Hi",
@@ -26,11 +28,100 @@ pub struct I18nPakFileIndex {
 }
 
 impl PakFileIndex {
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
     pub fn short_string(&self) -> String {
         format!("{:02}-{:06}", self.version, self.index)
     }
 }
 
+/// Positioned read that doesn't move the file cursor, so that it can be shared across threads.
+pub trait ReadAt {
+    fn read_exact_at(&self, buf: &mut [u8], offset: u64) -> Result<()>;
+}
+
+impl ReadAt for File {
+    #[cfg(unix)]
+    fn read_exact_at(&self, buf: &mut [u8], offset: u64) -> Result<()> {
+        std::os::unix::fs::FileExt::read_exact_at(self, buf, offset)?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn read_exact_at(&self, mut buf: &mut [u8], mut offset: u64) -> Result<()> {
+        while !buf.is_empty() {
+            let read = std::os::windows::fs::FileExt::seek_read(self, buf, offset)?;
+            if read == 0 {
+                bail!("Unexpected end of file");
+            }
+            buf = &mut buf[read..];
+            offset += u64::try_from(read)?;
+        }
+        Ok(())
+    }
+}
+
+struct EntryInfo {
+    offset: u64,
+    len_compressed: u64,
+    len: u64,
+    format: u8,
+}
+
+impl EntryInfo {
+    fn info_offset(file_index: PakFileIndex) -> u64 {
+        0x10 + 0x30 * u64::from(file_index.index) + 8
+    }
+
+    fn read<F: Read>(mut file: F) -> Result<EntryInfo> {
+        let offset = file.read_u64()?;
+        let len_compressed = file.read_u64()?;
+        let len = file.read_u64()?;
+        let format = file.read_u8()?;
+        let _ /*? */ = file.read_u8()?;
+        Ok(EntryInfo {
+            offset,
+            len_compressed,
+            len,
+            format,
+        })
+    }
+
+    fn decode<F: Read>(&self, mut data: F) -> Result<Vec<u8>> {
+        let len = self.len;
+        let len_compressed = self.len_compressed;
+        match self.format {
+            0 => {
+                if len != len_compressed {
+                    bail!("Uncompressed file should have len == len_compressed")
+                }
+                let mut content = vec![0; len.try_into()?];
+                data.read_exact(&mut content)?;
+                Ok(content)
+            }
+            1 => {
+                let stream = data.take(len_compressed);
+                let mut decompressed = Vec::new();
+                flate::Decoder::new(stream).read_to_end(&mut decompressed)?;
+                if u64::try_from(decompressed.len()).unwrap() != len {
+                    bail!("Expected size {}, actual size {}", len, decompressed.len());
+                }
+                Ok(decompressed)
+            }
+            2 => {
+                let decoded = zstd::decode_all(data.take(len_compressed))?;
+                if u64::try_from(decoded.len()).unwrap() != len {
+                    bail!("Expected size {}, actual size {}", len, decoded.len());
+                }
+                Ok(decoded)
+            }
+            format => bail!("Unsupported format: {}", format),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct PakReader<F> {
     files: Vec<F>,
@@ -69,13 +160,38 @@ impl<F: Read + Seek> PakReader<F> {
         })
     }
 
-    fn find_file_internal(&mut self, full_path: String) -> Option<PakFileIndex> {
+    pub fn read_file(&mut self, file_index: PakFileIndex) -> Result<Vec<u8>> {
+        let file = &mut self.files[file_index.version];
+        file.seek(SeekFrom::Start(EntryInfo::info_offset(file_index)))?;
+        let info = EntryInfo::read(&mut *file)?;
+        file.seek(SeekFrom::Start(info.offset))?;
+        info.decode(file)
+    }
+
+    pub fn read_file_at(&mut self, version: usize, index: u32) -> Result<Vec<u8>> {
+        if version > self.files.len() {
+            bail!("Version out of bound")
+        }
+        if index >= self.counts[version] {
+            bail!("Index out of bound");
+        }
+        self.read_file(PakFileIndex { version, index })
+    }
+}
+
+impl<F> PakReader<F> {
+    fn find_file_internal(&self, full_path: String) -> Option<PakFileIndex> {
+        self.find_full_path(&full_path)
+    }
+
+    /// Looks up a path including the `natives/` prefix, version suffix and language
+    pub fn find_full_path(&self, full_path: &str) -> Option<PakFileIndex> {
         let hash: u64 = u64::from(hash_as_utf16(&full_path.to_lowercase()))
             | (u64::from(hash_as_utf16(&full_path.to_uppercase())) << 32);
         self.hash_map.get(&hash).cloned()
     }
 
-    pub fn find_file_i18n(&mut self, mut path: &str) -> Result<Vec<I18nPakFileIndex>> {
+    pub fn find_file_i18n(&self, mut path: &str) -> Result<Vec<I18nPakFileIndex>> {
         if path.starts_with('@') {
             path = &path[1..];
         }
@@ -109,7 +225,7 @@ impl<F: Read + Seek> PakReader<F> {
         Ok(result)
     }
 
-    pub fn find_file(&mut self, path: &str) -> Result<PakFileIndex> {
+    pub fn find_file(&self, path: &str) -> Result<PakFileIndex> {
         Ok(self
             .find_file_i18n(path)?
             .first()
@@ -117,59 +233,29 @@ impl<F: Read + Seek> PakReader<F> {
             .index)
     }
 
-    pub fn read_file(&mut self, file_index: PakFileIndex) -> Result<Vec<u8>> {
-        let info_offset = 0x10 + 0x30 * u64::from(file_index.index) + 8;
-        let file = &mut self.files[file_index.version];
-        file.seek(SeekFrom::Start(info_offset))?;
-        let offset = file.read_u64()?;
-        let len_compressed = file.read_u64()?;
-        let len = file.read_u64()?;
-        let format = file.read_u8()?;
-        let _ /*? */ = file.read_u8()?;
-
-        file.seek(SeekFrom::Start(offset))?;
-        match format {
-            0 => {
-                if len != len_compressed {
-                    bail!("Uncompressed file should have len == len_compressed")
-                }
-                let mut data = vec![0; len.try_into()?];
-                file.read_exact(&mut data)?;
-                Ok(data)
-            }
-            1 => {
-                let stream = file.by_ref().take(len_compressed);
-                let mut decompressed = Vec::new();
-                flate::Decoder::new(stream).read_to_end(&mut decompressed)?;
-                if u64::try_from(decompressed.len()).unwrap() != len {
-                    bail!("Expected size {}, actual size {}", len, decompressed.len());
-                }
-                Ok(decompressed)
-            }
-            2 => {
-                let decoded = zstd::decode_all(file.by_ref().take(len_compressed))?;
-                if u64::try_from(decoded.len()).unwrap() != len {
-                    bail!("Expected size {}, actual size {}", len, decoded.len());
-                }
-                Ok(decoded)
-            }
-            _ => bail!("Unsupported format: {}", format),
-        }
-    }
-
-    pub fn read_file_at(&mut self, version: usize, index: u32) -> Result<Vec<u8>> {
-        if version > self.files.len() {
-            bail!("Version out of bound")
-        }
-        if index >= self.counts[version] {
-            bail!("Index out of bound");
-        }
-        self.read_file(PakFileIndex { version, index })
-    }
-
     pub fn all_file_indexs(&self) -> Vec<PakFileIndex> {
         let mut v: Vec<_> = self.hash_map.values().cloned().collect();
         v.sort();
         v
     }
 }
+
+impl<F: ReadAt + Sync> PakReader<F> {
+    /// Reads a file through `&self`, so that it can be called from many threads at once.
+    pub fn read_file_shared(&self, file_index: PakFileIndex) -> Result<Vec<u8>> {
+        let file = &self.files[file_index.version];
+        let mut info = [0; 0x20];
+        file.read_exact_at(&mut info, EntryInfo::info_offset(file_index))?;
+        let info = EntryInfo::read(Cursor::new(&info[..]))?;
+        let mut data = vec![0; info.len_compressed.try_into()?];
+        file.read_exact_at(&mut data, info.offset)?;
+        info.decode(Cursor::new(data))
+    }
+
+    /// Reads and decompresses all files in parallel.
+    pub fn par_files(&self) -> impl ParallelIterator<Item = (PakFileIndex, Result<Vec<u8>>)> + '_ {
+        self.all_file_indexs()
+            .into_par_iter()
+            .map(move |index| (index, self.read_file_shared(index)))
+    }
+}
@@ -0,0 +1,87 @@
+use crate::pak::*;
+use crate::suffix::SUFFIX_MAP;
+use anyhow::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::Path;
+
+/// Every full path spelling worth trying for a short path like `Quest/QuestData/Foo.user`.
+/// Besides the known suffix version of the extension, all other known versions are tried too.
+fn candidate_paths(path: &str) -> Vec<String> {
+    let path = path.trim_start_matches('@');
+    let mut versions: BTreeSet<u32> = SUFFIX_MAP.values().cloned().collect();
+    if let Some(dot) = path.rfind('.') {
+        if let Some(&version) = SUFFIX_MAP.get(&path[dot + 1..]) {
+            versions.insert(version);
+        }
+    }
+
+    let mut result = vec![];
+    for version in versions {
+        let full_path = format!("natives/NSW/{}.{}", path, version);
+        for base in [full_path.clone(), format!("{}.NSW", full_path)].iter() {
+            for &language in LANGUAGE_LIST {
+                if language.is_empty() {
+                    result.push(base.clone());
+                } else {
+                    result.push(format!("{}.{}", base, language));
+                }
+            }
+        }
+    }
+    result
+}
+
+/// A growing list of full pak paths known to exist, one per line.
+#[derive(Debug, Default)]
+pub struct PathDict {
+    pub paths: BTreeSet<String>,
+}
+
+impl PathDict {
+    /// Loads the dictionary, or starts an empty one if the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<PathDict> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(PathDict::default());
+        }
+        let mut paths = BTreeSet::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.is_empty() {
+                paths.insert(line.to_owned());
+            }
+        }
+        Ok(PathDict { paths })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut content = String::new();
+        for path in &self.paths {
+            content += path;
+            content += "\n";
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Adds every language and suffix version variant of `path` found in the pak.
+    /// Returns the number of newly added entries.
+    pub fn add_path<F: Read + Seek>(&mut self, pak: &PakReader<F>, path: &str) -> usize {
+        candidate_paths(path)
+            .into_iter()
+            .filter(|full_path| pak.find_full_path(full_path).is_some())
+            .filter(|full_path| self.paths.insert(full_path.clone()))
+            .count()
+    }
+
+    /// Maps pak entries to their known full paths.
+    pub fn resolve<F: Read + Seek>(&self, pak: &PakReader<F>) -> HashMap<PakFileIndex, &str> {
+        self.paths
+            .iter()
+            .filter_map(|path| Some((pak.find_full_path(path)?, path.as_str())))
+            .collect()
+    }
+}
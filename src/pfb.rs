@@ -4,8 +4,16 @@ use crate::user::UserChild;
 use anyhow::*;
 use std::io::{Read, Seek};
 
+#[derive(Debug)]
+pub struct PfbGameObject {
+    pub object_index: i32,
+    pub parent_index: i32,
+    pub component_count: i32,
+}
+
 #[derive(Debug)]
 pub struct Pfb {
+    pub game_objects: Vec<PfbGameObject>,
     pub resource_names: Vec<String>,
     pub children: Vec<UserChild>,
     pub rsz: Rsz,
@@ -30,12 +38,16 @@ impl Pfb {
         let child_list_offset = file.read_u64()?;
         let rsz_offset = file.read_u64()?;
 
-        let _ = (0..zinogre_count)
+        let game_objects = (0..zinogre_count)
             .map(|_| {
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                Ok(())
+                let object_index = file.read_i32()?;
+                let parent_index = file.read_i32()?;
+                let component_count = file.read_i32()?;
+                Ok(PfbGameObject {
+                    object_index,
+                    parent_index,
+                    component_count,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
         file.seek_noop(rathalos_offset)
@@ -100,6 +112,7 @@ impl Pfb {
         let rsz = Rsz::new(file, rsz_offset)?;
 
         Ok(Pfb {
+            game_objects,
             resource_names,
             children,
             rsz,
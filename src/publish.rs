@@ -0,0 +1,498 @@
+use anyhow::*;
+use rusoto_core::{ByteStream, Region};
+use rusoto_s3::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fs::{copy, create_dir_all, read, read_dir, remove_dir, remove_file, File};
+use std::io::{BufWriter, Write};
+use std::path::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::runtime::Runtime;
+use walkdir::WalkDir;
+
+pub struct UploadFile {
+    pub key: String,
+    pub path: PathBuf,
+    pub len: u64,
+    pub mime: &'static str,
+}
+
+pub trait Publisher {
+    fn list(&mut self) -> Result<BTreeSet<String>>;
+    fn is_up_to_date(&mut self, _file: &UploadFile) -> Result<bool> {
+        Ok(false)
+    }
+    fn upload(&mut self, files: Vec<UploadFile>) -> Result<()>;
+    fn delete(&mut self, keys: Vec<String>) -> Result<()>;
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn mime_type(path: &Path) -> Result<&'static str> {
+    Ok(
+        match path
+            .extension()
+            .context("Missing extension")?
+            .to_str()
+            .context("Extension is not UTF-8")?
+        {
+            "html" => "text/html",
+            "css" => "text/css",
+            "js" => "text/javascript",
+            "json" => "application/json",
+            "png" => "image/png",
+            _ => bail!("Unknown extension"),
+        },
+    )
+}
+
+fn path_key(root: &Path, path: &Path) -> Result<String> {
+    Ok(path
+        .strip_prefix(root)?
+        .to_str()
+        .context("Path contain non UTF-8 character")?
+        .replace('\\', "/"))
+}
+
+pub fn publish(source: &Path, publisher: &mut dyn Publisher, dry_run: bool) -> Result<()> {
+    let mut files = vec![];
+    for entry in WalkDir::new(source) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let len = entry.metadata()?.len();
+        let mime = mime_type(entry.path())?;
+        let key = path_key(source, entry.path())?;
+        files.push(UploadFile {
+            key,
+            path: entry.into_path(),
+            len,
+            mime,
+        });
+    }
+
+    let keys: BTreeSet<&str> = files.iter().map(|file| file.key.as_str()).collect();
+    let deletions: Vec<String> = publisher
+        .list()?
+        .into_iter()
+        .filter(|key| !keys.contains(key.as_str()))
+        .collect();
+
+    let mut uploads = vec![];
+    for file in files {
+        if !publisher.is_up_to_date(&file)? {
+            uploads.push(file);
+        }
+    }
+
+    if dry_run {
+        for file in &uploads {
+            println!("Would upload {}", file.key);
+        }
+        for key in &deletions {
+            println!("Would delete {}", key);
+        }
+        println!(
+            "Dry run: {} uploads, {} deletions",
+            uploads.len(),
+            deletions.len()
+        );
+        return Ok(());
+    }
+
+    println!("Uploading {} files...", uploads.len());
+    publisher.upload(uploads)?;
+    println!("Deleting {} files...", deletions.len());
+    publisher.delete(deletions)?;
+    publisher.finish()?;
+    Ok(())
+}
+
+pub struct LocalPublisher {
+    root: PathBuf,
+}
+
+impl LocalPublisher {
+    pub fn new(root: impl Into<PathBuf>) -> LocalPublisher {
+        LocalPublisher { root: root.into() }
+    }
+}
+
+impl Publisher for LocalPublisher {
+    fn list(&mut self) -> Result<BTreeSet<String>> {
+        let mut keys = BTreeSet::new();
+        if !self.root.exists() {
+            return Ok(keys);
+        }
+        for entry in WalkDir::new(&self.root) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                keys.insert(path_key(&self.root, entry.path())?);
+            }
+        }
+        Ok(keys)
+    }
+
+    fn is_up_to_date(&mut self, file: &UploadFile) -> Result<bool> {
+        let target = self.root.join(&file.key);
+        if !target.is_file() || target.metadata()?.len() != file.len {
+            return Ok(false);
+        }
+        Ok(read(&target)? == read(&file.path)?)
+    }
+
+    fn upload(&mut self, files: Vec<UploadFile>) -> Result<()> {
+        for file in files {
+            let target = self.root.join(&file.key);
+            if let Some(parent) = target.parent() {
+                create_dir_all(parent)?;
+            }
+            copy(&file.path, &target)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, keys: Vec<String>) -> Result<()> {
+        for key in keys {
+            let path = self.root.join(&key);
+            remove_file(&path)?;
+            // Remove the directories left empty, up to the root
+            let mut dir = path.parent();
+            while let Some(parent) = dir {
+                if parent == self.root || read_dir(parent)?.next().is_some() {
+                    break;
+                }
+                remove_dir(parent)?;
+                dir = parent.parent();
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct S3Publisher {
+    client: S3Client,
+    bucket: String,
+    runtime: Runtime,
+    etags: BTreeMap<String, String>,
+}
+
+impl S3Publisher {
+    pub fn new(
+        bucket: String,
+        region: Option<String>,
+        endpoint: Option<String>,
+    ) -> Result<S3Publisher> {
+        let region = match (region, endpoint) {
+            (region, Some(endpoint)) => Region::Custom {
+                name: region.unwrap_or_else(|| "us-east-1".to_owned()),
+                endpoint,
+            },
+            (Some(region), None) => region
+                .parse()
+                .map_err(|_| anyhow!("Unknown region {}", region))?,
+            (None, None) => Region::UsEast1,
+        };
+        Ok(S3Publisher {
+            client: S3Client::new(region),
+            bucket,
+            runtime: Runtime::new()?,
+            etags: BTreeMap::new(),
+        })
+    }
+}
+
+async fn upload_s3(file: UploadFile, bucket: String, client: &S3Client) -> Result<()> {
+    use futures::StreamExt;
+    use tokio_util::codec;
+    let stream = tokio::fs::File::open(file.path).await?;
+    let stream =
+        codec::FramedRead::new(stream, codec::BytesCodec::new()).map(|r| r.map(|r| r.freeze()));
+    let request = PutObjectRequest {
+        bucket,
+        key: file.key,
+        body: Some(ByteStream::new(stream)),
+        content_length: Some(i64::try_from(file.len)?),
+        content_type: Some(file.mime.to_owned()),
+        ..PutObjectRequest::default()
+    };
+    client.put_object(request).await?;
+    Ok(())
+}
+
+// Lists the objects with their ETag
+async fn list_s3(bucket: String, client: &S3Client) -> Result<BTreeMap<String, String>> {
+    let mut objects = BTreeMap::new();
+    let mut continuation_token = None;
+    loop {
+        println!("Continue listing files..");
+        let request = ListObjectsV2Request {
+            bucket: bucket.clone(),
+            continuation_token: continuation_token.take(),
+            ..ListObjectsV2Request::default()
+        };
+        let result = client.list_objects_v2(request).await?;
+        objects.extend(
+            result
+                .contents
+                .into_iter()
+                .flatten()
+                .filter_map(|o| Some((o.key?, o.e_tag.unwrap_or_default()))),
+        );
+
+        if result.is_truncated.unwrap_or(false) {
+            continuation_token = result.next_continuation_token;
+        } else {
+            break;
+        }
+    }
+    Ok(objects)
+}
+
+impl Publisher for S3Publisher {
+    fn list(&mut self) -> Result<BTreeSet<String>> {
+        self.etags = self
+            .runtime
+            .block_on(list_s3(self.bucket.clone(), &self.client))?;
+        Ok(self.etags.keys().cloned().collect())
+    }
+
+    fn is_up_to_date(&mut self, file: &UploadFile) -> Result<bool> {
+        // Files are uploaded with a single PutObject, so their ETag is the MD5 of the content
+        let etag = match self.etags.get(&file.key) {
+            Some(etag) => etag.trim_matches('"'),
+            None => return Ok(false),
+        };
+        Ok(etag == format!("{:x}", md5::compute(read(&file.path)?)))
+    }
+
+    fn upload(&mut self, files: Vec<UploadFile>) -> Result<()> {
+        use futures::future::try_join_all;
+        let futures = files
+            .into_iter()
+            .map(|file| upload_s3(file, self.bucket.clone(), &self.client));
+        self.runtime.block_on(try_join_all(futures))?;
+        Ok(())
+    }
+
+    fn delete(&mut self, keys: Vec<String>) -> Result<()> {
+        // DeleteObjects accepts at most 1000 keys per request
+        for chunk in keys.chunks(1000) {
+            let objects = chunk
+                .iter()
+                .map(|key| ObjectIdentifier {
+                    key: key.clone(),
+                    version_id: None,
+                })
+                .collect();
+            let request = DeleteObjectsRequest {
+                bucket: self.bucket.clone(),
+                delete: Delete {
+                    objects,
+                    quiet: Some(true),
+                },
+                ..DeleteObjectsRequest::default()
+            };
+            self.runtime.block_on(self.client.delete_objects(request))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct TarPublisher {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl TarPublisher {
+    pub fn new(path: impl Into<PathBuf>) -> TarPublisher {
+        TarPublisher {
+            path: path.into(),
+            writer: None,
+        }
+    }
+}
+
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+}
+
+fn write_tar_entry(writer: &mut impl Write, key: &str, data: &[u8], mtime: u64) -> Result<()> {
+    let (prefix, name) = if key.len() <= 100 {
+        ("", key)
+    } else {
+        let split = key[..std::cmp::min(key.len(), 156)]
+            .rfind('/')
+            .context("Path too long for tar")?;
+        (&key[..split], &key[split + 1..])
+    };
+    ensure!(
+        name.len() <= 100 && prefix.len() <= 155,
+        "Path too long for tar: {}",
+        key
+    );
+
+    let mut header = [0; 512];
+    header[0..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], 0o644);
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(&mut header[124..136], u64::try_from(data.len())?);
+    write_octal(&mut header[136..148], mtime);
+    header[148..156].copy_from_slice(b"        ");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+    let checksum: u64 = header.iter().map(|&b| u64::from(b)).sum();
+    write_octal(&mut header[148..155], checksum);
+    header[154] = 0;
+
+    writer.write_all(&header)?;
+    writer.write_all(data)?;
+    let padding = (512 - data.len() % 512) % 512;
+    writer.write_all(&vec![0; padding])?;
+    Ok(())
+}
+
+impl Publisher for TarPublisher {
+    fn list(&mut self) -> Result<BTreeSet<String>> {
+        // The bundle is always written from scratch
+        Ok(BTreeSet::new())
+    }
+
+    fn upload(&mut self, files: Vec<UploadFile>) -> Result<()> {
+        let mtime = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if self.writer.is_none() {
+            self.writer = Some(BufWriter::new(File::create(&self.path)?));
+        }
+        let writer = self.writer.as_mut().unwrap();
+        for file in files {
+            write_tar_entry(writer, &file.key, &read(&file.path)?, mtime)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, _keys: Vec<String>) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.write_all(&[0; 1024])?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ZipPublisher {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+    offset: u32,
+    central_directory: Vec<u8>,
+    entry_count: u16,
+}
+
+impl ZipPublisher {
+    pub fn new(path: impl Into<PathBuf>) -> ZipPublisher {
+        ZipPublisher {
+            path: path.into(),
+            writer: None,
+            offset: 0,
+            central_directory: vec![],
+            entry_count: 0,
+        }
+    }
+}
+
+// MS-DOS date and time, with two second precision
+fn dos_time() -> (u16, u16) {
+    use chrono::prelude::*;
+    let now = Utc::now();
+    let time = (now.hour() << 11 | now.minute() << 5 | now.second() / 2) as u16;
+    let date = ((now.year() - 1980) as u32) << 9 | now.month() << 5 | now.day();
+    (time, date as u16)
+}
+
+impl Publisher for ZipPublisher {
+    fn list(&mut self) -> Result<BTreeSet<String>> {
+        // The bundle is always written from scratch
+        Ok(BTreeSet::new())
+    }
+
+    fn upload(&mut self, files: Vec<UploadFile>) -> Result<()> {
+        let (time, date) = dos_time();
+        if self.writer.is_none() {
+            self.writer = Some(BufWriter::new(File::create(&self.path)?));
+        }
+        let writer = self.writer.as_mut().unwrap();
+        for file in files {
+            let data = read(&file.path)?;
+            let crc = crc32fast::hash(&data);
+            let len = u32::try_from(data.len()).context("File too large for zip")?;
+            let name_len = u16::try_from(file.key.len())?;
+            self.entry_count = self
+                .entry_count
+                .checked_add(1)
+                .context("Too many files for zip")?;
+
+            // Entries are stored uncompressed, with the UTF-8 name flag set
+            let mut common = vec![];
+            common.extend_from_slice(&20u16.to_le_bytes());
+            common.extend_from_slice(&0x0800u16.to_le_bytes());
+            common.extend_from_slice(&0u16.to_le_bytes());
+            common.extend_from_slice(&time.to_le_bytes());
+            common.extend_from_slice(&date.to_le_bytes());
+            common.extend_from_slice(&crc.to_le_bytes());
+            common.extend_from_slice(&len.to_le_bytes());
+            common.extend_from_slice(&len.to_le_bytes());
+            common.extend_from_slice(&name_len.to_le_bytes());
+            common.extend_from_slice(&0u16.to_le_bytes());
+
+            writer.write_all(&0x04034b50u32.to_le_bytes())?;
+            writer.write_all(&common)?;
+            writer.write_all(file.key.as_bytes())?;
+            writer.write_all(&data)?;
+
+            let directory = &mut self.central_directory;
+            directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            directory.extend_from_slice(&(3u16 << 8 | 20).to_le_bytes());
+            directory.extend_from_slice(&common);
+            directory.extend_from_slice(&[0; 6]);
+            directory.extend_from_slice(&(0o100644u32 << 16).to_le_bytes());
+            directory.extend_from_slice(&self.offset.to_le_bytes());
+            directory.extend_from_slice(file.key.as_bytes());
+
+            let offset = self.offset;
+            self.offset = (30 + u32::from(name_len))
+                .checked_add(len)
+                .and_then(|entry_len| offset.checked_add(entry_len))
+                .context("Bundle too large for zip")?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, _keys: Vec<String>) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            let directory_len = u32::try_from(self.central_directory.len())?;
+            writer.write_all(&self.central_directory)?;
+            writer.write_all(&0x06054b50u32.to_le_bytes())?;
+            writer.write_all(&[0; 4])?;
+            writer.write_all(&self.entry_count.to_le_bytes())?;
+            writer.write_all(&self.entry_count.to_le_bytes())?;
+            writer.write_all(&directory_len.to_le_bytes())?;
+            writer.write_all(&self.offset.to_le_bytes())?;
+            writer.write_all(&0u16.to_le_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
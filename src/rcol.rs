@@ -554,29 +554,16 @@ impl Rcol {
     }
 
     pub fn color_monster_model(&self, mesh: &Mesh) -> Result<(Vec<ColoredVertex>, Vec<u32>)> {
-        let position = mesh
-            .vertex_layouts
-            .iter()
-            .find(|layout| layout.usage == 0)
-            .context("No position data")?;
-
-        let vertex_count = (mesh.vertex_layouts[1].offset - mesh.vertex_layouts[0].offset)
-            / u32::from(mesh.vertex_layouts[0].width);
-
-        let mut buffer = mesh
-            .vertex_buffer
-            .get(usize::try_from(position.offset)?..)
-            .context("Vertex out of bound")?;
+        let positions = mesh.vertices()?.positions;
 
         let ignore_tag_filter = self.get_monster_ride_filter();
         if ignore_tag_filter == 0 {
             bail!("Didn't find monster ride filter")
         }
 
-        let vertexs = (0..vertex_count)
-            .map(|_| {
-                let position = buffer.read_f32vec3()?;
-
+        let vertexs = positions
+            .into_iter()
+            .map(|position| {
                 let mut meat_dist = f32::MAX;
                 let mut meat = None;
                 let mut parts_group = None;
@@ -645,13 +632,7 @@ impl Rcol {
 
         for model_group in &mesh.main_model_lods[0].model_groups {
             for model in &model_group.models {
-                let mut index_buffer = mesh
-                    .index_buffer
-                    .get(model.index_buffer_start as usize * 2..)
-                    .context("index out of bound")?;
-                for _ in 0..usize::try_from(model.vertex_count)? {
-                    indexs.push(u32::from(index_buffer.read_u16()?) + (model.vertex_buffer_start))
-                }
+                indexs.extend(mesh.indices(model)?);
             }
         }
 
@@ -67,7 +67,7 @@ rsz_enum! {
 
 rsz_enum! {
     #[rsz(u32)]
-    #[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
     pub enum ItemId {
         Null = 0, // not defined in TDB, but appears in some overwear data
         None = 0x04000000,
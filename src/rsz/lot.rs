@@ -183,3 +183,56 @@ rsz_struct! {
         pub params: Vec<PartsTypeInfo>
     }
 }
+
+rsz_enum! {
+    #[rsz(i32)]
+    #[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+    pub enum LotRule {
+        Random = 0,
+        RandomOut1 = 1,
+        RandomOut2 = 2,
+        RandomOut3 = 3,
+        FirstFix = 4,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.data.RewardIdLotTableUserData.Param")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct RewardIdLotTableUserDataParam {
+        pub id: u32,
+        pub lot_rule: LotRule,
+        pub item_id_list: Vec<ItemId>,
+        pub num_list: Vec<u32>,
+        pub probability_list: Vec<u32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.data.RewardIdLotTableUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct RewardIdLotTableUserData {
+        pub param: Vec<RewardIdLotTableUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.data.QuestDataForRewardUserData.Param")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct QuestDataForRewardUserDataParam {
+        pub quest_numer: i32,
+        pub common_material_reward_num: u32,
+        pub additional_target_reward_table_index: u32,
+        pub common_material_reward_table_index: u32,
+        pub additional_quest_reward_table_index: Vec<u32>,
+        pub cloth_ticket_index: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.data.QuestDataForRewardUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct QuestDataForRewardUserData {
+        pub param: Vec<QuestDataForRewardUserDataParam>,
+    }
+}
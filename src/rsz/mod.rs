@@ -15,6 +15,8 @@ mod monster_list;
 mod parts_break_data;
 mod quest_data;
 mod skill;
+mod via;
+mod weapon;
 
 pub use alchemy::*;
 pub use anger_data::*;
@@ -33,6 +35,8 @@ pub use monster_list::*;
 pub use parts_break_data::*;
 pub use quest_data::*;
 pub use skill::*;
+pub use via::*;
+pub use weapon::*;
 
 use crate::file_ext::*;
 use crate::hash::*;
@@ -147,14 +151,19 @@ impl Rsz {
         })
     }
 
-    pub fn deserialize(&self) -> Result<Vec<Box<dyn Any>>> {
+    /// Deserializes instances in order until the first type missing from RSZ_TYPE_MAP.
+    /// Instance data has no size prefix, so nothing after an unknown type can be located.
+    /// Returns the decoded nodes and the first unknown instance, if any.
+    fn deserialize_known_nodes(&self) -> Result<(Vec<Option<Box<dyn Any>>>, Option<(usize, u32)>)> {
         let mut node_buf: Vec<Option<Box<dyn Any>>> = vec![None];
         let mut cursor = Cursor::new(&self.data);
-        for td in self.type_descriptors.iter().skip(1) {
+        for (index, td) in self.type_descriptors.iter().enumerate().skip(1) {
             let hash = u32::try_from(*td & 0xFFFFFFFF)?;
-            let deserializer = RSZ_TYPE_MAP
-                .get(&hash)
-                .with_context(|| format!("Unsupported type {:08X}", hash))?;
+            let deserializer = if let Some(info) = RSZ_TYPE_MAP.get(&hash) {
+                info.deserializer
+            } else {
+                return Ok((node_buf, Some((index, hash))));
+            };
             let pos = cursor.tell().unwrap();
             let mut rsz_deserializer = RszDeserializer {
                 node_buf: &mut node_buf,
@@ -166,6 +175,27 @@ impl Rsz {
             node_buf.push(Some(node));
         }
 
+        let mut leftover = vec![];
+        cursor.read_to_end(&mut leftover)?;
+        if !leftover.is_empty() {
+            bail!("Left over data");
+        }
+
+        Ok((node_buf, None))
+    }
+
+    fn deserialize_nodes(&self) -> Result<Vec<Option<Box<dyn Any>>>> {
+        match self.deserialize_known_nodes()? {
+            (node_buf, None) => Ok(node_buf),
+            (_, Some((index, hash))) => {
+                bail!("Unsupported type {:08X} for instance {}", hash, index)
+            }
+        }
+    }
+
+    pub fn deserialize(&self) -> Result<Vec<Box<dyn Any>>> {
+        let mut node_buf = self.deserialize_nodes()?;
+
         let result = self
             .roots
             .iter()
@@ -182,15 +212,27 @@ impl Rsz {
             bail!("Left over node");
         }
 
-        let mut leftover = vec![];
-        cursor.read_to_end(&mut leftover)?;
-        if !leftover.is_empty() {
-            bail!("Left over data");
-        }
-
         Ok(result)
     }
 
+    /// Deserializes every instance, keeping them by instance index.
+    /// Instances already consumed as a child of another one are left as None,
+    /// and so are instances from the first unknown type onwards.
+    pub fn deserialize_instances(&self) -> Result<Vec<RszInstance>> {
+        let (node_buf, _) = self.deserialize_known_nodes()?;
+        let values = node_buf.into_iter().chain(std::iter::repeat_with(|| None));
+        self.type_descriptors
+            .iter()
+            .zip(values)
+            .map(|(td, value)| {
+                Ok(RszInstance {
+                    hash: u32::try_from(*td & 0xFFFFFFFF)?,
+                    value,
+                })
+            })
+            .collect()
+    }
+
     pub fn deserialize_single<T: 'static>(&self) -> Result<T> {
         let mut result = self.deserialize()?;
         if result.len() != 1 {
@@ -204,6 +246,15 @@ impl Rsz {
     }
 }
 
+pub struct RszInstance {
+    pub hash: u32,
+    pub value: Option<Box<dyn Any>>,
+}
+
+pub fn rsz_type_name(hash: u32) -> Option<&'static str> {
+    RSZ_TYPE_MAP.get(&hash).map(|info| info.symbol)
+}
+
 pub struct RszDeserializer<'a, 'b> {
     node_buf: &'a mut [Option<Box<dyn Any>>],
     cursor: &'a mut Cursor<&'b Vec<u8>>,
@@ -313,7 +364,7 @@ impl FieldFromRsz for f32 {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, Copy)]
 pub struct Aligner<const ALIGN: u64>;
 
 impl<const ALIGN: u64> FieldFromRsz for Aligner<ALIGN> {
@@ -538,12 +589,21 @@ rsz_enum! {
 
 type RszDeserializerFn = fn(&mut RszDeserializer) -> Result<Box<dyn Any>>;
 
-static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszDeserializerFn>> = Lazy::new(|| {
+struct RszTypeInfo {
+    symbol: &'static str,
+    deserializer: RszDeserializerFn,
+}
+
+static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszTypeInfo>> = Lazy::new(|| {
     let mut m = HashMap::new();
 
-    fn register<T: 'static + FromRsz>(m: &mut HashMap<u32, RszDeserializerFn>) {
+    fn register<T: 'static + FromRsz>(m: &mut HashMap<u32, RszTypeInfo>) {
         let hash = T::type_hash();
-        let old = m.insert(hash, |rsz| Ok(Box::new(T::from_rsz(rsz)?) as Box<dyn Any>));
+        let info = RszTypeInfo {
+            symbol: T::SYMBOL,
+            deserializer: |rsz| Ok(Box::new(T::from_rsz(rsz)?) as Box<dyn Any>),
+        };
+        let old = m.insert(hash, info);
         if old.is_some() {
             panic!("Multiple type reigstered for the same hash")
         }
@@ -555,6 +615,8 @@ static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszDeserializerFn>> = Lazy::new(|| {
         };
     }
 
+    r!(ViaGameObject, ViaFolder, ViaTransform);
+
     r!(MeatGroupInfo, EnemyMeatContainer, EnemyMeatData);
 
     r!(
@@ -666,6 +728,11 @@ static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszDeserializerFn>> = Lazy::new(|| {
         EnemySizeListData,
         DiscoverEmSetDataParam,
         DiscoverEmSetData,
+        SupplyDataParam,
+        SupplyData,
+        HyakuryuWaveData,
+        HyakuryuQuestData,
+        HyakuryuQuestDataTbl,
     );
 
     r!(
@@ -719,6 +786,37 @@ static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszDeserializerFn>> = Lazy::new(|| {
 
     r!(ItemUserDataParam, ItemUserData);
 
+    r!(
+        GreatSwordBaseUserDataParam,
+        GreatSwordBaseUserData,
+        ShortSwordBaseUserDataParam,
+        ShortSwordBaseUserData,
+        DualBladesBaseUserDataParam,
+        DualBladesBaseUserData,
+        LongSwordBaseUserDataParam,
+        LongSwordBaseUserData,
+        HammerBaseUserDataParam,
+        HammerBaseUserData,
+        HornBaseUserDataParam,
+        HornBaseUserData,
+        LanceBaseUserDataParam,
+        LanceBaseUserData,
+        GunLanceBaseUserDataParam,
+        GunLanceBaseUserData,
+        SlashAxeBaseUserDataParam,
+        SlashAxeBaseUserData,
+        ChargeAxeBaseUserDataParam,
+        ChargeAxeBaseUserData,
+        InsectGlaiveBaseUserDataParam,
+        InsectGlaiveBaseUserData,
+        BowBaseUserDataParam,
+        BowBaseUserData,
+        LightBowgunBaseUserDataParam,
+        LightBowgunBaseUserData,
+        HeavyBowgunBaseUserDataParam,
+        HeavyBowgunBaseUserData,
+    );
+
     r!(
         MonsterLotTableUserDataParam,
         MonsterLotTableUserData,
@@ -731,6 +829,10 @@ static RSZ_TYPE_MAP: Lazy<HashMap<u32, RszDeserializerFn>> = Lazy::new(|| {
         PartsTypeTextUserDataTextInfo,
         PartsTypeInfo,
         PartsTypeTextUserData,
+        RewardIdLotTableUserDataParam,
+        RewardIdLotTableUserData,
+        QuestDataForRewardUserDataParam,
+        QuestDataForRewardUserData,
     );
 
     m
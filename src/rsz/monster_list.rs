@@ -8,8 +8,8 @@ rsz_struct! {
     pub struct ViaVec2 {
         #[serde(skip)]
         begin_align: Aligner<16>,
-        x: f32,
-        y: f32,
+        pub x: f32,
+        pub y: f32,
         #[serde(skip)]
         endn_align: Aligner<16>,
     }
@@ -31,7 +31,22 @@ rsz_struct! {
     #[rsz("snow.BitSetFlag`1<snow.data.monsterList.HabitatType>")]
     #[derive(Debug, Serialize)]
     pub struct BitSetFlagHabitatType {
-        flag: Vec<u32>
+        pub flag: Vec<u32>
+    }
+}
+
+impl BitSetFlagHabitatType {
+    /// Indexes of all set habitat bits
+    pub fn habitats(&self) -> Vec<usize> {
+        self.flag
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| {
+                (0..32)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| word_index * 32 + bit)
+            })
+            .collect()
     }
 }
 
@@ -455,3 +455,63 @@ rsz_struct! {
         pub param: Vec<DiscoverEmSetDataParam>,
     }
 }
+
+rsz_struct! {
+    #[rsz("snow.data.SupplyData.Param")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct SupplyDataParam {
+        pub id: u32,
+        pub item_id: Vec<ItemId>,
+        pub num: Vec<u32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.data.SupplyData")]
+    #[derive(Debug, Serialize)]
+    pub struct SupplyData {
+        pub param: Vec<SupplyDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.quest.HyakuryuQuestData.WaveData")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct HyakuryuWaveData {
+        pub boss_em: EmTypes,
+        pub boss_sub_type: u32,
+        pub order_tbl: i32,
+        pub boss_em_nando_tbl_no: i32,
+        pub wave_em_nando_tbl_no: i32,
+        pub em_table: Vec<EmTypes>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.quest.HyakuryuQuestData")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct HyakuryuQuestData {
+        pub quest_no: i32,
+        pub random_seed: i32,
+        pub attr: u32,
+        pub wave_data: Vec<HyakuryuWaveData>,
+        pub quest_lv: i32,
+        pub map_block: i32,
+        pub start_block_no: i32,
+        pub end_block_no: i32,
+        pub extra_em_wave_no: i32,
+        pub extra_em_nando_tbl_no: i32,
+        pub nushi_order_tbl_no: i32,
+        pub hm_unlock_tbl_no: i32,
+        pub sub_target: Vec<i32>,
+        pub sub_target5_wave_no: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.quest.HyakuryuQuestDataTbl")]
+    #[derive(Debug, Serialize)]
+    pub struct HyakuryuQuestDataTbl {
+        pub data_list: Vec<HyakuryuQuestData>,
+    }
+}
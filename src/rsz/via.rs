@@ -0,0 +1,56 @@
+use super::*;
+use crate::rsz_struct;
+use serde::*;
+
+rsz_struct! {
+    #[rsz("via.GameObject")]
+    #[derive(Debug, Serialize)]
+    pub struct ViaGameObject {
+        pub name: String,
+        pub tag: String,
+        pub draw_self: bool,
+        pub update_self: bool,
+        pub time_scale: f32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("via.Folder")]
+    #[derive(Debug, Serialize)]
+    pub struct ViaFolder {
+        pub name: String,
+        pub tag: String,
+        pub draw_self: bool,
+        pub update_self: bool,
+        pub select: bool,
+        pub path: String,
+    }
+}
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize, Clone, Copy)]
+    pub struct ViaVec4 {
+        #[serde(skip)]
+        begin_align: Aligner<16>,
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+        pub w: f32,
+        #[serde(skip)]
+        end_align: Aligner<16>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("via.Transform")]
+    #[derive(Debug, Serialize, Clone)]
+    pub struct ViaTransform {
+        pub local_position: ViaVec4,
+        pub local_rotation: ViaVec4,
+        pub local_scale: ViaVec4,
+        pub parent_bone: String,
+        pub same_joints_constraint: bool,
+        pub absolute_scaling: bool,
+    }
+}
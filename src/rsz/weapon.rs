@@ -0,0 +1,405 @@
+use super::*;
+use crate::rsz_struct;
+use serde::*;
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize)]
+    pub struct WeaponBaseData {
+        pub id: u32, // snow.data.ContentsIdSystem.WeaponId
+        pub sort_id: u32,
+        pub rare_type: RareTypes,
+        pub model_id: u32,
+        pub base_val: u32,
+        pub buy_val: u32,
+    }
+}
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize)]
+    pub struct MainWeaponBaseData {
+        #[serde(flatten)]
+        pub base: WeaponBaseData,
+        pub atk: i32,
+        pub critical_rate: i32,
+        pub def_bonus: i32,
+        pub hyakuryu_skill_id_list: Vec<u32>, // snow.data.DataDef.PlHyakuryuSkillId, 1 = ID_0
+        pub slot_num_list: Vec<u32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize)]
+    pub struct CloseRangeWeaponBaseData {
+        #[serde(flatten)]
+        pub base: MainWeaponBaseData,
+        pub sharpness_val_list: Vec<i32>,
+        pub takumi_val_list: Vec<i32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize)]
+    pub struct ElementWeaponBaseData {
+        #[serde(flatten)]
+        pub base: CloseRangeWeaponBaseData,
+        pub main_element_type: i32, // snow.data.DataDef.PlWeaponElementTypes
+        pub main_element_val: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz()]
+    #[derive(Debug, Serialize)]
+    pub struct BulletWeaponBaseData {
+        #[serde(flatten)]
+        pub base: MainWeaponBaseData,
+        pub fluctuation: i32, // snow.data.BulletWeaponBaseUserData.Fluctuation
+        pub reload: i32,
+        pub recoil: i32,
+        pub kakusan_type: i32,
+        pub bullet_equip_flag_list: Vec<bool>,
+        pub bullet_num_list: Vec<u32>,
+        pub bullet_type_list: Vec<i32>, // snow.data.BulletTypes
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.GreatSwordBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct GreatSwordBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.GreatSwordBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct GreatSwordBaseUserData {
+        pub param: Vec<GreatSwordBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.ShortSwordBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct ShortSwordBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.ShortSwordBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct ShortSwordBaseUserData {
+        pub param: Vec<ShortSwordBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.DualBladesBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct DualBladesBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: CloseRangeWeaponBaseData,
+        pub main_element_type: i32,
+        pub main_element_val: i32,
+        pub sub_element_type: i32,
+        pub sub_element_val: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.DualBladesBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct DualBladesBaseUserData {
+        pub param: Vec<DualBladesBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LongSwordBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct LongSwordBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LongSwordBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct LongSwordBaseUserData {
+        pub param: Vec<LongSwordBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HammerBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct HammerBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HammerBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct HammerBaseUserData {
+        pub param: Vec<HammerBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HornBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct HornBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+        pub horn_melody_type_list: Vec<i32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HornBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct HornBaseUserData {
+        pub param: Vec<HornBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LanceBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct LanceBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LanceBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct LanceBaseUserData {
+        pub param: Vec<LanceBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.GunLanceBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct GunLanceBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+        pub gun_lance_fire_type: i32,
+        pub gun_lance_fire_lv: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.GunLanceBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct GunLanceBaseUserData {
+        pub param: Vec<GunLanceBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.SlashAxeBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct SlashAxeBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+        pub slash_axe_bottle_type: i32,
+        pub slash_axe_bottle_element_val: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.SlashAxeBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct SlashAxeBaseUserData {
+        pub param: Vec<SlashAxeBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.ChargeAxeBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct ChargeAxeBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+        pub charge_axe_bottle_type: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.ChargeAxeBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct ChargeAxeBaseUserData {
+        pub param: Vec<ChargeAxeBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.InsectGlaiveBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct InsectGlaiveBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: ElementWeaponBaseData,
+        pub insect_glaive_insect_lv: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.InsectGlaiveBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct InsectGlaiveBaseUserData {
+        pub param: Vec<InsectGlaiveBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.BowBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct BowBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: MainWeaponBaseData,
+        pub main_element_type: i32,
+        pub main_element_val: i32,
+        pub bow_bottle_power_up_type_list: Vec<i32>,
+        pub bow_bottle_equip_flag_list: Vec<bool>,
+        pub bow_default_charge_lv_limit: i32,
+        pub bow_charge_type_list: Vec<i32>,
+        pub bow_curve_type: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.BowBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct BowBaseUserData {
+        pub param: Vec<BowBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LightBowgunBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct LightBowgunBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: BulletWeaponBaseData,
+        pub rapid_shot_list: Vec<i32>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.LightBowgunBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct LightBowgunBaseUserData {
+        pub param: Vec<LightBowgunBaseUserDataParam>,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HeavyBowgunBaseUserData.Param")]
+    #[derive(Debug, Serialize)]
+    pub struct HeavyBowgunBaseUserDataParam {
+        #[serde(flatten)]
+        pub base: BulletWeaponBaseData,
+        pub heavy_bowgun_unique_bullet_type: i32,
+    }
+}
+
+rsz_struct! {
+    #[rsz("snow.equip.HeavyBowgunBaseUserData")]
+    #[derive(Debug, Serialize)]
+    pub struct HeavyBowgunBaseUserData {
+        pub param: Vec<HeavyBowgunBaseUserDataParam>,
+    }
+}
+
+pub trait WeaponParam {
+    fn main(&self) -> &MainWeaponBaseData;
+    fn close_range(&self) -> Option<&CloseRangeWeaponBaseData>;
+    // (snow.data.DataDef.PlWeaponElementTypes, value)
+    fn elements(&self) -> Vec<(i32, i32)>;
+}
+
+macro_rules! impl_element_weapon {
+    ($name:ty) => {
+        impl WeaponParam for $name {
+            fn main(&self) -> &MainWeaponBaseData {
+                &self.base.base.base
+            }
+            fn close_range(&self) -> Option<&CloseRangeWeaponBaseData> {
+                Some(&self.base.base)
+            }
+            fn elements(&self) -> Vec<(i32, i32)> {
+                vec![(self.base.main_element_type, self.base.main_element_val)]
+            }
+        }
+    };
+}
+
+macro_rules! impl_bullet_weapon {
+    ($name:ty) => {
+        impl WeaponParam for $name {
+            fn main(&self) -> &MainWeaponBaseData {
+                &self.base.base
+            }
+            fn close_range(&self) -> Option<&CloseRangeWeaponBaseData> {
+                None
+            }
+            fn elements(&self) -> Vec<(i32, i32)> {
+                vec![]
+            }
+        }
+    };
+}
+
+impl_element_weapon!(GreatSwordBaseUserDataParam);
+impl_element_weapon!(ShortSwordBaseUserDataParam);
+impl_element_weapon!(LongSwordBaseUserDataParam);
+impl_element_weapon!(HammerBaseUserDataParam);
+impl_element_weapon!(HornBaseUserDataParam);
+impl_element_weapon!(LanceBaseUserDataParam);
+impl_element_weapon!(GunLanceBaseUserDataParam);
+impl_element_weapon!(SlashAxeBaseUserDataParam);
+impl_element_weapon!(ChargeAxeBaseUserDataParam);
+impl_element_weapon!(InsectGlaiveBaseUserDataParam);
+impl_bullet_weapon!(LightBowgunBaseUserDataParam);
+impl_bullet_weapon!(HeavyBowgunBaseUserDataParam);
+
+impl WeaponParam for DualBladesBaseUserDataParam {
+    fn main(&self) -> &MainWeaponBaseData {
+        &self.base.base
+    }
+    fn close_range(&self) -> Option<&CloseRangeWeaponBaseData> {
+        Some(&self.base)
+    }
+    fn elements(&self) -> Vec<(i32, i32)> {
+        vec![
+            (self.main_element_type, self.main_element_val),
+            (self.sub_element_type, self.sub_element_val),
+        ]
+    }
+}
+
+impl WeaponParam for BowBaseUserDataParam {
+    fn main(&self) -> &MainWeaponBaseData {
+        &self.base
+    }
+    fn close_range(&self) -> Option<&CloseRangeWeaponBaseData> {
+        None
+    }
+    fn elements(&self) -> Vec<(i32, i32)> {
+        vec![(self.main_element_type, self.main_element_val)]
+    }
+}
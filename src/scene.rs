@@ -0,0 +1,377 @@
+use crate::pfb::Pfb;
+use crate::rsz::*;
+use crate::scn::Scn;
+use anyhow::*;
+use nalgebra_glm::*;
+use serde::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum SceneNodeKind {
+    Folder,
+    GameObject,
+}
+
+#[derive(Serialize)]
+pub struct SceneComponent {
+    pub type_hash: u32,
+    pub type_name: Option<&'static str>,
+    #[serde(skip)]
+    pub value: Option<Box<dyn Any>>,
+}
+
+impl SceneComponent {
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.value.as_ref()?.downcast_ref()
+    }
+}
+
+#[derive(Serialize)]
+pub struct SceneNode {
+    pub kind: SceneNodeKind,
+    pub guid: Option<Guid>,
+    pub name: Option<String>,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub transform: Option<ViaTransform>,
+    pub prefab: Option<String>,
+    pub components: Vec<SceneComponent>,
+}
+
+impl SceneNode {
+    pub fn component<T: 'static>(&self) -> Option<&T> {
+        self.components.iter().find_map(|c| c.get())
+    }
+}
+
+#[derive(Serialize)]
+pub struct Scene {
+    pub nodes: Vec<SceneNode>,
+    pub roots: Vec<usize>,
+}
+
+struct RawNode {
+    kind: SceneNodeKind,
+    guid: Option<Guid>,
+    object_index: i32,
+    parent_index: i32,
+    component_count: usize,
+    prefab: Option<String>,
+}
+
+fn take_object(
+    rsz: &Rsz,
+    instances: &mut [RszInstance],
+    object_index: usize,
+) -> Result<(u32, Option<Box<dyn Any>>)> {
+    let instance_index = *rsz
+        .roots
+        .get(object_index)
+        .with_context(|| format!("Object index {} out of bound", object_index))?;
+    let instance = instances
+        .get_mut(usize::try_from(instance_index)?)
+        .context("Instance index out of bound")?;
+    Ok((instance.hash, instance.value.take()))
+}
+
+impl Scene {
+    fn build(rsz: &Rsz, raw_nodes: Vec<RawNode>) -> Result<Scene> {
+        let mut instances = rsz.deserialize_instances()?;
+
+        let object_map: HashMap<i32, usize> = raw_nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.object_index, i))
+            .collect();
+
+        let mut nodes = vec![];
+        for raw in &raw_nodes {
+            let object_index = usize::try_from(raw.object_index)?;
+            let (_, value) = take_object(rsz, &mut instances, object_index)?;
+            let name = match raw.kind {
+                SceneNodeKind::GameObject => value
+                    .and_then(|v| v.downcast::<ViaGameObject>().ok())
+                    .map(|o| o.name),
+                SceneNodeKind::Folder => value
+                    .and_then(|v| v.downcast::<ViaFolder>().ok())
+                    .map(|o| o.name),
+            };
+
+            let mut transform = None;
+            let mut components = vec![];
+            for component_index in object_index + 1..=object_index + raw.component_count {
+                let (type_hash, value) = take_object(rsz, &mut instances, component_index)?;
+                let value = match value.map(|v| v.downcast::<ViaTransform>()) {
+                    Some(Ok(t)) => {
+                        transform = Some(*t.clone());
+                        Some(t as Box<dyn Any>)
+                    }
+                    Some(Err(v)) => Some(v),
+                    None => None,
+                };
+                components.push(SceneComponent {
+                    type_hash,
+                    type_name: rsz_type_name(type_hash),
+                    value,
+                })
+            }
+
+            let parent =
+                if raw.parent_index < 0 {
+                    None
+                } else {
+                    Some(*object_map.get(&raw.parent_index).with_context(|| {
+                        format!("Unknown parent object index {}", raw.parent_index)
+                    })?)
+                };
+
+            nodes.push(SceneNode {
+                kind: raw.kind,
+                guid: raw.guid,
+                name,
+                parent,
+                children: vec![],
+                transform,
+                prefab: raw.prefab.clone(),
+                components,
+            })
+        }
+
+        let parents: Vec<_> = nodes.iter().map(|node| node.parent).collect();
+        let mut roots = vec![];
+        for (i, parent) in parents.into_iter().enumerate() {
+            if let Some(parent) = parent {
+                nodes[parent].children.push(i);
+            } else {
+                roots.push(i);
+            }
+        }
+
+        Ok(Scene { nodes, roots })
+    }
+
+    pub fn from_scn(scn: &Scn) -> Result<Scene> {
+        let folders = scn.folders.iter().map(|folder| RawNode {
+            kind: SceneNodeKind::Folder,
+            guid: None,
+            object_index: folder.object_index,
+            parent_index: folder.parent_index,
+            component_count: 0,
+            prefab: None,
+        });
+        let game_objects = scn
+            .game_objects
+            .iter()
+            .map(|object| {
+                let prefab = if object.prefab_index < 0 {
+                    None
+                } else {
+                    Some(
+                        scn.resource_b_names
+                            .get(usize::try_from(object.prefab_index)?)
+                            .context("Prefab index out of bound")?
+                            .clone(),
+                    )
+                };
+                Ok(RawNode {
+                    kind: SceneNodeKind::GameObject,
+                    guid: Some(object.guid),
+                    object_index: object.object_index,
+                    parent_index: object.parent_index,
+                    component_count: usize::from(object.component_count),
+                    prefab,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Scene::build(&scn.rsz, folders.chain(game_objects).collect())
+    }
+
+    pub fn from_pfb(pfb: &Pfb) -> Result<Scene> {
+        let game_objects = pfb
+            .game_objects
+            .iter()
+            .map(|object| {
+                Ok(RawNode {
+                    kind: SceneNodeKind::GameObject,
+                    guid: None,
+                    object_index: object.object_index,
+                    parent_index: object.parent_index,
+                    component_count: usize::try_from(object.component_count)?,
+                    prefab: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Scene::build(&pfb.rsz, game_objects)
+    }
+
+    pub fn path(&self, index: usize) -> String {
+        let node = &self.nodes[index];
+        let name = node.name.as_deref().unwrap_or("?");
+        match node.parent {
+            Some(parent) => format!("{}/{}", self.path(parent), name),
+            None => name.to_owned(),
+        }
+    }
+
+    /// Finds a node by its slash-separated name path from a root
+    pub fn find(&self, path: &str) -> Option<usize> {
+        let mut candidates = &self.roots;
+        let mut found = None;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            let index = *candidates
+                .iter()
+                .find(|&&i| self.nodes[i].name.as_deref() == Some(name))?;
+            found = Some(index);
+            candidates = &self.nodes[index].children;
+        }
+        found
+    }
+
+    /// Composes the transforms from the root down to the node. Nodes without a transform
+    /// are treated as identity.
+    pub fn world_position(&self, index: usize) -> [f32; 3] {
+        let p = self.world_transform(index) * vec4(0.0, 0.0, 0.0, 1.0);
+        [p.x, p.y, p.z]
+    }
+
+    fn world_transform(&self, index: usize) -> Mat4 {
+        let node = &self.nodes[index];
+        let local = match &node.transform {
+            Some(t) => {
+                let (p, r, s) = (t.local_position, t.local_rotation, t.local_scale);
+                translation(&vec3(p.x, p.y, p.z))
+                    * quat_to_mat4(&quat(r.x, r.y, r.z, r.w))
+                    * scaling(&vec3(s.x, s.y, s.z))
+            }
+            None => Mat4::identity(),
+        };
+        match node.parent {
+            Some(parent) => self.world_transform(parent) * local,
+            None => local,
+        }
+    }
+
+    pub fn dump_tree(&self) {
+        fn dump_node(scene: &Scene, index: usize, depth: usize) {
+            let node = &scene.nodes[index];
+            print!(
+                "{}{:?} {}",
+                "  ".repeat(depth),
+                node.kind,
+                node.name.as_deref().unwrap_or("?")
+            );
+            if let Some(prefab) = &node.prefab {
+                print!(" <{}>", prefab);
+            }
+            if let Some(transform) = &node.transform {
+                let p = transform.local_position;
+                print!(" @ ({}, {}, {})", p.x, p.y, p.z);
+            }
+            println!();
+            for component in &node.components {
+                match component.type_name {
+                    Some(name) => println!("{}  - {}", "  ".repeat(depth), name),
+                    None => println!("{}  - {:08X}", "  ".repeat(depth), component.type_hash),
+                }
+            }
+            for &child in &node.children {
+                dump_node(scene, child, depth + 1);
+            }
+        }
+
+        for &root in &self.roots {
+            dump_node(self, root, 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash_as_utf8;
+    use std::io::Cursor;
+
+    const UNKNOWN_TYPE: u32 = 0x1234ABCD;
+
+    fn push_u32(buf: &mut Vec<u8>, v: u32) {
+        buf.extend_from_slice(&v.to_le_bytes())
+    }
+
+    fn push_u64(buf: &mut Vec<u8>, v: u64) {
+        buf.extend_from_slice(&v.to_le_bytes())
+    }
+
+    fn push_rsz_string(buf: &mut Vec<u8>, s: &str) {
+        let utf16: Vec<u16> = s.encode_utf16().chain(std::iter::once(0)).collect();
+        push_u32(buf, utf16.len() as u32);
+        for c in utf16 {
+            buf.extend_from_slice(&c.to_le_bytes());
+        }
+        while buf.len() % 4 != 0 {
+            buf.push(0)
+        }
+    }
+
+    // One game object "Root" with a single component of an unregistered type
+    fn scn_fixture() -> Vec<u8> {
+        let mut data = vec![];
+        push_rsz_string(&mut data, "Root");
+        push_rsz_string(&mut data, "");
+        data.extend_from_slice(&[1, 1, 0, 0]);
+        data.extend_from_slice(&1.0f32.to_le_bytes());
+        data.extend_from_slice(&[0xAA; 12]);
+
+        let mut rsz = vec![];
+        rsz.extend_from_slice(b"RSZ\0");
+        push_u32(&mut rsz, 0x10);
+        push_u32(&mut rsz, 2); // roots
+        push_u32(&mut rsz, 3); // type descriptors
+        push_u32(&mut rsz, 0); // strings
+        push_u32(&mut rsz, 0);
+        push_u64(&mut rsz, 56); // type descriptor offset
+        push_u64(&mut rsz, 80); // data offset
+        push_u64(&mut rsz, 80); // string table offset
+        push_u32(&mut rsz, 1);
+        push_u32(&mut rsz, 2);
+        push_u64(&mut rsz, 0);
+        push_u64(&mut rsz, u64::from(hash_as_utf8("via.GameObject")));
+        push_u64(&mut rsz, u64::from(UNKNOWN_TYPE));
+        rsz.extend(data);
+
+        let mut scn = vec![];
+        scn.extend_from_slice(b"SCN\0");
+        push_u32(&mut scn, 1); // game objects
+        for _ in 0..4 {
+            push_u32(&mut scn, 0);
+        }
+        for _ in 0..5 {
+            push_u64(&mut scn, 96);
+        }
+        scn.extend_from_slice(&[0x11; 16]);
+        push_u32(&mut scn, 0); // object index
+        push_u32(&mut scn, 0xFFFFFFFF); // parent index
+        scn.extend_from_slice(&[1, 0, 0, 0]); // component count
+        push_u32(&mut scn, 0xFFFFFFFF); // prefab index
+        assert_eq!(scn.len(), 96);
+        scn.extend(rsz);
+        scn
+    }
+
+    #[test]
+    fn unknown_component() {
+        let scn = Scn::new(Cursor::new(scn_fixture())).unwrap();
+        assert!(scn.rsz.deserialize().is_err());
+
+        let scene = Scene::from_scn(&scn).unwrap();
+        assert_eq!(scene.roots, vec![0]);
+        let node = &scene.nodes[0];
+        assert_eq!(node.name.as_deref(), Some("Root"));
+        assert!(node.transform.is_none());
+        assert_eq!(node.components.len(), 1);
+        assert_eq!(node.components[0].type_hash, UNKNOWN_TYPE);
+        assert!(node.components[0].type_name.is_none());
+        assert!(node.components[0].value.is_none());
+    }
+}
@@ -1,11 +1,28 @@
 use crate::file_ext::*;
-use crate::rsz::Rsz;
+use crate::rsz::{Guid, Rsz};
 use crate::user::UserChild;
 use anyhow::*;
 use std::io::{Read, Seek};
 
+#[derive(Debug)]
+pub struct ScnGameObject {
+    pub guid: Guid,
+    pub object_index: i32,
+    pub parent_index: i32,
+    pub component_count: u16,
+    pub prefab_index: i32,
+}
+
+#[derive(Debug)]
+pub struct ScnFolder {
+    pub object_index: i32,
+    pub parent_index: i32,
+}
+
 #[derive(Debug)]
 pub struct Scn {
+    pub game_objects: Vec<ScnGameObject>,
+    pub folders: Vec<ScnFolder>,
     pub resource_a_names: Vec<String>,
     pub resource_b_names: Vec<String>,
     pub children: Vec<UserChild>,
@@ -30,27 +47,35 @@ impl Scn {
         let child_list_offset = file.read_u64()?;
         let rsz_offset = file.read_u64()?;
 
-        let _ = (0..nergigante_count)
+        let game_objects = (0..nergigante_count)
             .map(|_| {
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                file.read_u32()?;
-                Ok(())
+                let mut guid = Guid { bytes: [0; 16] };
+                file.read_exact(&mut guid.bytes)?;
+                let object_index = file.read_i32()?;
+                let parent_index = file.read_i32()?;
+                let component_count = file.read_u16()?;
+                let _ = file.read_u16()?;
+                let prefab_index = file.read_i32()?;
+                Ok(ScnGameObject {
+                    guid,
+                    object_index,
+                    parent_index,
+                    component_count,
+                    prefab_index,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
 
         file.seek_noop(velkhana_offset)
             .context("Undisconvered data before velkhana list")?;
-        let _ = (0..velkhana_count)
+        let folders = (0..velkhana_count)
             .map(|_| {
-                file.read_u32()?;
-                file.read_u32()?;
-                Ok(())
+                let object_index = file.read_i32()?;
+                let parent_index = file.read_i32()?;
+                Ok(ScnFolder {
+                    object_index,
+                    parent_index,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -122,6 +147,8 @@ impl Scn {
         let rsz = Rsz::new(file, rsz_offset)?;
 
         Ok(Scn {
+            game_objects,
+            folders,
             resource_a_names,
             resource_b_names,
             children,
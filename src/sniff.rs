@@ -0,0 +1,121 @@
+use serde::*;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FileType {
+    User,
+    Pfb,
+    Scn,
+    Rsz,
+    Tex,
+    Mesh,
+    Rcol,
+    Msg,
+    Gui,
+    Uvs,
+    Tdb,
+    Mdf,
+    Motlist,
+    Mot,
+    Motbank,
+    Bhvt,
+    Fsm,
+    Efx,
+    Chain,
+    Clip,
+    Sound,
+    SoundPackage,
+    Wem,
+    Dds,
+    Png,
+    Text,
+    Unknown,
+}
+
+// Magic numbers found either at the start of the file, or after a 4-byte version field
+const MAGIC_LIST: &[(&[u8; 4], FileType)] = &[
+    (b"USR\0", FileType::User),
+    (b"PFB\0", FileType::Pfb),
+    (b"SCN\0", FileType::Scn),
+    (b"RSZ\0", FileType::Rsz),
+    (b"TEX\0", FileType::Tex),
+    (b"MESH", FileType::Mesh),
+    (b"RCOL", FileType::Rcol),
+    (b"GMSG", FileType::Msg),
+    (b"GUIR", FileType::Gui),
+    (b".SVU", FileType::Uvs),
+    (b"TDB\0", FileType::Tdb),
+    (b"MDF\0", FileType::Mdf),
+    (b"mlst", FileType::Motlist),
+    (b"mot ", FileType::Mot),
+    (b"mbnk", FileType::Motbank),
+    (b"BHVT", FileType::Bhvt),
+    (b"FSMR", FileType::Fsm),
+    (b"EFXR", FileType::Efx),
+    (b"CHN\0", FileType::Chain),
+    (b"CLIP", FileType::Clip),
+    (b"BKHD", FileType::Sound),
+    (b"AKPK", FileType::SoundPackage),
+    (b"RIFF", FileType::Wem),
+    (b"DDS ", FileType::Dds),
+    (b"\x89PNG", FileType::Png),
+];
+
+impl FileType {
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::User => "user",
+            FileType::Pfb => "pfb",
+            FileType::Scn => "scn",
+            FileType::Rsz => "rsz",
+            FileType::Tex => "tex",
+            FileType::Mesh => "mesh",
+            FileType::Rcol => "rcol",
+            FileType::Msg => "msg",
+            FileType::Gui => "gui",
+            FileType::Uvs => "uvs",
+            FileType::Tdb => "tdb",
+            FileType::Mdf => "mdf2",
+            FileType::Motlist => "motlist",
+            FileType::Mot => "mot",
+            FileType::Motbank => "motbank",
+            FileType::Bhvt => "bhvt",
+            FileType::Fsm => "fsm",
+            FileType::Efx => "efx",
+            FileType::Chain => "chain",
+            FileType::Clip => "clip",
+            FileType::Sound => "bnk",
+            FileType::SoundPackage => "pck",
+            FileType::Wem => "wem",
+            FileType::Dds => "dds",
+            FileType::Png => "png",
+            FileType::Text => "txt",
+            FileType::Unknown => "unknown",
+        }
+    }
+}
+
+fn is_text(file: &[u8]) -> bool {
+    if file.starts_with(b"\xEF\xBB\xBF") || file.starts_with(b"\xFF\xFE") {
+        return true;
+    }
+    let sample = &file[0..file.len().min(0x400)];
+    !sample.is_empty()
+        && sample
+            .iter()
+            .all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+}
+
+/// Guesses the file type from the content
+pub fn sniff(file: &[u8]) -> FileType {
+    for &offset in &[0, 4] {
+        if let Some(magic) = file.get(offset..offset + 4) {
+            if let Some(&(_, file_type)) = MAGIC_LIST.iter().find(|(m, _)| &m[..] == magic) {
+                return file_type;
+            }
+        }
+    }
+    if is_text(file) {
+        return FileType::Text;
+    }
+    FileType::Unknown
+}
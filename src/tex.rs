@@ -418,6 +418,15 @@ impl Tex {
         ))
     }
 
+    /// Picks the largest mipmap that is no wider or taller than `max_size`,
+    /// or the smallest available one
+    pub fn mipmap_for_size(&self, max_size: u16) -> usize {
+        let mipmap_count = self.textures.first().map_or(1, |mipmaps| mipmaps.len());
+        (0..mipmap_count)
+            .find(|&mipmap| self.width >> mipmap <= max_size && self.height >> mipmap <= max_size)
+            .unwrap_or(mipmap_count.saturating_sub(1))
+    }
+
     pub fn save_png(&self, index: usize, mipmap: usize, output: &Path) -> anyhow::Result<()> {
         self.to_rgba(index, mipmap)?.save_png(output)?;
 